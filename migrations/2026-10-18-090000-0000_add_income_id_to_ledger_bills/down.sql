@@ -0,0 +1,2 @@
+ALTER TABLE ledger_bills
+DROP COLUMN income_id;
@@ -0,0 +1,3 @@
+-- Paycheck allocation: which income covers this ledger bill
+ALTER TABLE ledger_bills
+ADD COLUMN income_id INTEGER REFERENCES incomes(id) ON DELETE SET NULL;
@@ -0,0 +1,10 @@
+CREATE TABLE ledger_reconciliations (
+  id SERIAL PRIMARY KEY,
+  ledger_id INTEGER NOT NULL REFERENCES ledgers(id) ON DELETE CASCADE,
+  date DATE NOT NULL,
+  actual_balance NUMERIC NOT NULL,
+  expected_balance NUMERIC NOT NULL,
+  delta NUMERIC GENERATED ALWAYS AS (actual_balance - expected_balance) STORED,
+  notes TEXT,
+  created_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,3 @@
+DROP TRIGGER IF EXISTS trigger_ledger_bill_payments_paid_status ON ledger_bill_payments;
+DROP FUNCTION IF EXISTS sync_ledger_bill_paid_status();
+DROP TABLE ledger_bill_payments;
@@ -0,0 +1,37 @@
+CREATE TABLE ledger_bill_payments (
+  id SERIAL PRIMARY KEY,
+  ledger_bill_id INTEGER NOT NULL REFERENCES ledger_bills(id) ON DELETE CASCADE,
+  date DATE NOT NULL,
+  amount NUMERIC NOT NULL,
+  notes TEXT,
+  created_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+-- Function to mark a ledger bill paid once its payments cover the amount
+CREATE OR REPLACE FUNCTION sync_ledger_bill_paid_status()
+RETURNS TRIGGER AS $$
+DECLARE
+    target_ledger_bill_id INTEGER;
+BEGIN
+    IF TG_OP = 'DELETE' THEN
+        target_ledger_bill_id := OLD.ledger_bill_id;
+    ELSE
+        target_ledger_bill_id := NEW.ledger_bill_id;
+    END IF;
+
+    UPDATE ledger_bills
+    SET is_payed = COALESCE((
+        SELECT SUM(amount)
+        FROM ledger_bill_payments
+        WHERE ledger_bill_id = target_ledger_bill_id
+    ), 0) >= amount
+    WHERE id = target_ledger_bill_id;
+
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trigger_ledger_bill_payments_paid_status
+AFTER INSERT OR UPDATE OR DELETE ON ledger_bill_payments
+FOR EACH ROW
+EXECUTE FUNCTION sync_ledger_bill_paid_status();
@@ -0,0 +1,3 @@
+ALTER TABLE ledgers
+    DROP COLUMN closed_at,
+    DROP COLUMN is_closed;
@@ -0,0 +1,4 @@
+-- Closed ledgers are locked against changes until reopened
+ALTER TABLE ledgers
+    ADD COLUMN is_closed BOOLEAN NOT NULL DEFAULT FALSE,
+    ADD COLUMN closed_at TIMESTAMP;
@@ -0,0 +1,2 @@
+ALTER TABLE ledgers
+DROP COLUMN previous_ledger_id;
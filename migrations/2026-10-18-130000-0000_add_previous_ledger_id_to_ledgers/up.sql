@@ -0,0 +1,3 @@
+-- Link a ledger to its predecessor so opening balances can carry forward
+ALTER TABLE ledgers
+ADD COLUMN previous_ledger_id INTEGER REFERENCES ledgers(id) ON DELETE SET NULL;
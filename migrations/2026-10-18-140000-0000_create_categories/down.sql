@@ -0,0 +1,4 @@
+ALTER TABLE bills
+DROP COLUMN category_id;
+
+DROP TABLE categories;
@@ -0,0 +1,11 @@
+CREATE TABLE categories (
+    id SERIAL PRIMARY KEY,
+    name VARCHAR NOT NULL,
+    parent_id INTEGER REFERENCES categories(id) ON DELETE SET NULL,
+    monthly_budget NUMERIC(10, 2),
+    created_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+-- Ledger bills inherit the category of their bill
+ALTER TABLE bills
+ADD COLUMN category_id INTEGER REFERENCES categories(id) ON DELETE SET NULL;
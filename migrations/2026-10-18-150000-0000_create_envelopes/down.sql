@@ -0,0 +1,5 @@
+ALTER TABLE bills
+DROP COLUMN envelope_id;
+
+DROP TABLE ledger_envelopes;
+DROP TABLE envelopes;
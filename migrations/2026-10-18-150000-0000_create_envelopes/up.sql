@@ -0,0 +1,22 @@
+CREATE TABLE envelopes (
+    id SERIAL PRIMARY KEY,
+    name VARCHAR NOT NULL,
+    notes TEXT,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+-- Funds a ledger gives to each envelope; unspent funds roll into the next ledger
+CREATE TABLE ledger_envelopes (
+    id SERIAL PRIMARY KEY,
+    ledger_id INTEGER NOT NULL REFERENCES ledgers(id) ON DELETE CASCADE,
+    envelope_id INTEGER NOT NULL REFERENCES envelopes(id) ON DELETE CASCADE,
+    allocated NUMERIC(10, 2) NOT NULL DEFAULT 0,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    UNIQUE (ledger_id, envelope_id)
+);
+
+CREATE INDEX idx_ledger_envelopes_ledger_id ON ledger_envelopes(ledger_id);
+
+-- Paid ledger bills draw down their bill's envelope
+ALTER TABLE bills
+ADD COLUMN envelope_id INTEGER REFERENCES envelopes(id) ON DELETE SET NULL;
@@ -0,0 +1,84 @@
+CREATE TABLE savings_goals (
+    id SERIAL PRIMARY KEY,
+    name VARCHAR NOT NULL,
+    target_amount NUMERIC(10, 2) NOT NULL,
+    target_date DATE NOT NULL,
+    bill_id INTEGER REFERENCES bills(id) ON DELETE SET NULL,
+    notes TEXT,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+-- Set-asides for a goal within a ledger
+CREATE TABLE ledger_savings_contributions (
+    id SERIAL PRIMARY KEY,
+    ledger_id INTEGER NOT NULL REFERENCES ledgers(id) ON DELETE CASCADE,
+    savings_goal_id INTEGER NOT NULL REFERENCES savings_goals(id) ON DELETE CASCADE,
+    amount NUMERIC(10, 2) NOT NULL,
+    is_transferred BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    UNIQUE (ledger_id, savings_goal_id)
+);
+
+CREATE INDEX idx_ledger_savings_contributions_ledger_id ON ledger_savings_contributions(ledger_id);
+
+-- Contributions count toward ledger expenses alongside bills
+CREATE OR REPLACE FUNCTION recalculate_ledger_totals()
+RETURNS TRIGGER AS $$
+DECLARE
+    target_ledger_id INTEGER;
+BEGIN
+    -- Determine which ledger(s) to update
+    IF TG_OP = 'DELETE' THEN
+        target_ledger_id := OLD.ledger_id;
+    ELSE
+        target_ledger_id := NEW.ledger_id;
+    END IF;
+
+    -- Update the ledger totals
+    UPDATE ledgers
+    SET
+        income = COALESCE((
+            SELECT SUM(amount)
+            FROM incomes
+            WHERE ledger_id = target_ledger_id
+        ), 0),
+        expenses = COALESCE((
+            SELECT SUM(amount)
+            FROM ledger_bills
+            WHERE ledger_id = target_ledger_id
+        ), 0) + COALESCE((
+            SELECT SUM(amount)
+            FROM ledger_savings_contributions
+            WHERE ledger_id = target_ledger_id
+        ), 0)
+    WHERE id = target_ledger_id;
+
+    -- If UPDATE changed ledger_id, recalculate old ledger too
+    IF TG_OP = 'UPDATE' AND OLD.ledger_id IS DISTINCT FROM NEW.ledger_id THEN
+        UPDATE ledgers
+        SET
+            income = COALESCE((
+                SELECT SUM(amount)
+                FROM incomes
+                WHERE ledger_id = OLD.ledger_id
+            ), 0),
+            expenses = COALESCE((
+                SELECT SUM(amount)
+                FROM ledger_bills
+                WHERE ledger_id = OLD.ledger_id
+            ), 0) + COALESCE((
+                SELECT SUM(amount)
+                FROM ledger_savings_contributions
+                WHERE ledger_id = OLD.ledger_id
+            ), 0)
+        WHERE id = OLD.ledger_id;
+    END IF;
+
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trigger_ledger_savings_contributions_totals
+AFTER INSERT OR UPDATE OR DELETE ON ledger_savings_contributions
+FOR EACH ROW
+EXECUTE FUNCTION recalculate_ledger_totals();
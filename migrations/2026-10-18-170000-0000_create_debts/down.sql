@@ -0,0 +1,5 @@
+DROP TRIGGER IF EXISTS trigger_ledger_bills_debt_payment_delete ON ledger_bills;
+DROP TRIGGER IF EXISTS trigger_ledger_bills_debt_payment ON ledger_bills;
+DROP FUNCTION IF EXISTS apply_debt_payment();
+DROP TABLE debt_payments;
+DROP TABLE debts;
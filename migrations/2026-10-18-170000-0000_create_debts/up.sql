@@ -0,0 +1,78 @@
+CREATE TABLE debts (
+    id SERIAL PRIMARY KEY,
+    name VARCHAR NOT NULL,
+    balance NUMERIC(12, 2) NOT NULL,
+    apr NUMERIC(6, 3) NOT NULL DEFAULT 0,
+    minimum_payment NUMERIC(10, 2) NOT NULL,
+    bill_id INTEGER REFERENCES bills(id) ON DELETE SET NULL,
+    notes TEXT,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+-- Interest/principal split applied to a debt when its ledger bill was paid
+CREATE TABLE debt_payments (
+    id SERIAL PRIMARY KEY,
+    debt_id INTEGER NOT NULL REFERENCES debts(id) ON DELETE CASCADE,
+    ledger_bill_id INTEGER NOT NULL UNIQUE REFERENCES ledger_bills(id) ON DELETE CASCADE,
+    amount NUMERIC(10, 2) NOT NULL,
+    interest NUMERIC(10, 2) NOT NULL,
+    principal NUMERIC(10, 2) NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_debt_payments_debt_id ON debt_payments(debt_id);
+
+-- Function to pay down a debt when its linked ledger bill is marked paid
+CREATE OR REPLACE FUNCTION apply_debt_payment()
+RETURNS TRIGGER AS $$
+DECLARE
+    existing_payment debt_payments%ROWTYPE;
+    target_debt debts%ROWTYPE;
+    interest_due NUMERIC;
+    principal_paid NUMERIC;
+BEGIN
+    -- Undo whatever this ledger bill applied before
+    IF TG_OP IN ('UPDATE', 'DELETE') THEN
+        SELECT * INTO existing_payment FROM debt_payments WHERE ledger_bill_id = OLD.id;
+        IF FOUND THEN
+            UPDATE debts
+            SET balance = balance + existing_payment.principal
+            WHERE id = existing_payment.debt_id;
+
+            DELETE FROM debt_payments WHERE id = existing_payment.id;
+        END IF;
+    END IF;
+
+    IF TG_OP = 'DELETE' THEN
+        RETURN OLD;
+    END IF;
+
+    IF NEW.is_payed THEN
+        SELECT * INTO target_debt FROM debts WHERE bill_id = NEW.bill_id ORDER BY id LIMIT 1;
+        IF FOUND THEN
+            -- One month of interest comes out of the payment first
+            interest_due := ROUND(target_debt.balance * target_debt.apr / 1200, 2);
+            principal_paid := LEAST(GREATEST(NEW.amount - interest_due, 0), target_debt.balance);
+
+            UPDATE debts
+            SET balance = balance - principal_paid
+            WHERE id = target_debt.id;
+
+            INSERT INTO debt_payments (debt_id, ledger_bill_id, amount, interest, principal)
+            VALUES (target_debt.id, NEW.id, NEW.amount, LEAST(interest_due, NEW.amount), principal_paid);
+        END IF;
+    END IF;
+
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trigger_ledger_bills_debt_payment
+AFTER INSERT OR UPDATE OF is_payed, amount ON ledger_bills
+FOR EACH ROW
+EXECUTE FUNCTION apply_debt_payment();
+
+CREATE TRIGGER trigger_ledger_bills_debt_payment_delete
+BEFORE DELETE ON ledger_bills
+FOR EACH ROW
+EXECUTE FUNCTION apply_debt_payment();
@@ -0,0 +1,12 @@
+-- Amortizing loan terms for an installment bill (mortgage, car loan)
+-- start_date is the due date of the first payment
+CREATE TABLE loans (
+    id SERIAL PRIMARY KEY,
+    bill_id INTEGER NOT NULL UNIQUE REFERENCES bills(id) ON DELETE CASCADE,
+    principal NUMERIC(12, 2) NOT NULL,
+    annual_rate NUMERIC(6, 3) NOT NULL DEFAULT 0,
+    term_months INTEGER NOT NULL CHECK (term_months > 0),
+    start_date DATE NOT NULL,
+    notes TEXT,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,3 @@
+ALTER TABLE ledgers DROP COLUMN account_id;
+DROP TABLE account_balances;
+DROP TABLE accounts;
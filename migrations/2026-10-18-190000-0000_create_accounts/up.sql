@@ -0,0 +1,24 @@
+-- Accounts tracked for net worth; credit cards and loans are liabilities
+CREATE TABLE accounts (
+    id SERIAL PRIMARY KEY,
+    name VARCHAR NOT NULL,
+    account_type VARCHAR NOT NULL DEFAULT 'Checking'
+        CHECK (account_type IN ('Checking', 'Savings', 'Brokerage', 'Retirement', 'Property', 'Credit Card', 'Loan')),
+    notes TEXT,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+-- Dated balance snapshots; liabilities are stored as the positive amount owed
+CREATE TABLE account_balances (
+    id SERIAL PRIMARY KEY,
+    account_id INTEGER NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    date DATE NOT NULL,
+    balance NUMERIC(12, 2) NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    UNIQUE (account_id, date)
+);
+
+CREATE INDEX idx_account_balances_account_id ON account_balances(account_id);
+
+-- The account a ledger draws from
+ALTER TABLE ledgers ADD COLUMN account_id INTEGER REFERENCES accounts(id) ON DELETE SET NULL;
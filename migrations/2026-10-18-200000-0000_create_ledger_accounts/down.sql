@@ -0,0 +1,4 @@
+ALTER TABLE incomes DROP COLUMN account_id;
+ALTER TABLE ledger_bills DROP COLUMN account_id;
+DROP TABLE ledger_transfers;
+DROP TABLE ledger_accounts;
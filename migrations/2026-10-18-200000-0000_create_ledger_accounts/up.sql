@@ -0,0 +1,28 @@
+-- Per-account opening balances for a ledger
+CREATE TABLE ledger_accounts (
+    id SERIAL PRIMARY KEY,
+    ledger_id INTEGER NOT NULL REFERENCES ledgers(id) ON DELETE CASCADE,
+    account_id INTEGER NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    opening_balance NUMERIC(12, 2) NOT NULL DEFAULT 0,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    UNIQUE (ledger_id, account_id)
+);
+
+-- Money moved between accounts within a ledger (nets to zero across the ledger)
+CREATE TABLE ledger_transfers (
+    id SERIAL PRIMARY KEY,
+    ledger_id INTEGER NOT NULL REFERENCES ledgers(id) ON DELETE CASCADE,
+    from_account_id INTEGER NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    to_account_id INTEGER NOT NULL REFERENCES accounts(id) ON DELETE CASCADE,
+    amount NUMERIC(10, 2) NOT NULL CHECK (amount > 0),
+    date DATE NOT NULL,
+    notes TEXT,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    CHECK (from_account_id <> to_account_id)
+);
+
+CREATE INDEX idx_ledger_transfers_ledger_id ON ledger_transfers(ledger_id);
+
+-- Which account a bill is paid from or an income lands in; NULL means the ledger's account
+ALTER TABLE ledger_bills ADD COLUMN account_id INTEGER REFERENCES accounts(id) ON DELETE SET NULL;
+ALTER TABLE incomes ADD COLUMN account_id INTEGER REFERENCES accounts(id) ON DELETE SET NULL;
@@ -0,0 +1,2 @@
+DROP TABLE card_charges;
+DROP TABLE credit_cards;
@@ -0,0 +1,22 @@
+-- Statement cycle for a credit card account; the statement balance is paid through the linked bill
+CREATE TABLE credit_cards (
+    id SERIAL PRIMARY KEY,
+    account_id INTEGER NOT NULL UNIQUE REFERENCES accounts(id) ON DELETE CASCADE,
+    bill_id INTEGER UNIQUE REFERENCES bills(id) ON DELETE SET NULL,
+    closing_day INTEGER NOT NULL CHECK (closing_day BETWEEN 1 AND 31),
+    due_day INTEGER NOT NULL CHECK (due_day BETWEEN 1 AND 31),
+    notes TEXT,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+-- Charges posted to a card; refunds and credits are negative
+CREATE TABLE card_charges (
+    id SERIAL PRIMARY KEY,
+    credit_card_id INTEGER NOT NULL REFERENCES credit_cards(id) ON DELETE CASCADE,
+    date DATE NOT NULL,
+    amount NUMERIC(12, 2) NOT NULL,
+    description VARCHAR NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_card_charges_credit_card_id ON card_charges(credit_card_id);
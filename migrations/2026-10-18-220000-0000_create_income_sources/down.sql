@@ -0,0 +1,4 @@
+ALTER TABLE incomes DROP CONSTRAINT incomes_source_date_unique;
+ALTER TABLE incomes DROP COLUMN is_expected;
+ALTER TABLE incomes DROP COLUMN income_source_id;
+DROP TABLE income_sources;
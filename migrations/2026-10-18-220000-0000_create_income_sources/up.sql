@@ -0,0 +1,20 @@
+-- Where income comes from and when it's paid; the anchor date is the first pay date
+-- Semi-monthly sources are paid on the anchor's day and on second_day
+CREATE TABLE income_sources (
+    id SERIAL PRIMARY KEY,
+    name VARCHAR NOT NULL,
+    schedule VARCHAR NOT NULL DEFAULT 'Monthly'
+        CHECK (schedule IN ('Weekly', 'Bi-Weekly', 'Semi-Monthly', 'Monthly')),
+    anchor_date DATE NOT NULL,
+    second_day INTEGER CHECK (second_day BETWEEN 1 AND 31),
+    expected_amount NUMERIC(12, 2) NOT NULL,
+    account_id INTEGER REFERENCES accounts(id) ON DELETE SET NULL,
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    notes TEXT,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+-- Generated paychecks stay expected until confirmed with the real amount
+ALTER TABLE incomes ADD COLUMN income_source_id INTEGER REFERENCES income_sources(id) ON DELETE SET NULL;
+ALTER TABLE incomes ADD COLUMN is_expected BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE incomes ADD CONSTRAINT incomes_source_date_unique UNIQUE (income_source_id, date);
@@ -0,0 +1 @@
+ALTER TABLE income_sources DROP COLUMN category;
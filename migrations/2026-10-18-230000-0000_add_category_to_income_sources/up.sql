@@ -0,0 +1,3 @@
+-- What kind of income a source pays, for grouping and reports
+ALTER TABLE income_sources ADD COLUMN category VARCHAR NOT NULL DEFAULT 'Salary'
+    CHECK (category IN ('Salary', 'Side Gig', 'Reimbursement', 'Interest', 'Dividends', 'Gift', 'Other'));
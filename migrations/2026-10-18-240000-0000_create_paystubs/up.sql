@@ -0,0 +1,15 @@
+-- Optional paystub breakdown for an income; gross less every deduction equals the income's net amount
+CREATE TABLE paystubs (
+    id SERIAL PRIMARY KEY,
+    income_id INTEGER NOT NULL UNIQUE REFERENCES incomes(id) ON DELETE CASCADE,
+    gross NUMERIC(12, 2) NOT NULL,
+    federal_tax NUMERIC(12, 2) NOT NULL DEFAULT 0,
+    state_tax NUMERIC(12, 2) NOT NULL DEFAULT 0,
+    social_security NUMERIC(12, 2) NOT NULL DEFAULT 0,
+    medicare NUMERIC(12, 2) NOT NULL DEFAULT 0,
+    retirement_401k NUMERIC(12, 2) NOT NULL DEFAULT 0,
+    hsa NUMERIC(12, 2) NOT NULL DEFAULT 0,
+    insurance NUMERIC(12, 2) NOT NULL DEFAULT 0,
+    other_deductions NUMERIC(12, 2) NOT NULL DEFAULT 0,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
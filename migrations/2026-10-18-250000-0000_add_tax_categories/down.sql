@@ -0,0 +1,3 @@
+ALTER TABLE incomes DROP COLUMN tax_category;
+ALTER TABLE ledger_bills DROP COLUMN tax_category;
+ALTER TABLE bills DROP COLUMN tax_category;
@@ -0,0 +1,8 @@
+-- Tax-relevant flags for the annual tax summary
+-- A ledger bill without its own category inherits the bill's
+ALTER TABLE bills ADD COLUMN tax_category VARCHAR
+    CHECK (tax_category IN ('Deductible', 'Charitable', 'Medical', 'HSA-Eligible', 'Business'));
+ALTER TABLE ledger_bills ADD COLUMN tax_category VARCHAR
+    CHECK (tax_category IN ('Deductible', 'Charitable', 'Medical', 'HSA-Eligible', 'Business'));
+ALTER TABLE incomes ADD COLUMN tax_category VARCHAR
+    CHECK (tax_category IN ('Deductible', 'Charitable', 'Medical', 'HSA-Eligible', 'Business'));
@@ -0,0 +1 @@
+DROP TABLE bill_price_changes;
@@ -0,0 +1,11 @@
+-- Dated catalog price changes, recorded whenever a bill's amount is edited
+CREATE TABLE bill_price_changes (
+    id SERIAL PRIMARY KEY,
+    bill_id INTEGER NOT NULL REFERENCES bills(id) ON DELETE CASCADE,
+    changed_on DATE NOT NULL DEFAULT CURRENT_DATE,
+    old_amount NUMERIC(12, 2) NOT NULL,
+    new_amount NUMERIC(12, 2) NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_bill_price_changes_bill_id ON bill_price_changes(bill_id);
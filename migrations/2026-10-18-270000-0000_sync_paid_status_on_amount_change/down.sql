@@ -0,0 +1,25 @@
+DROP TRIGGER IF EXISTS trigger_ledger_bills_amount_paid_status ON ledger_bills;
+DROP FUNCTION IF EXISTS sync_ledger_bill_paid_status_on_amount();
+
+CREATE OR REPLACE FUNCTION sync_ledger_bill_paid_status()
+RETURNS TRIGGER AS $$
+DECLARE
+    target_ledger_bill_id INTEGER;
+BEGIN
+    IF TG_OP = 'DELETE' THEN
+        target_ledger_bill_id := OLD.ledger_bill_id;
+    ELSE
+        target_ledger_bill_id := NEW.ledger_bill_id;
+    END IF;
+
+    UPDATE ledger_bills
+    SET is_payed = COALESCE((
+        SELECT SUM(amount)
+        FROM ledger_bill_payments
+        WHERE ledger_bill_id = target_ledger_bill_id
+    ), 0) >= amount
+    WHERE id = target_ledger_bill_id;
+
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
@@ -0,0 +1,49 @@
+-- Bills without payments keep the paid flag they were given by hand
+CREATE OR REPLACE FUNCTION sync_ledger_bill_paid_status()
+RETURNS TRIGGER AS $$
+DECLARE
+    target_ledger_bill_id INTEGER;
+BEGIN
+    IF TG_OP = 'DELETE' THEN
+        target_ledger_bill_id := OLD.ledger_bill_id;
+    ELSE
+        target_ledger_bill_id := NEW.ledger_bill_id;
+    END IF;
+
+    UPDATE ledger_bills
+    SET is_payed = paid.total >= ledger_bills.amount
+    FROM (
+        SELECT SUM(amount) AS total
+        FROM ledger_bill_payments
+        WHERE ledger_bill_id = target_ledger_bill_id
+    ) AS paid
+    WHERE ledger_bills.id = target_ledger_bill_id
+      AND paid.total IS NOT NULL;
+
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+-- Function to re-check a ledger bill's payments when its amount changes
+CREATE OR REPLACE FUNCTION sync_ledger_bill_paid_status_on_amount()
+RETURNS TRIGGER AS $$
+DECLARE
+    paid_total NUMERIC;
+BEGIN
+    SELECT SUM(amount) INTO paid_total
+    FROM ledger_bill_payments
+    WHERE ledger_bill_id = NEW.id;
+
+    IF paid_total IS NOT NULL THEN
+        NEW.is_payed := paid_total >= NEW.amount;
+    END IF;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trigger_ledger_bills_amount_paid_status
+BEFORE UPDATE OF amount ON ledger_bills
+FOR EACH ROW
+WHEN (OLD.amount IS DISTINCT FROM NEW.amount)
+EXECUTE FUNCTION sync_ledger_bill_paid_status_on_amount();
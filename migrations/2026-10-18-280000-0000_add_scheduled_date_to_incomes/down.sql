@@ -0,0 +1,3 @@
+ALTER TABLE incomes DROP CONSTRAINT incomes_source_scheduled_date_unique;
+ALTER TABLE incomes ADD CONSTRAINT incomes_source_date_unique UNIQUE (income_source_id, date);
+ALTER TABLE incomes DROP COLUMN scheduled_date;
@@ -0,0 +1,6 @@
+-- The pay date a generated paycheck was scheduled for, kept when confirming moves its date
+-- Generation dedupes on it so a confirmed paycheck isn't generated again
+ALTER TABLE incomes ADD COLUMN scheduled_date DATE;
+UPDATE incomes SET scheduled_date = date WHERE income_source_id IS NOT NULL;
+ALTER TABLE incomes DROP CONSTRAINT incomes_source_date_unique;
+ALTER TABLE incomes ADD CONSTRAINT incomes_source_scheduled_date_unique UNIQUE (income_source_id, scheduled_date);
@@ -0,0 +1 @@
+ALTER TABLE incomes DROP COLUMN category;
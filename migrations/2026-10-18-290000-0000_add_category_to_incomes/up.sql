@@ -0,0 +1,4 @@
+-- An income's own category, for one-off payments with no pay-schedule source
+-- NULL falls back to the category of the income's source
+ALTER TABLE incomes ADD COLUMN category VARCHAR
+    CHECK (category IN ('Salary', 'Side Gig', 'Reimbursement', 'Interest', 'Dividends', 'Gift', 'Other'));
@@ -0,0 +1,2 @@
+-- The 2024 dates were wrong, so they are not restored
+SELECT 1;
@@ -0,0 +1,21 @@
+-- Editing a ledger bill used to store its due date in 2024 whatever the ledger's year
+-- Move those dates into the ledger's year (the next year for months before the ledger's),
+-- clamping 29 February to the end of the month
+WITH due AS (
+    SELECT
+        lb.id,
+        EXTRACT(DAY FROM lb.due_day)::int AS day,
+        make_date(
+            EXTRACT(YEAR FROM l.date)::int
+                + CASE WHEN EXTRACT(MONTH FROM lb.due_day) < EXTRACT(MONTH FROM l.date) THEN 1 ELSE 0 END,
+            EXTRACT(MONTH FROM lb.due_day)::int,
+            1
+        ) AS month_start
+    FROM ledger_bills lb
+    JOIN ledgers l ON l.id = lb.ledger_id
+    WHERE EXTRACT(YEAR FROM lb.due_day) = 2024
+)
+UPDATE ledger_bills
+SET due_day = due.month_start + (LEAST(due.day, EXTRACT(DAY FROM due.month_start + INTERVAL '1 month - 1 day')::int) - 1)
+FROM due
+WHERE ledger_bills.id = due.id;
@@ -0,0 +1,608 @@
+use std::cmp::Ordering;
+use std::rc::Rc;
+use std::str::FromStr;
+use bigdecimal::{BigDecimal, RoundingMode, ToPrimitive};
+use chrono::{Local, NaiveDate};
+use cursive::Cursive;
+use cursive::traits::*;
+use cursive::views::{Button, Dialog, EditView, HideableView, LinearLayout, ListView, Panel, SelectView, TextArea, TextView};
+use cursive_table_view::{TableView, TableViewItem};
+
+use crate::models::{self, AccountType};
+use crate::card_logic;
+use crate::net_worth_logic::{self, BalanceSnapshot, NetWorthPoint};
+use crate::dependency_container::AccountRepos;
+use crate::repositories::CreditCardRepo;
+use crate::ui_helpers::toggle_buttons_visible;
+
+const ACCOUNT_EDIT_BUTTON: &str = "account_edit_button";
+const ACCOUNT_BALANCE_BUTTON: &str = "account_balance_button";
+const ACCOUNT_HISTORY_BUTTON: &str = "account_history_button";
+const ACCOUNT_CARD_BUTTON: &str = "account_card_button";
+const ACCOUNT_DELETE_BUTTON: &str = "account_delete_button";
+const TOGGLE_BUTTONS: &[&str] = &[ACCOUNT_EDIT_BUTTON, ACCOUNT_BALANCE_BUTTON, ACCOUNT_HISTORY_BUTTON, ACCOUNT_CARD_BUTTON, ACCOUNT_DELETE_BUTTON];
+
+/// Closed statements listed in a card's statement history
+const STATEMENT_HISTORY_MONTHS: u32 = 12;
+
+/// Width of the longest bar in the net worth history
+const BAR_WIDTH: usize = 20;
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+enum AccountColumn {
+    Name,
+    Type,
+    Balance,
+    AsOf,
+}
+
+#[derive(Clone, Debug)]
+struct AccountDisplay {
+    id: i32,
+    name: String,
+    account_type: AccountType,
+    balance: Option<BigDecimal>,
+    as_of: Option<NaiveDate>,
+    notes: Option<String>,
+}
+
+impl TableViewItem<AccountColumn> for AccountDisplay {
+    fn to_column(&self, column: AccountColumn) -> String {
+        match column {
+            AccountColumn::Name => self.name.clone(),
+            AccountColumn::Type => self.account_type.to_string(),
+            AccountColumn::Balance => match &self.balance {
+                Some(b) if self.account_type.is_liability() => format!("-${}", b),
+                Some(b) => format!("${}", b),
+                None => "-".to_string(),
+            },
+            AccountColumn::AsOf => self.as_of.map_or("-".to_string(), |d| d.format("%d/%m/%Y").to_string()),
+        }
+    }
+
+    fn cmp(&self, other: &Self, column: AccountColumn) -> Ordering where Self: Sized {
+        match column {
+            AccountColumn::Name => self.name.cmp(&other.name),
+            AccountColumn::Type => self.account_type.to_string().cmp(&other.account_type.to_string()),
+            AccountColumn::Balance => self.balance.cmp(&other.balance),
+            AccountColumn::AsOf => self.as_of.cmp(&other.as_of),
+        }
+    }
+}
+
+/// Snapshots in the shape the net worth calculation expects
+pub fn to_balance_snapshots(accounts: &[models::Account], balances: &[models::AccountBalance]) -> Vec<BalanceSnapshot> {
+    balances
+        .iter()
+        .filter_map(|b| {
+            accounts.iter().find(|a| a.id == b.account_id).map(|a| BalanceSnapshot {
+                account_id: b.account_id,
+                is_liability: AccountType::from(a.account_type.clone()).is_liability(),
+                date: b.date,
+                balance: b.balance.clone(),
+            })
+        })
+        .collect()
+}
+
+/// Popup of accounts with a "(None)" entry
+pub fn build_account_select(accounts: &[models::Account], selected_id: Option<i32>) -> SelectView<Option<i32>> {
+    let mut select = SelectView::new().popup();
+    select.add_item("(None)", None);
+    for account in accounts {
+        select.add_item(format!("{} ({})", account.name, AccountType::from(account.account_type.clone())), Some(account.id));
+    }
+
+    let selected_index = selected_id
+        .and_then(|aid| accounts.iter().position(|a| a.id == aid))
+        .map_or(0, |i| i + 1);
+    select.selected(selected_index)
+}
+
+pub fn show_account_table(siv: &mut Cursive, repos: &Rc<AccountRepos>) {
+    siv.pop_layer();
+
+    let accounts = repos.account.find_all();
+    let balances = repos.account.find_balances();
+    let snapshots = to_balance_snapshots(&accounts, &balances);
+    let today = Local::now().date_naive();
+
+    let account_displays: Vec<AccountDisplay> = accounts
+        .iter()
+        .map(|a| {
+            let latest = balances
+                .iter()
+                .filter(|b| b.account_id == a.id)
+                .max_by_key(|b| b.date);
+            AccountDisplay {
+                id: a.id,
+                name: a.name.clone(),
+                account_type: AccountType::from(a.account_type.clone()),
+                balance: latest.map(|b| b.balance.clone()),
+                as_of: latest.map(|b| b.date),
+                notes: a.notes.clone(),
+            }
+        })
+        .collect();
+    let account_count = account_displays.len();
+
+    let table = TableView::<AccountDisplay, AccountColumn>::new()
+        .column(AccountColumn::Name, "Account", |c| c)
+        .column(AccountColumn::Type, "Type", |c| c.width(14))
+        .column(AccountColumn::Balance, "Balance", |c| c.width(16))
+        .column(AccountColumn::AsOf, "As Of", |c| c.width(12))
+        .items(account_displays);
+
+    let current = net_worth_logic::calculate_net_worth(&snapshots, today);
+    let history = net_worth_logic::net_worth_history(&snapshots);
+
+    let repo_add = Rc::clone(repos);
+    let repo_edit = Rc::clone(repos);
+    let repo_balance = Rc::clone(repos);
+    let repo_history = Rc::clone(repos);
+    let repo_card = Rc::clone(repos);
+    let repo_delete = Rc::clone(repos);
+
+    let buttons = LinearLayout::horizontal()
+        .child(Button::new("Add", move |s| account_form(s, None, &repo_add)))
+        .child(HideableView::new(Button::new("Edit", move |s| {
+            if let Some(account) = selected_account(s) {
+                account_form(s, Some(account), &repo_edit);
+            }
+        })).with_name(ACCOUNT_EDIT_BUTTON))
+        .child(HideableView::new(Button::new("Record Balance", move |s| {
+            if let Some(account) = selected_account(s) {
+                record_balance(s, &account, &repo_balance);
+            }
+        })).with_name(ACCOUNT_BALANCE_BUTTON))
+        .child(HideableView::new(Button::new("History", move |s| {
+            if let Some(account) = selected_account(s) {
+                show_balance_history(s, &account, &repo_history);
+            }
+        })).with_name(ACCOUNT_HISTORY_BUTTON))
+        .child(HideableView::new(Button::new("Card", move |s| {
+            if let Some(account) = selected_account(s) {
+                card_form(s, &account, &repo_card);
+            }
+        })).with_name(ACCOUNT_CARD_BUTTON))
+        .child(HideableView::new(Button::new("Delete", move |s| delete_account(s, &repo_delete))).with_name(ACCOUNT_DELETE_BUTTON));
+
+    let summary = format!(
+        "Assets: ${}\nLiabilities: ${}\nNet Worth: ${}",
+        current.assets,
+        current.liabilities,
+        current.net_worth
+    );
+
+    let content = LinearLayout::vertical()
+        .child(Panel::new(table.with_name("account_table").min_size((60, 10))).full_width())
+        .child(buttons)
+        .child(Panel::new(TextView::new(summary)).title("Today"))
+        .child(Panel::new(TextView::new(format_net_worth_history(&history)).scrollable()).title("Net Worth Over Time").full_screen());
+
+    let screen = crate::common_layout::create_screen(
+        "Net Worth",
+        content,
+        &crate::common_layout::view_footer()
+    );
+
+    siv.add_layer(screen);
+
+    toggle_buttons_visible(siv, account_count, TOGGLE_BUTTONS);
+}
+
+/// One line per snapshot date with the change since the last and a bar scaled to the largest net worth
+fn format_net_worth_history(history: &[NetWorthPoint]) -> String {
+    if history.is_empty() {
+        return "Record account balances to see net worth over time.".to_string();
+    }
+
+    let largest = history
+        .iter()
+        .map(|p| p.net_worth.abs())
+        .max()
+        .unwrap_or(BigDecimal::from(0));
+
+    let mut text = format!("{:<12}{:>14}{:>14}{:>14}{:>12}\n", "DATE", "ASSETS", "LIABILITIES", "NET WORTH", "CHANGE");
+    let mut previous: Option<&BigDecimal> = None;
+    for point in history {
+        let date_str = point.date.format("%d/%m/%Y").to_string();
+        let assets_str = format!("${}", point.assets);
+        let liabilities_str = format!("${}", point.liabilities);
+        let net_worth_str = format!("${}", point.net_worth);
+        let change_str = previous.map_or(String::new(), |p| (&point.net_worth - p).to_string());
+
+        let bar_length = if largest > BigDecimal::from(0) {
+            (point.net_worth.abs() * BigDecimal::from(BAR_WIDTH as u32) / &largest)
+                .with_scale_round(0, RoundingMode::HalfUp)
+                .to_usize()
+                .unwrap_or(0)
+        } else {
+            0
+        };
+        let bar_char = if point.net_worth < BigDecimal::from(0) { "░" } else { "█" };
+
+        text.push_str(&format!(
+            "{:<12}{:>14}{:>14}{:>14}{:>12}  {}\n",
+            date_str,
+            assets_str,
+            liabilities_str,
+            net_worth_str,
+            change_str,
+            bar_char.repeat(bar_length)
+        ));
+        previous = Some(&point.net_worth);
+    }
+    text
+}
+
+fn selected_account(siv: &mut Cursive) -> Option<AccountDisplay> {
+    siv.call_on_name("account_table", |v: &mut TableView<AccountDisplay, AccountColumn>| {
+        v.item().and_then(|i| v.borrow_item(i).cloned())
+    }).flatten()
+}
+
+fn account_form(siv: &mut Cursive, existing: Option<AccountDisplay>, repos: &Rc<AccountRepos>) {
+    let title = if existing.is_some() { "Edit Account" } else { "Add Account" };
+
+    let name_value = existing.as_ref().map(|a| a.name.clone()).unwrap_or_default();
+    let notes_value = existing.as_ref().and_then(|a| a.notes.clone()).unwrap_or_default();
+    let type_value = existing.as_ref().map_or(AccountType::Checking, |a| a.account_type);
+    let account_id = existing.map(|a| a.id);
+
+    let mut type_select = SelectView::new().popup();
+    for account_type in AccountType::all() {
+        type_select.add_item(account_type.to_string(), account_type);
+    }
+    let type_index = AccountType::all().iter().position(|t| *t == type_value).unwrap_or(0);
+    let type_select = type_select.selected(type_index);
+
+    let repo = Rc::clone(repos);
+    siv.add_layer(
+        Dialog::new()
+            .title(title)
+            .button("Ok", move |s| {
+                let name_str = s.call_on_name("account_name_input", |v: &mut EditView| v.get_content()).unwrap();
+                let notes_str = s.call_on_name("account_notes_input", |v: &mut TextArea| v.get_content().to_string()).unwrap();
+                let account_type = s.call_on_name("account_type_select", |v: &mut SelectView<AccountType>| {
+                    v.selection().map(|t| *t)
+                }).flatten().unwrap_or(AccountType::Checking);
+
+                if name_str.trim().is_empty() {
+                    s.add_layer(Dialog::info("Name cannot be empty"));
+                    return;
+                }
+
+                let notes = if notes_str.is_empty() { None } else { Some(notes_str) };
+                if let Some(record_id) = account_id {
+                    repo.account.update(record_id, name_str.trim().to_string(), account_type.into(), notes);
+                } else {
+                    repo.account.create(name_str.trim().to_string(), account_type.into(), notes);
+                }
+
+                s.pop_layer();
+                show_account_table(s, &repo);
+            })
+            .button("Cancel", |s| { s.pop_layer(); })
+            .content(
+                ListView::new()
+                    .child("Name", EditView::new().content(name_value).with_name("account_name_input").fixed_width(20))
+                    .child("Type", type_select.with_name("account_type_select"))
+                    .child("Notes", TextArea::new().content(notes_value).with_name("account_notes_input").min_size((40, 3)))
+            )
+    );
+}
+
+fn record_balance(siv: &mut Cursive, account: &AccountDisplay, repos: &Rc<AccountRepos>) {
+    let account_id = account.id;
+    let balance_label = if account.account_type.is_liability() { "Amount Owed" } else { "Balance" };
+    let balance_value = account.balance.as_ref().map(|b| b.to_string()).unwrap_or_default();
+
+    let repo = Rc::clone(repos);
+    siv.add_layer(
+        Dialog::around(
+            ListView::new()
+                .child("Date (DD/MM/YYYY)", EditView::new()
+                    .content(Local::now().format("%d/%m/%Y").to_string())
+                    .with_name("account_balance_date_input")
+                    .fixed_width(20))
+                .child(balance_label, EditView::new().content(balance_value).with_name("account_balance_input").fixed_width(20))
+        )
+        .title(format!("Record Balance: {}", account.name))
+        .button("Save", move |s| {
+            let date_str = s.call_on_name("account_balance_date_input", |v: &mut EditView| v.get_content()).unwrap();
+            let balance_str = s.call_on_name("account_balance_input", |v: &mut EditView| v.get_content()).unwrap();
+
+            let date = match NaiveDate::parse_from_str(date_str.trim(), "%d/%m/%Y") {
+                Ok(d) => d,
+                Err(_) => {
+                    s.add_layer(Dialog::info("Invalid date format. Use DD/MM/YYYY"));
+                    return;
+                }
+            };
+
+            let balance = match BigDecimal::from_str(balance_str.trim()) {
+                Ok(b) => b,
+                Err(_) => {
+                    s.add_layer(Dialog::info("Invalid balance format"));
+                    return;
+                }
+            };
+
+            repo.account.record_balance(account_id, date, balance);
+
+            s.pop_layer();
+            show_account_table(s, &repo);
+        })
+        .button("Cancel", |s| { s.pop_layer(); })
+    );
+}
+
+fn show_balance_history(siv: &mut Cursive, account: &AccountDisplay, repos: &Rc<AccountRepos>) {
+    let snapshots = repos.account.find_balances_by_account(account.id);
+
+    if snapshots.is_empty() {
+        siv.add_layer(Dialog::info("No balances recorded yet"));
+        return;
+    }
+
+    let mut select = SelectView::<i32>::new();
+    for snapshot in &snapshots {
+        select.add_item(format!("{}  ${}", snapshot.date.format("%d/%m/%Y"), snapshot.balance), snapshot.id);
+    }
+
+    let repo = Rc::clone(repos);
+    siv.add_layer(
+        Dialog::around(select.with_name("account_balance_select").scrollable().min_size((30, 8)))
+            .title(format!("{} Balances", account.name))
+            .button("Delete", move |s| {
+                let balance_id = s.call_on_name("account_balance_select", |v: &mut SelectView<i32>| {
+                    v.selection().map(|id| *id)
+                }).flatten();
+
+                if let Some(id) = balance_id {
+                    repo.account.delete_balance(id);
+                    s.pop_layer();
+                    show_account_table(s, &repo);
+                }
+            })
+            .button("Close", |s| { s.pop_layer(); })
+    );
+}
+
+/// Statement cycle and payment bill for a credit card account
+fn card_form(siv: &mut Cursive, account: &AccountDisplay, repos: &Rc<AccountRepos>) {
+    if account.account_type != AccountType::CreditCard {
+        siv.add_layer(Dialog::info("Statement cycles are only tracked for credit card accounts"));
+        return;
+    }
+
+    let card_repo = Rc::clone(&repos.credit_card);
+    let existing = card_repo.find_by_account(account.id);
+
+    let closing_value = existing.as_ref().map(|c| c.closing_day.to_string()).unwrap_or_default();
+    let due_value = existing.as_ref().map(|c| c.due_day.to_string()).unwrap_or_default();
+    let notes_value = existing.as_ref().and_then(|c| c.notes.clone()).unwrap_or_default();
+    let bill_value = existing.as_ref().and_then(|c| c.bill_id);
+
+    // The statement balance becomes this bill's amount in the ledger where it's due
+    let bills = repos.bill.find_all();
+    let mut bill_select = SelectView::new().popup();
+    bill_select.add_item("(None)", None);
+    for bill in &bills {
+        bill_select.add_item(bill.name.clone(), Some(bill.id));
+    }
+    let bill_index = bill_value
+        .and_then(|bid| bills.iter().position(|b| b.id == bid))
+        .map_or(0, |i| i + 1);
+    let bill_select = bill_select.selected(bill_index);
+
+    let account_id = account.id;
+    let repo_save = Rc::clone(&card_repo);
+
+    let mut dialog = Dialog::new()
+        .title(format!("Card: {}", account.name))
+        .button("Save", move |s| {
+            let (closing_day, due_day) = match get_card_form_values(s) {
+                Ok(values) => values,
+                Err(message) => {
+                    s.add_layer(Dialog::info(message));
+                    return;
+                }
+            };
+            let bill_id = s.call_on_name("card_bill_select", |v: &mut SelectView<Option<i32>>| {
+                v.selection().and_then(|b| *b)
+            }).flatten();
+            let notes_str = s.call_on_name("card_notes_input", |v: &mut TextArea| v.get_content().to_string()).unwrap();
+
+            repo_save.save(
+                account_id,
+                bill_id,
+                closing_day,
+                due_day,
+                if notes_str.is_empty() { None } else { Some(notes_str) }
+            );
+
+            s.pop_layer();
+        });
+
+    if let Some(card) = existing {
+        let card_charges = card.clone();
+        let card_statements = card.clone();
+        let repo_charges = Rc::clone(&card_repo);
+        let repo_statements = Rc::clone(&card_repo);
+        let repo_remove = Rc::clone(&card_repo);
+        let account_name = account.name.clone();
+        dialog.add_button("Charges", move |s| show_card_charges(s, &card_charges, &repo_charges));
+        dialog.add_button("Statements", move |s| show_card_statements(s, &account_name, &card_statements, &repo_statements));
+        dialog.add_button("Remove", move |s| {
+            repo_remove.delete(card.id);
+            s.pop_layer();
+        });
+    }
+
+    siv.add_layer(
+        dialog
+            .button("Cancel", |s| { s.pop_layer(); })
+            .content(
+                ListView::new()
+                    .child("Closing Day", EditView::new().content(closing_value).with_name("card_closing_input").fixed_width(20))
+                    .child("Due Day", EditView::new().content(due_value).with_name("card_due_input").fixed_width(20))
+                    .child("Payment Bill", bill_select.with_name("card_bill_select"))
+                    .child("Notes", TextArea::new().content(notes_value).with_name("card_notes_input").min_size((40, 3)))
+            )
+    );
+}
+
+fn get_card_form_values(s: &mut Cursive) -> Result<(i32, i32), &'static str> {
+    let closing_str = s.call_on_name("card_closing_input", |v: &mut EditView| v.get_content()).unwrap();
+    let due_str = s.call_on_name("card_due_input", |v: &mut EditView| v.get_content()).unwrap();
+
+    let closing_day = closing_str.trim().parse::<i32>().ok().filter(|d| (1..=31).contains(d)).ok_or("Closing day must be between 1 and 31")?;
+    let due_day = due_str.trim().parse::<i32>().ok().filter(|d| (1..=31).contains(d)).ok_or("Due day must be between 1 and 31")?;
+
+    Ok((closing_day, due_day))
+}
+
+fn show_card_charges(siv: &mut Cursive, card: &models::CreditCard, card_repo: &Rc<CreditCardRepo>) {
+    let charges = card_repo.find_charges(card.id);
+    let amounts: Vec<(NaiveDate, BigDecimal)> = charges.iter().map(|c| (c.date, c.amount.clone())).collect();
+    let open_cycle = card_logic::cycle_containing(card.closing_day as u32, card.due_day as u32, Local::now().date_naive());
+    let open_balance = card_logic::statement_balance(&amounts, &open_cycle);
+
+    let mut select = SelectView::<i32>::new();
+    for charge in &charges {
+        select.add_item(format!("{}  ${}  {}", charge.date.format("%d/%m/%Y"), charge.amount, charge.description), charge.id);
+    }
+
+    let summary = format!(
+        "Current cycle {} - {}: ${} (due {})",
+        open_cycle.start.format("%d/%m/%Y"),
+        open_cycle.close.format("%d/%m/%Y"),
+        open_balance,
+        open_cycle.due.format("%d/%m/%Y")
+    );
+
+    let card_add = card.clone();
+    let card_delete = card.clone();
+    let repo_add = Rc::clone(card_repo);
+    let repo_delete = Rc::clone(card_repo);
+    siv.add_layer(
+        Dialog::around(
+            LinearLayout::vertical()
+                .child(TextView::new(summary))
+                .child(select.with_name("card_charge_select").scrollable().min_size((50, 8)))
+        )
+        .title("Card Charges")
+        .button("Add", move |s| charge_form(s, &card_add, &repo_add))
+        .button("Delete", move |s| {
+            let charge_id = s.call_on_name("card_charge_select", |v: &mut SelectView<i32>| {
+                v.selection().map(|id| *id)
+            }).flatten();
+
+            if let Some(id) = charge_id {
+                repo_delete.delete_charge(&card_delete, id);
+                s.pop_layer();
+                show_card_charges(s, &card_delete, &repo_delete);
+            }
+        })
+        .button("Close", |s| { s.pop_layer(); })
+    );
+}
+
+fn charge_form(siv: &mut Cursive, card: &models::CreditCard, card_repo: &Rc<CreditCardRepo>) {
+    let card = card.clone();
+    let repo = Rc::clone(card_repo);
+    siv.add_layer(
+        Dialog::around(
+            ListView::new()
+                .child("Date (DD/MM/YYYY)", EditView::new()
+                    .content(Local::now().format("%d/%m/%Y").to_string())
+                    .with_name("card_charge_date_input")
+                    .fixed_width(20))
+                .child("Amount", EditView::new().with_name("card_charge_amount_input").fixed_width(20))
+                .child("Description", EditView::new().with_name("card_charge_description_input").fixed_width(30))
+        )
+        .title("Add Charge (negative for refunds)")
+        .button("Save", move |s| {
+            let date_str = s.call_on_name("card_charge_date_input", |v: &mut EditView| v.get_content()).unwrap();
+            let amount_str = s.call_on_name("card_charge_amount_input", |v: &mut EditView| v.get_content()).unwrap();
+            let description_str = s.call_on_name("card_charge_description_input", |v: &mut EditView| v.get_content()).unwrap();
+
+            let date = match NaiveDate::parse_from_str(date_str.trim(), "%d/%m/%Y") {
+                Ok(d) => d,
+                Err(_) => {
+                    s.add_layer(Dialog::info("Invalid date format. Use DD/MM/YYYY"));
+                    return;
+                }
+            };
+
+            let amount = match BigDecimal::from_str(amount_str.trim()) {
+                Ok(a) => a,
+                Err(_) => {
+                    s.add_layer(Dialog::info("Invalid amount format"));
+                    return;
+                }
+            };
+
+            if description_str.trim().is_empty() {
+                s.add_layer(Dialog::info("Description cannot be empty"));
+                return;
+            }
+
+            repo.create_charge(&card, date, amount, description_str.trim().to_string());
+
+            s.pop_layer(); // Close form
+            s.pop_layer(); // Close charges
+            show_card_charges(s, &card, &repo);
+        })
+        .button("Cancel", |s| { s.pop_layer(); })
+    );
+}
+
+/// Recent closed statements with their periods, due dates and balances
+fn show_card_statements(siv: &mut Cursive, account_name: &str, card: &models::CreditCard, card_repo: &Rc<CreditCardRepo>) {
+    let amounts = card_repo.charge_amounts(card.id);
+    let statements = card_logic::recent_statements(
+        card.closing_day as u32,
+        card.due_day as u32,
+        &amounts,
+        Local::now().date_naive(),
+        STATEMENT_HISTORY_MONTHS
+    );
+
+    let mut content = format!("{:<12}{:<12}{:<12}{:>12}\n", "FROM", "CLOSED", "DUE", "BALANCE");
+    content.push_str("────────────────────────────────────────────────\n");
+    for (cycle, balance) in &statements {
+        let balance_str = format!("${}", balance);
+        content.push_str(&format!(
+            "{:<12}{:<12}{:<12}{:>12}\n",
+            cycle.start.format("%d/%m/%Y").to_string(),
+            cycle.close.format("%d/%m/%Y").to_string(),
+            cycle.due.format("%d/%m/%Y").to_string(),
+            balance_str
+        ));
+    }
+
+    siv.add_layer(
+        Dialog::around(TextView::new(content).scrollable())
+            .title(format!("{} Statements", account_name))
+            .button("Close", |s| { s.pop_layer(); })
+    );
+}
+
+fn delete_account(siv: &mut Cursive, repos: &Rc<AccountRepos>) {
+    if let Some(account) = selected_account(siv) {
+        let repo = Rc::clone(repos);
+        siv.add_layer(
+            Dialog::text(format!("Delete account '{}' and its balance history?", account.name))
+                .button("Yes", move |s| {
+                    repo.account.delete(account.id);
+
+                    s.pop_layer();
+                    show_account_table(s, &repo);
+                })
+                .button("No", |s| { s.pop_layer(); })
+        );
+    }
+}
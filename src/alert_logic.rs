@@ -0,0 +1,142 @@
+use bigdecimal::BigDecimal;
+use chrono::NaiveDate;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum AlertLevel {
+    Overdue,
+    DueSoon,
+}
+
+/// An unpaid ledger bill to look at
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnpaidBill {
+    pub ledger_id: i32,
+    pub name: String,
+    pub amount: BigDecimal,
+    pub due: Option<NaiveDate>,
+}
+
+/// An unpaid bill that is past due or due within the look-ahead window
+#[derive(Debug, Clone, PartialEq)]
+pub struct BillAlert {
+    pub level: AlertLevel,
+    pub ledger_id: i32,
+    pub name: String,
+    pub amount: BigDecimal,
+    pub due: NaiveDate,
+}
+
+impl BillAlert {
+    /// "3 days overdue", "due today", "due in 2 days"
+    pub fn describe(&self, today: NaiveDate) -> String {
+        let days = (self.due - today).num_days();
+        match days {
+            d if d < -1 => format!("{} days overdue", -d),
+            -1 => "1 day overdue".to_string(),
+            0 => "due today".to_string(),
+            1 => "due tomorrow".to_string(),
+            d => format!("due in {} days", d),
+        }
+    }
+}
+
+/// Overdue when the due date has passed; due soon from today through the look-ahead window
+pub fn classify(due: NaiveDate, today: NaiveDate, look_ahead_days: u32) -> Option<AlertLevel> {
+    let days = (due - today).num_days();
+    if days < 0 {
+        Some(AlertLevel::Overdue)
+    } else if days <= look_ahead_days as i64 {
+        Some(AlertLevel::DueSoon)
+    } else {
+        None
+    }
+}
+
+/// Alerts for every bill with a due date that needs attention, overdue first, then by due date
+pub fn collect_alerts(bills: &[UnpaidBill], today: NaiveDate, look_ahead_days: u32) -> Vec<BillAlert> {
+    let mut alerts: Vec<BillAlert> = bills
+        .iter()
+        .filter_map(|bill| {
+            let due = bill.due?;
+            classify(due, today, look_ahead_days).map(|level| BillAlert {
+                level,
+                ledger_id: bill.ledger_id,
+                name: bill.name.clone(),
+                amount: bill.amount.clone(),
+                due,
+            })
+        })
+        .collect();
+    alerts.sort_by(|a, b| a.level.cmp(&b.level).then(a.due.cmp(&b.due)));
+    alerts
+}
+
+/// Short footer summary such as "2 overdue, 1 due soon", or None when nothing needs attention
+pub fn badge(alerts: &[BillAlert]) -> Option<String> {
+    let overdue = alerts.iter().filter(|a| a.level == AlertLevel::Overdue).count();
+    let due_soon = alerts.len() - overdue;
+    match (overdue, due_soon) {
+        (0, 0) => None,
+        (0, soon) => Some(format!("{} due soon", soon)),
+        (late, 0) => Some(format!("{} overdue", late)),
+        (late, soon) => Some(format!("{} overdue, {} due soon", late, soon)),
+    }
+}
+
+/// The startup dialog stays quiet through the snooze date
+pub fn is_snoozed(snoozed_until: Option<NaiveDate>, today: NaiveDate) -> bool {
+    snoozed_until.is_some_and(|until| today <= until)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, month, day).unwrap()
+    }
+
+    fn bill(name: &str, due: Option<NaiveDate>) -> UnpaidBill {
+        UnpaidBill { ledger_id: 1, name: name.to_string(), amount: BigDecimal::from(50), due }
+    }
+
+    #[test]
+    fn test_classify_respects_look_ahead() {
+        let today = date(10, 18);
+
+        assert_eq!(classify(date(10, 17), today, 3), Some(AlertLevel::Overdue));
+        assert_eq!(classify(date(10, 18), today, 3), Some(AlertLevel::DueSoon));
+        assert_eq!(classify(date(10, 21), today, 3), Some(AlertLevel::DueSoon));
+        assert_eq!(classify(date(10, 22), today, 3), None);
+    }
+
+    #[test]
+    fn test_alerts_put_overdue_first_and_skip_undated() {
+        let bills = vec![
+            bill("Phone", Some(date(10, 20))),
+            bill("Rent", Some(date(10, 1))),
+            bill("Gym", None),
+            bill("Water", Some(date(10, 12))),
+            bill("Insurance", Some(date(11, 30))),
+        ];
+
+        let alerts = collect_alerts(&bills, date(10, 18), 7);
+        let names: Vec<&str> = alerts.iter().map(|a| a.name.as_str()).collect();
+
+        assert_eq!(names, vec!["Rent", "Water", "Phone"]);
+        assert_eq!(badge(&alerts), Some("2 overdue, 1 due soon".to_string()));
+        assert_eq!(alerts[1].describe(date(10, 18)), "6 days overdue");
+    }
+
+    #[test]
+    fn test_no_badge_without_alerts() {
+        assert_eq!(badge(&[]), None);
+    }
+
+    #[test]
+    fn test_snooze_lasts_through_its_date() {
+        assert!(is_snoozed(Some(date(10, 20)), date(10, 20)));
+        assert!(!is_snoozed(Some(date(10, 20)), date(10, 21)));
+        assert!(!is_snoozed(None, date(10, 21)));
+    }
+}
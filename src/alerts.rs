@@ -0,0 +1,113 @@
+use std::rc::Rc;
+use chrono::{Duration, Local};
+use cursive::Cursive;
+use cursive::theme::{BaseColor, Color};
+use cursive::traits::*;
+use cursive::utils::markup::StyledString;
+use cursive::views::{Dialog, TextView};
+
+use crate::alert_logic::{self, AlertLevel, BillAlert, UnpaidBill};
+use crate::dependency_container::LedgerRepos;
+use crate::repositories::LedgerRepo;
+
+/// Overdue and due-soon bills across every open ledger
+fn load_alerts(ledger_repo: &LedgerRepo, look_ahead_days: u32) -> Vec<BillAlert> {
+    let bills: Vec<UnpaidBill> = ledger_repo
+        .find_unpaid_bills_in_open_ledgers()
+        .into_iter()
+        .map(|(lb, bill)| UnpaidBill {
+            ledger_id: lb.ledger_id,
+            name: bill.name,
+            amount: lb.amount,
+            due: lb.due_day,
+        })
+        .collect();
+    alert_logic::collect_alerts(&bills, Local::now().date_naive(), look_ahead_days)
+}
+
+/// Footer badge summarizing the current alerts, None when nothing needs attention
+pub fn footer_badge(ledger_repos: &LedgerRepos) -> Option<String> {
+    let alerts = load_alerts(&ledger_repos.ledger, ledger_repos.config.get_alert_look_ahead_days());
+    alert_logic::badge(&alerts)
+}
+
+/// Startup check: shows the alert dialog only when something needs attention and alerts aren't snoozed
+pub fn show_startup_alerts(siv: &mut Cursive, ledger_repos: &Rc<LedgerRepos>) {
+    let config = &ledger_repos.config;
+    if alert_logic::is_snoozed(config.get_alerts_snoozed_until(), Local::now().date_naive()) {
+        return;
+    }
+
+    let alerts = load_alerts(&ledger_repos.ledger, config.get_alert_look_ahead_days());
+    if !alerts.is_empty() {
+        add_alert_dialog(siv, ledger_repos, alerts, config.get_alert_look_ahead_days());
+    }
+}
+
+/// The alert dialog on demand, even when snoozed or empty
+pub fn show_alerts(siv: &mut Cursive, ledger_repos: &Rc<LedgerRepos>) {
+    let config = &ledger_repos.config;
+    let alerts = load_alerts(&ledger_repos.ledger, config.get_alert_look_ahead_days());
+    add_alert_dialog(siv, ledger_repos, alerts, config.get_alert_look_ahead_days());
+}
+
+fn add_alert_dialog(siv: &mut Cursive, ledger_repos: &Rc<LedgerRepos>, alerts: Vec<BillAlert>, look_ahead_days: u32) {
+    let today = Local::now().date_naive();
+
+    let mut content = StyledString::new();
+    if alerts.is_empty() {
+        content.append_plain(format!("No unpaid bills are overdue or due in the next {} days.", look_ahead_days));
+    }
+    for alert in &alerts {
+        let line = format!(
+            "{}  {:<20} ${:<10} {}\n",
+            alert.due.format("%d/%m/%Y"),
+            alert.name,
+            alert.amount,
+            alert.describe(today)
+        );
+        match alert.level {
+            AlertLevel::Overdue => content.append_styled(line, Color::Light(BaseColor::Red)),
+            AlertLevel::DueSoon => content.append_styled(line, Color::Light(BaseColor::Yellow)),
+        }
+    }
+
+    // Jump to the ledger holding the most urgent bill
+    let first_ledger_id = alerts.first().map(|a| a.ledger_id);
+    let snooze_days = ledger_repos.config.get_alert_snooze_days();
+    let repo_open = Rc::clone(ledger_repos);
+    let config_snooze = Rc::clone(&ledger_repos.config);
+
+    let mut dialog = Dialog::around(TextView::new(content).scrollable())
+        .title(match alert_logic::badge(&alerts) {
+            Some(badge) => format!("Bill Alerts: {}", badge),
+            None => "Bill Alerts".to_string(),
+        });
+    if let Some(ledger_id) = first_ledger_id {
+        dialog = dialog.button("Open Ledger", move |s| {
+            s.pop_layer();
+            crate::ledger_detail::show_ledger_detail(s, ledger_id, &repo_open);
+        });
+    }
+    siv.add_layer(
+        dialog
+            .button(snooze_label(snooze_days), move |s| {
+                // Snoozing counts today, so one day stays quiet until tomorrow's startup
+                let until = Local::now().date_naive() + Duration::days(snooze_days.saturating_sub(1) as i64);
+                s.pop_layer();
+                if let Err(e) = config_snooze.snooze_alerts_until(until) {
+                    log::error!("Failed to save alert snooze: {}", e);
+                    s.add_layer(Dialog::info(format!("Could not save the snooze: {}", e)));
+                }
+            })
+            .button("Dismiss", |s| { s.pop_layer(); })
+    );
+}
+
+fn snooze_label(days: u32) -> String {
+    if days == 1 {
+        "Snooze 1 day".to_string()
+    } else {
+        format!("Snooze {} days", days)
+    }
+}
@@ -0,0 +1,128 @@
+use bigdecimal::{BigDecimal, RoundingMode};
+use chrono::{Datelike, NaiveDate};
+
+/// Months averaged for the rolling estimate
+const AVERAGE_MONTHS: usize = 3;
+
+/// What a bill came to in one month, summed across that month's ledgers
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonthAmount {
+    pub month: NaiveDate,
+    pub amount: BigDecimal,
+}
+
+/// Suggested amounts for adding a bill to a ledger, from its history before that ledger's month
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct BillEstimates {
+    pub last: Option<BigDecimal>,
+    pub three_month_average: Option<BigDecimal>,
+    pub same_month_last_year: Option<BigDecimal>,
+}
+
+impl BillEstimates {
+    pub fn is_empty(&self) -> bool {
+        self.last.is_none() && self.three_month_average.is_none() && self.same_month_last_year.is_none()
+    }
+
+    /// The average smooths out swings, so it leads; then the last amount
+    pub fn preferred(&self) -> Option<&BigDecimal> {
+        self.three_month_average.as_ref().or(self.last.as_ref())
+    }
+}
+
+/// Ledger-dated amounts grouped into months, oldest first
+pub fn monthly_amounts(history: &[(NaiveDate, BigDecimal)]) -> Vec<MonthAmount> {
+    let mut months: Vec<MonthAmount> = Vec::new();
+    let mut sorted: Vec<&(NaiveDate, BigDecimal)> = history.iter().collect();
+    sorted.sort_by_key(|(date, _)| *date);
+
+    for (date, amount) in sorted {
+        let month = date.with_day(1).unwrap_or(*date);
+        match months.last_mut() {
+            Some(last) if last.month == month => last.amount += amount,
+            _ => months.push(MonthAmount { month, amount: amount.clone() }),
+        }
+    }
+    months
+}
+
+/// Estimates for a ledger dated `ledger_date`, ignoring that month and anything after it
+pub fn estimates(history: &[(NaiveDate, BigDecimal)], ledger_date: NaiveDate) -> BillEstimates {
+    let ledger_month = ledger_date.with_day(1).unwrap_or(ledger_date);
+    let prior: Vec<MonthAmount> = monthly_amounts(history)
+        .into_iter()
+        .filter(|m| m.month < ledger_month)
+        .collect();
+
+    let recent: Vec<&MonthAmount> = prior.iter().rev().take(AVERAGE_MONTHS).collect();
+    let three_month_average = if recent.is_empty() {
+        None
+    } else {
+        let total: BigDecimal = recent.iter().map(|m| m.amount.clone()).sum();
+        Some((total / BigDecimal::from(recent.len() as u32)).with_scale_round(2, RoundingMode::HalfUp))
+    };
+
+    let same_month_last_year = ledger_month
+        .with_year(ledger_month.year() - 1)
+        .and_then(|last_year| prior.iter().find(|m| m.month == last_year))
+        .map(|m| m.amount.clone());
+
+    BillEstimates {
+        last: prior.last().map(|m| m.amount.clone()),
+        three_month_average,
+        same_month_last_year,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn dec(value: &str) -> BigDecimal {
+        BigDecimal::from_str(value).unwrap()
+    }
+
+    #[test]
+    fn test_estimates_use_months_before_the_ledger() {
+        let history = vec![
+            (date(2025, 11, 1), dec("140.00")),
+            (date(2026, 8, 1), dec("80.00")),
+            (date(2026, 9, 1), dec("95.50")),
+            (date(2026, 10, 1), dec("110.25")),
+            (date(2026, 11, 1), dec("150.00")),
+        ];
+
+        let found = estimates(&history, date(2026, 11, 15));
+
+        assert_eq!(found.last, Some(dec("110.25")));
+        assert_eq!(found.three_month_average, Some(dec("95.25")));
+        assert_eq!(found.same_month_last_year, Some(dec("140.00")));
+        assert_eq!(found.preferred(), Some(&dec("95.25")));
+    }
+
+    #[test]
+    fn test_months_with_two_ledgers_are_summed() {
+        let history = vec![
+            (date(2026, 9, 15), dec("40")),
+            (date(2026, 9, 1), dec("60")),
+        ];
+
+        let months = monthly_amounts(&history);
+
+        assert_eq!(months, vec![MonthAmount { month: date(2026, 9, 1), amount: dec("100") }]);
+        assert_eq!(estimates(&history, date(2026, 10, 1)).three_month_average, Some(dec("100.00")));
+    }
+
+    #[test]
+    fn test_no_history_means_no_estimates() {
+        let found = estimates(&[(date(2026, 12, 1), dec("10"))], date(2026, 11, 1));
+
+        assert!(found.is_empty());
+        assert_eq!(found.preferred(), None);
+    }
+}
@@ -2,26 +2,38 @@ use std::cmp::Ordering;
 use std::rc::Rc;
 use std::str::FromStr;
 use bigdecimal::BigDecimal;
-use chrono::{Datelike, Local, NaiveDate};
+use chrono::{Datelike, Local, Months, NaiveDate};
 use cursive::Cursive;
 use cursive::traits::*;
-use cursive::views::{Button, Checkbox, Dialog, EditView, HideableView, LinearLayout, ListView, Panel, TextArea};
+use cursive::views::{Button, Checkbox, Dialog, EditView, HideableView, LinearLayout, ListView, Panel, SelectView, TextArea, TextView};
 use cursive_table_view::{TableView, TableViewItem};
+use crate::bill_history_logic;
+use crate::debt_logic::{self, AmortizationRow};
 use crate::models;
 use crate::repositories::bill_repo::BillRepo;
+use crate::repositories::{CategoryRepo, EnvelopeRepo, LedgerRepo, LoanRepo};
 use crate::ui_helpers::toggle_buttons_visible;
 
 // Button name constants
 const BILL_EDIT_BUTTON: &str = "bill_table_edit_button";
 const BILL_DELETE_BUTTON: &str = "bill_table_delete_button";
-const TOGGLE_BUTTONS: &[&str] = &[BILL_EDIT_BUTTON, BILL_DELETE_BUTTON];
+const BILL_LOAN_BUTTON: &str = "bill_table_loan_button";
+const BILL_HISTORY_BUTTON: &str = "bill_table_history_button";
+const TOGGLE_BUTTONS: &[&str] = &[BILL_EDIT_BUTTON, BILL_DELETE_BUTTON, BILL_LOAN_BUTTON, BILL_HISTORY_BUTTON];
+
+/// Rows in the bill history chart
+const HISTORY_CHART_HEIGHT: usize = 8;
+
+/// Characters per month column in the bill history chart
+const HISTORY_CHART_COLUMN_WIDTH: usize = 3;
 
 #[derive(Copy, Clone, PartialEq, Eq, Hash)]
 enum BasicColumn {
     Name,
     Amount,
     DueDay,
-    IsAutoPay
+    IsAutoPay,
+    Category
 }
 
 #[derive(Clone, Debug)]
@@ -32,6 +44,10 @@ struct BillDisplay {
     due_day: Option<u32>,
     is_auto_pay: bool,
     notes: Option<String>,
+    category_id: Option<i32>,
+    category_name: Option<String>,
+    envelope_id: Option<i32>,
+    tax_category: Option<String>,
 }
 
 impl From<models::Bill> for BillDisplay {
@@ -43,6 +59,10 @@ impl From<models::Bill> for BillDisplay {
             due_day: bill.due_day.map(|d| d.day()),
             is_auto_pay: bill.is_auto_pay,
             notes: bill.notes,
+            category_id: bill.category_id,
+            category_name: None,
+            envelope_id: bill.envelope_id,
+            tax_category: bill.tax_category,
         }
     }
 }
@@ -53,7 +73,8 @@ impl TableViewItem<BasicColumn> for BillDisplay {
             BasicColumn::Name => self.name.to_string(),
             BasicColumn::Amount => self.amount.to_string(),
             BasicColumn::DueDay => self.due_day.map_or("-".to_string(), |d| d.to_string()),
-            BasicColumn::IsAutoPay => self.is_auto_pay.to_string()
+            BasicColumn::IsAutoPay => self.is_auto_pay.to_string(),
+            BasicColumn::Category => self.category_name.clone().unwrap_or_else(|| "-".to_string()),
         }
     }
 
@@ -66,32 +87,53 @@ impl TableViewItem<BasicColumn> for BillDisplay {
             BasicColumn::Amount => self.amount.cmp(&other.amount),
             BasicColumn::DueDay => self.due_day.cmp(&other.due_day),
             BasicColumn::IsAutoPay => Ordering::Equal,
+            BasicColumn::Category => self.category_name.cmp(&other.category_name),
         }
     }
 }
 
 pub struct BillTableView {
     table: TableView<BillDisplay,BasicColumn>,
-    bill_repo: Rc<BillRepo>
+    bill_repo: Rc<BillRepo>,
+    category_repo: Rc<CategoryRepo>,
+    envelope_repo: Rc<EnvelopeRepo>,
+    loan_repo: Rc<LoanRepo>,
+    ledger_repo: Rc<LedgerRepo>
 }
 
-impl BillTableView {
-    pub fn new(bill_repo: Rc<BillRepo>) -> Self {
-        let results = bill_repo.find_all();
+/// Load all bills with their category names filled in
+fn load_bill_displays(bill_repo: &BillRepo, category_repo: &CategoryRepo) -> Vec<BillDisplay> {
+    let categories = category_repo.find_all();
+
+    bill_repo.find_all()
+        .into_iter()
+        .map(|b| {
+            let mut display: BillDisplay = b.into();
+            display.category_name = display.category_id
+                .and_then(|cid| categories.iter().find(|c| c.id == cid))
+                .map(|c| c.name.clone());
+            display
+        })
+        .collect()
+}
 
-        let bill_displays: Vec<BillDisplay> = results
-            .into_iter()
-            .map(|b| b.into())
-            .collect();
+impl BillTableView {
+    pub fn new(bill_repo: Rc<BillRepo>, category_repo: Rc<CategoryRepo>, envelope_repo: Rc<EnvelopeRepo>, loan_repo: Rc<LoanRepo>, ledger_repo: Rc<LedgerRepo>) -> Self {
+        let bill_displays = load_bill_displays(&bill_repo, &category_repo);
 
         Self {
             table: TableView::<BillDisplay,BasicColumn>::new()
-                .column(BasicColumn::Name, "Name", |c| c.width_percent(30))
-                .column(BasicColumn::Amount, "Amount", |c| c.width_percent(25))
-                .column(BasicColumn::DueDay, "Due Day", |c| c.width_percent(25))
-                .column(BasicColumn::IsAutoPay, "Auto Pay", |c| c.width_percent(20))
+                .column(BasicColumn::Name, "Name", |c| c.width_percent(25))
+                .column(BasicColumn::Amount, "Amount", |c| c.width_percent(20))
+                .column(BasicColumn::DueDay, "Due Day", |c| c.width_percent(15))
+                .column(BasicColumn::IsAutoPay, "Auto Pay", |c| c.width_percent(15))
+                .column(BasicColumn::Category, "Category", |c| c.width_percent(25))
                 .items(bill_displays),
-            bill_repo
+            bill_repo,
+            category_repo,
+            envelope_repo,
+            loan_repo,
+            ledger_repo
         }
     }
 
@@ -101,19 +143,54 @@ impl BillTableView {
         let repo_add = Rc::clone(&self.bill_repo);
         let repo_edit = Rc::clone(&self.bill_repo);
         let repo_delete = Rc::clone(&self.bill_repo);
+        let repo_category_add = Rc::clone(&self.category_repo);
+        let repo_category_edit = Rc::clone(&self.category_repo);
+        let repo_category_delete = Rc::clone(&self.category_repo);
+        let repo_categories = Rc::clone(&self.category_repo);
+        let repo_categories_bill = Rc::clone(&self.bill_repo);
+        let repo_envelope_add = Rc::clone(&self.envelope_repo);
+        let repo_envelope_edit = Rc::clone(&self.envelope_repo);
+        let repo_loan = Rc::clone(&self.loan_repo);
+        let repo_history = Rc::clone(&self.ledger_repo);
 
         let buttons = LinearLayout::horizontal()
-            .child(Button::new("Add", move |s| bill_form(s, None, &repo_add)))
+            .child(Button::new("Add", move |s| bill_form(s, None, &repo_add, &repo_category_add, &repo_envelope_add)))
             .child(HideableView::new(Button::new("Edit", move |s| {
                 let selected = s.call_on_name("bill_table", |v: &mut TableView<BillDisplay, BasicColumn>| {
                     v.borrow_item(v.item().unwrap()).cloned()
                 }).flatten();
 
                 if let Some(bill) = selected {
-                    bill_form(s, Some(bill), &repo_edit);
+                    bill_form(s, Some(bill), &repo_edit, &repo_category_edit, &repo_envelope_edit);
                 }
             })).with_name(BILL_EDIT_BUTTON))
-            .child(HideableView::new(Button::new("Delete", move |s| delete_bill(s, &repo_delete))).with_name(BILL_DELETE_BUTTON));
+            .child(HideableView::new(Button::new("Delete", move |s| delete_bill(s, &repo_delete, &repo_category_delete))).with_name(BILL_DELETE_BUTTON))
+            .child(HideableView::new(Button::new("Loan", move |s| {
+                let selected = s.call_on_name("bill_table", |v: &mut TableView<BillDisplay, BasicColumn>| {
+                    v.borrow_item(v.item().unwrap()).cloned()
+                }).flatten();
+
+                if let Some(bill) = selected {
+                    loan_form(s, &bill, &repo_loan);
+                }
+            })).with_name(BILL_LOAN_BUTTON))
+            .child(HideableView::new(Button::new("History", move |s| {
+                let selected = s.call_on_name("bill_table", |v: &mut TableView<BillDisplay, BasicColumn>| {
+                    v.borrow_item(v.item().unwrap()).cloned()
+                }).flatten();
+
+                if let Some(bill) = selected {
+                    show_bill_history(s, &bill, &repo_history);
+                }
+            })).with_name(BILL_HISTORY_BUTTON))
+            .child(Button::new("Categories", move |s| {
+                // Category names may have changed by the time the screen closes
+                let bill_repo = Rc::clone(&repo_categories_bill);
+                let category_repo = Rc::clone(&repo_categories);
+                crate::category_table::show_category_table(s, &repo_categories, move |s| {
+                    reload_table(s, &bill_repo, &category_repo);
+                });
+            }));
         let bill_count = self.table.len();
         let content = LinearLayout::vertical()
             .child(Panel::new(
@@ -135,7 +212,18 @@ impl BillTableView {
     }
 }
 
-fn bill_form(siv: &mut Cursive, existing: Option<BillDisplay>, bill_repo: &Rc<BillRepo>) {
+fn reload_table(siv: &mut Cursive, bill_repo: &BillRepo, category_repo: &CategoryRepo) {
+    let bill_displays = load_bill_displays(bill_repo, category_repo);
+    let bill_count = bill_displays.len();
+
+    siv.call_on_name("bill_table", |v: &mut TableView<BillDisplay, BasicColumn>| {
+        v.set_items(bill_displays);
+    });
+
+    toggle_buttons_visible(siv, bill_count, TOGGLE_BUTTONS);
+}
+
+fn bill_form(siv: &mut Cursive, existing: Option<BillDisplay>, bill_repo: &Rc<BillRepo>, category_repo: &Rc<CategoryRepo>, envelope_repo: &Rc<EnvelopeRepo>) {
     let is_edit = existing.is_some();
     let title = if is_edit { "Edit Bill" } else { "Add Bill" };
     let button_label = if is_edit { "Update" } else { "Ok" };
@@ -166,9 +254,37 @@ fn bill_form(siv: &mut Cursive, existing: Option<BillDisplay>, bill_repo: &Rc<Bi
         .and_then(|b| b.notes.clone())
         .unwrap_or_default();
 
+    let category_value = existing
+        .as_ref()
+        .and_then(|b| b.category_id);
+
+    let category_select = crate::category_table::build_category_select(&category_repo.find_all(), None, category_value);
+
+    // Paying the bill draws down this envelope
+    let envelope_value = existing
+        .as_ref()
+        .and_then(|b| b.envelope_id);
+
+    let mut envelope_select = SelectView::new().popup();
+    envelope_select.add_item("(None)", None);
+    let envelopes = envelope_repo.find_all();
+    for envelope in &envelopes {
+        envelope_select.add_item(envelope.name.clone(), Some(envelope.id));
+    }
+    let envelope_index = envelope_value
+        .and_then(|eid| envelopes.iter().position(|e| e.id == eid))
+        .map_or(0, |i| i + 1);
+    let envelope_select = envelope_select.selected(envelope_index);
+
+    let tax_value = existing
+        .as_ref()
+        .and_then(|b| b.tax_category.clone());
+    let tax_select = crate::tax_report::build_tax_category_select("(None)", tax_value.as_deref());
+
     let bill_id = existing.map(|b| b.id);
 
     let repo = Rc::clone(bill_repo);
+    let repo_category = Rc::clone(category_repo);
     siv.add_layer(
         Dialog::new()
             .title(title)
@@ -193,6 +309,18 @@ fn bill_form(siv: &mut Cursive, existing: Option<BillDisplay>, bill_repo: &Rc<Bi
                     v.get_content().to_string()
                 }).unwrap();
 
+                let category_id = s.call_on_name("category_select", |v: &mut SelectView<Option<i32>>| {
+                    v.selection().and_then(|c| *c)
+                }).flatten();
+
+                let envelope_id = s.call_on_name("envelope_select", |v: &mut SelectView<Option<i32>>| {
+                    v.selection().and_then(|e| *e)
+                }).flatten();
+
+                let tax_category = s.call_on_name("bill_tax_select", |v: &mut SelectView<Option<String>>| {
+                    v.selection().and_then(|t| (*t).clone())
+                }).flatten();
+
                 // Validate name
                 if name_str.trim().is_empty() {
                     s.add_layer(Dialog::info("Name cannot be empty"));
@@ -221,42 +349,27 @@ fn bill_form(siv: &mut Cursive, existing: Option<BillDisplay>, bill_repo: &Rc<Bi
                     return;
                 }
 
+                let bill = models::NewBill {
+                    name: name_str.to_string(),
+                    amount: amount_bd.unwrap(),
+                    due_day: due_date,
+                    is_auto_pay: is_auto,
+                    notes: if notes_str.is_empty() { None } else { Some(notes_str.to_string()) },
+                    category_id,
+                    envelope_id,
+                    tax_category,
+                };
+
                 if let Some(record_id) = bill_id {
                     // Update existing
-                    repo.update(
-                        record_id,
-                        name_str.to_string(),
-                        amount_bd.unwrap(),
-                        due_date,
-                        is_auto,
-                        if notes_str.is_empty() { None } else { Some(notes_str.to_string()) }
-                    );
+                    repo.update(record_id, bill);
                 } else {
                     // Insert new
-                    repo.create(
-                        name_str.to_string(),
-                        amount_bd.unwrap(),
-                        due_date,
-                        is_auto,
-                        if notes_str.is_empty() { None } else { Some(notes_str.to_string()) }
-                    );
+                    repo.create(bill);
                 }
 
-                // Reload table
-                let results = repo.find_all();
-
-                let bill_displays: Vec<BillDisplay> = results
-                    .into_iter()
-                    .map(|b| b.into())
-                    .collect();
-                let bill_count = bill_displays.len();
-
-                s.call_on_name("bill_table", |v: &mut TableView<BillDisplay, BasicColumn>| {
-                    v.set_items(bill_displays);
-                });
                 s.pop_layer();
-
-                toggle_buttons_visible(s, bill_count, TOGGLE_BUTTONS);
+                reload_table(s, &repo, &repo_category);
             })
             .button("Cancel", |s| { s.pop_layer(); })
             .content(
@@ -271,37 +384,192 @@ fn bill_form(siv: &mut Cursive, existing: Option<BillDisplay>, bill_repo: &Rc<Bi
                         }
                         cb.with_name("auto_pay_checkbox")
                     })
+                    .child("Category", category_select.with_name("category_select"))
+                    .child("Envelope", envelope_select.with_name("envelope_select"))
+                    .child("Tax", tax_select.with_name("bill_tax_select"))
                     .child("Notes", TextArea::new().content(notes_value).with_name("notes_input").min_size((40, 3)))
             )
     );
 }
 
-fn delete_bill(siv: &mut Cursive, bill_repo: &Rc<BillRepo>) {
+/// Monthly chart of what a bill came to across ledgers, with estimates for next month
+fn show_bill_history(siv: &mut Cursive, bill: &BillDisplay, ledger_repo: &LedgerRepo) {
+    let history = ledger_repo.find_bill_amount_history(bill.id);
+    let months = bill_history_logic::monthly_amounts(&history);
+
+    if months.is_empty() {
+        siv.add_layer(Dialog::info(format!("{} hasn't been added to a ledger yet", bill.name)));
+        return;
+    }
+
+    let values: Vec<BigDecimal> = months.iter().map(|m| m.amount.clone()).collect();
+    let mut text = String::new();
+    for row in crate::trend_logic::column_chart(&values, HISTORY_CHART_HEIGHT, HISTORY_CHART_COLUMN_WIDTH) {
+        text.push_str(&format!("  {}\n", row));
+    }
+    let months_row: String = months
+        .iter()
+        .map(|m| format!("{:<width$} ", m.month.format("%b").to_string(), width = HISTORY_CHART_COLUMN_WIDTH))
+        .collect();
+    let years_row: String = months
+        .iter()
+        .enumerate()
+        .map(|(i, m)| {
+            let label = if i == 0 || m.month.month() == 1 { m.month.format("'%y").to_string() } else { String::new() };
+            format!("{:<width$} ", label, width = HISTORY_CHART_COLUMN_WIDTH)
+        })
+        .collect();
+    text.push_str(&format!("  {}\n  {}\n\n", months_row.trim_end(), years_row.trim_end()));
+
+    for month in months.iter().rev() {
+        text.push_str(&format!("{:<10}{:>12}\n", month.month.format("%m/%Y").to_string(), format!("${}", month.amount)));
+    }
+
+    // Estimates as add_bill_to_ledger would offer them for next month's ledger
+    let today = Local::now().date_naive();
+    let next_month = today.with_day(1).and_then(|d| d.checked_add_months(Months::new(1))).unwrap_or(today);
+    let estimates = bill_history_logic::estimates(&history, next_month);
+    let or_dash = |amount: &Option<BigDecimal>| amount.as_ref().map_or("-".to_string(), |a| format!("${}", a));
+    text.push_str(&format!(
+        "\nEstimates for {}\n  Last amount: {}\n  3-month average: {}\n  Same month last year: {}\n  Catalog: ${}",
+        next_month.format("%m/%Y"),
+        or_dash(&estimates.last),
+        or_dash(&estimates.three_month_average),
+        or_dash(&estimates.same_month_last_year),
+        bill.amount
+    ));
+
+    siv.add_layer(
+        Dialog::around(TextView::new(text).scrollable())
+            .title(format!("{} Amount History", bill.name))
+            .button("Close", |s| { s.pop_layer(); })
+    );
+}
+
+fn loan_form(siv: &mut Cursive, bill: &BillDisplay, loan_repo: &Rc<LoanRepo>) {
+    let existing = loan_repo.find_by_bill(bill.id);
+
+    let principal_value = existing.as_ref().map(|l| l.principal.to_string()).unwrap_or_else(|| bill.amount.to_string());
+    let rate_value = existing.as_ref().map(|l| l.annual_rate.to_string()).unwrap_or_default();
+    let term_value = existing.as_ref().map(|l| l.term_months.to_string()).unwrap_or_default();
+    let start_value = existing
+        .as_ref()
+        .map(|l| l.start_date)
+        .unwrap_or_else(|| Local::now().date_naive())
+        .format("%d/%m/%Y")
+        .to_string();
+    let notes_value = existing.as_ref().and_then(|l| l.notes.clone()).unwrap_or_default();
+
+    let bill_id = bill.id;
+    let bill_name = bill.name.clone();
+    let repo_save = Rc::clone(loan_repo);
+
+    let mut dialog = Dialog::new()
+        .title(format!("Loan: {}", bill.name))
+        .button("Save", move |s| {
+            let (principal, annual_rate, term_months, start_date) = match get_loan_form_values(s) {
+                Ok(values) => values,
+                Err(message) => {
+                    s.add_layer(Dialog::info(message));
+                    return;
+                }
+            };
+            let notes_str = s.call_on_name("loan_notes_input", |v: &mut TextArea| v.get_content().to_string()).unwrap();
+
+            repo_save.save(
+                bill_id,
+                principal,
+                annual_rate,
+                term_months,
+                start_date,
+                if notes_str.is_empty() { None } else { Some(notes_str) }
+            );
+
+            s.pop_layer();
+        })
+        .button("Schedule", move |s| {
+            match get_loan_form_values(s) {
+                Ok((principal, annual_rate, term_months, start_date)) => {
+                    let schedule = debt_logic::amortization_schedule(&principal, &annual_rate, term_months as u32, start_date);
+                    show_amortization_schedule(s, &bill_name, &schedule);
+                }
+                Err(message) => s.add_layer(Dialog::info(message)),
+            }
+        });
+
+    if let Some(loan) = existing {
+        let repo_remove = Rc::clone(loan_repo);
+        dialog.add_button("Remove", move |s| {
+            repo_remove.delete(loan.id);
+            s.pop_layer();
+        });
+    }
+
+    siv.add_layer(
+        dialog
+            .button("Cancel", |s| { s.pop_layer(); })
+            .content(
+                ListView::new()
+                    .child("Principal", EditView::new().content(principal_value).with_name("loan_principal_input").fixed_width(20))
+                    .child("Rate (APR %)", EditView::new().content(rate_value).with_name("loan_rate_input").fixed_width(20))
+                    .child("Term (months)", EditView::new().content(term_value).with_name("loan_term_input").fixed_width(20))
+                    .child("First Payment", EditView::new().content(start_value).with_name("loan_start_input").fixed_width(20))
+                    .child("Notes", TextArea::new().content(notes_value).with_name("loan_notes_input").min_size((40, 3)))
+            )
+    );
+}
+
+fn get_loan_form_values(s: &mut Cursive) -> Result<(BigDecimal, BigDecimal, i32, NaiveDate), &'static str> {
+    let principal_str = s.call_on_name("loan_principal_input", |v: &mut EditView| v.get_content()).unwrap();
+    let rate_str = s.call_on_name("loan_rate_input", |v: &mut EditView| v.get_content()).unwrap();
+    let term_str = s.call_on_name("loan_term_input", |v: &mut EditView| v.get_content()).unwrap();
+    let start_str = s.call_on_name("loan_start_input", |v: &mut EditView| v.get_content()).unwrap();
+
+    let principal = BigDecimal::from_str(principal_str.trim()).map_err(|_| "Invalid principal format")?;
+    let annual_rate = BigDecimal::from_str(rate_str.trim()).map_err(|_| "Invalid rate format")?;
+    let term_months = term_str.trim().parse::<i32>().ok().filter(|t| *t > 0).ok_or("Term must be a positive number of months")?;
+    let start_date = NaiveDate::parse_from_str(start_str.trim(), "%d/%m/%Y").map_err(|_| "Invalid date format. Use DD/MM/YYYY")?;
+
+    Ok((principal, annual_rate, term_months, start_date))
+}
+
+fn show_amortization_schedule(siv: &mut Cursive, bill_name: &str, schedule: &[AmortizationRow]) {
+    let total_interest: BigDecimal = schedule.iter().map(|r| r.interest.clone()).sum();
+    let monthly_payment = schedule.first().map(|r| r.payment.clone()).unwrap_or(BigDecimal::from(0));
+
+    let mut content = format!("Monthly Payment: ${}\nTotal Interest: ${}\n\n", monthly_payment, total_interest);
+    content.push_str(&format!("{:>4}  {:<12}{:>12}{:>12}{:>12}{:>14}\n", "#", "DUE", "PAYMENT", "INTEREST", "PRINCIPAL", "BALANCE"));
+    content.push_str("──────────────────────────────────────────────────────────────────\n");
+    for row in schedule {
+        let due_str = row.date.format("%d/%m/%Y").to_string();
+        let payment_str = format!("${}", row.payment);
+        let interest_str = format!("${}", row.interest);
+        let principal_str = format!("${}", row.principal);
+        let balance_str = format!("${}", row.balance);
+        content.push_str(&format!("{:>4}  {:<12}{:>12}{:>12}{:>12}{:>14}\n", row.number, due_str, payment_str, interest_str, principal_str, balance_str));
+    }
+
+    siv.add_layer(
+        Dialog::around(TextView::new(content).scrollable())
+            .title(format!("{} Amortization", bill_name))
+            .button("Close", |s| { s.pop_layer(); })
+    );
+}
+
+fn delete_bill(siv: &mut Cursive, bill_repo: &Rc<BillRepo>, category_repo: &Rc<CategoryRepo>) {
     let selected = siv.call_on_name("bill_table", |v: &mut TableView<BillDisplay, BasicColumn>| {
         v.borrow_item(v.item().unwrap()).cloned()
     }).flatten();
 
     if let Some(bill) = selected {
         let repo = Rc::clone(bill_repo);
+        let repo_category = Rc::clone(category_repo);
         siv.add_layer(
             Dialog::text(format!("Delete bill '{}'?", bill.name))
                 .button("Yes", move |s| {
                     repo.delete(bill.id);
 
-                    // Reload table
-                    let results = repo.find_all();
-
-                    let bill_displays: Vec<BillDisplay> = results
-                        .into_iter()
-                        .map(|b| b.into())
-                        .collect();
-                    let item_count = bill_displays.iter().count();
-
-                    s.call_on_name("bill_table", |v: &mut TableView<BillDisplay, BasicColumn>| {
-                        v.set_items(bill_displays);
-                    });
-
-                    toggle_buttons_visible(s, item_count, TOGGLE_BUTTONS);
+                    reload_table(s, &repo, &repo_category);
 
                     s.pop_layer();
                 })
@@ -0,0 +1,406 @@
+use std::collections::HashSet;
+use bigdecimal::{BigDecimal, RoundingMode};
+use chrono::{Datelike, NaiveDate};
+
+/// A category as far as budgeting is concerned
+#[derive(Debug, Clone)]
+pub struct CategoryNode {
+    pub id: i32,
+    pub parent_id: Option<i32>,
+    pub name: String,
+    pub monthly_budget: Option<BigDecimal>,
+}
+
+/// Budget vs. actual for one category, including its subcategories
+#[derive(Debug, Clone, PartialEq)]
+pub struct CategoryBudgetRow {
+    pub category_id: Option<i32>,
+    pub name: String,
+    pub depth: usize,
+    pub budget: Option<BigDecimal>,
+    pub planned: BigDecimal,
+    pub paid: BigDecimal,
+    pub remaining: Option<BigDecimal>,
+}
+
+/// Order categories parent-first, children alphabetically, with their depth
+/// Categories whose parent is missing are treated as top-level
+pub fn order_category_tree(nodes: &[CategoryNode]) -> Vec<(CategoryNode, usize)> {
+    let known_ids: HashSet<i32> = nodes.iter().map(|n| n.id).collect();
+    let mut roots: Vec<&CategoryNode> = nodes
+        .iter()
+        .filter(|n| n.parent_id.is_none_or(|pid| !known_ids.contains(&pid)))
+        .collect();
+    roots.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let mut ordered = Vec::new();
+    let mut visited = HashSet::new();
+    for root in roots {
+        push_subtree(nodes, root, 0, &mut visited, &mut ordered);
+    }
+    ordered
+}
+
+fn push_subtree(
+    nodes: &[CategoryNode],
+    node: &CategoryNode,
+    depth: usize,
+    visited: &mut HashSet<i32>,
+    ordered: &mut Vec<(CategoryNode, usize)>,
+) {
+    if !visited.insert(node.id) {
+        return;
+    }
+    ordered.push((node.clone(), depth));
+
+    let mut children: Vec<&CategoryNode> = nodes
+        .iter()
+        .filter(|n| n.parent_id == Some(node.id))
+        .collect();
+    children.sort_by(|a, b| a.name.cmp(&b.name));
+    for child in children {
+        push_subtree(nodes, child, depth + 1, visited, ordered);
+    }
+}
+
+/// A category's id together with the ids of everything beneath it
+pub fn descendant_ids(nodes: &[CategoryNode], category_id: i32) -> HashSet<i32> {
+    let mut ids = HashSet::new();
+    let mut pending = vec![category_id];
+    while let Some(current) = pending.pop() {
+        if ids.insert(current) {
+            pending.extend(nodes.iter().filter(|n| n.parent_id == Some(current)).map(|n| n.id));
+        }
+    }
+    ids
+}
+
+/// Summarize planned and paid amounts per category against monthly budgets
+/// Items are (category, planned amount, paid amount); parents include their children
+pub fn summarize_category_budgets(
+    nodes: &[CategoryNode],
+    items: &[(Option<i32>, BigDecimal, BigDecimal)],
+) -> Vec<CategoryBudgetRow> {
+    let mut rows = Vec::new();
+
+    for (node, depth) in order_category_tree(nodes) {
+        let ids = descendant_ids(nodes, node.id);
+        let in_subtree = |category_id: &Option<i32>| category_id.is_some_and(|cid| ids.contains(&cid));
+
+        let planned: BigDecimal = items.iter()
+            .filter(|(cid, _, _)| in_subtree(cid))
+            .map(|(_, amount, _)| amount.clone())
+            .sum();
+        let paid: BigDecimal = items.iter()
+            .filter(|(cid, _, _)| in_subtree(cid))
+            .map(|(_, _, paid)| paid.clone())
+            .sum();
+        let subtree_has_budget = nodes.iter()
+            .any(|n| ids.contains(&n.id) && n.monthly_budget.is_some());
+
+        if !subtree_has_budget && planned == BigDecimal::from(0) {
+            continue;
+        }
+
+        rows.push(CategoryBudgetRow {
+            category_id: Some(node.id),
+            name: node.name.clone(),
+            depth,
+            remaining: node.monthly_budget.as_ref().map(|b| b - &paid),
+            budget: node.monthly_budget.clone(),
+            planned,
+            paid,
+        });
+    }
+
+    // Anything without a known category
+    let known_ids: HashSet<i32> = nodes.iter().map(|n| n.id).collect();
+    let uncategorized: Vec<&(Option<i32>, BigDecimal, BigDecimal)> = items.iter()
+        .filter(|(cid, _, _)| cid.is_none_or(|c| !known_ids.contains(&c)))
+        .collect();
+    if !uncategorized.is_empty() {
+        rows.push(CategoryBudgetRow {
+            category_id: None,
+            name: "Uncategorized".to_string(),
+            depth: 0,
+            budget: None,
+            planned: uncategorized.iter().map(|(_, amount, _)| amount.clone()).sum(),
+            paid: uncategorized.iter().map(|(_, _, paid)| paid.clone()).sum(),
+            remaining: None,
+        });
+    }
+
+    rows
+}
+
+/// What one ledger did with its envelopes: funds given to and spent from each
+#[derive(Debug, Clone, Default)]
+pub struct EnvelopePeriod {
+    pub allocations: Vec<(i32, BigDecimal)>,
+    pub spending: Vec<(i32, BigDecimal)>,
+}
+
+/// An envelope's position within a ledger
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnvelopeBalance {
+    pub envelope_id: i32,
+    pub carried_in: BigDecimal,
+    pub allocated: BigDecimal,
+    pub spent: BigDecimal,
+    pub balance: BigDecimal,
+}
+
+/// Roll envelope balances through a chain of ledgers (oldest first)
+/// Returns each envelope's position in the last ledger; overspending carries forward too
+pub fn roll_envelope_balances(envelope_ids: &[i32], periods: &[EnvelopePeriod]) -> Vec<EnvelopeBalance> {
+    let sum_for = |entries: &[(i32, BigDecimal)], envelope_id: i32| -> BigDecimal {
+        entries
+            .iter()
+            .filter(|(eid, _)| *eid == envelope_id)
+            .map(|(_, amount)| amount.clone())
+            .sum()
+    };
+
+    envelope_ids
+        .iter()
+        .map(|&envelope_id| {
+            let mut carried_in = BigDecimal::from(0);
+            let mut allocated = BigDecimal::from(0);
+            let mut spent = BigDecimal::from(0);
+
+            for (index, period) in periods.iter().enumerate() {
+                if index > 0 {
+                    carried_in = carried_in + allocated - spent;
+                }
+                allocated = sum_for(&period.allocations, envelope_id);
+                spent = sum_for(&period.spending, envelope_id);
+            }
+
+            EnvelopeBalance {
+                envelope_id,
+                balance: carried_in.clone() + allocated.clone() - spent.clone(),
+                carried_in,
+                allocated,
+                spent,
+            }
+        })
+        .collect()
+}
+
+/// Funds in a ledger that have no job yet
+/// Total less what sits in envelopes and what other lines (bills, set-asides) will take
+pub fn calculate_to_be_budgeted(
+    ledger_total: &BigDecimal,
+    balances: &[EnvelopeBalance],
+    unenveloped_spending: &[BigDecimal],
+) -> BigDecimal {
+    let in_envelopes: BigDecimal = balances
+        .iter()
+        .map(|b| b.carried_in.clone() + b.allocated.clone())
+        .sum();
+    let committed: BigDecimal = unenveloped_spending.iter().sum();
+
+    ledger_total.clone() - in_envelopes - committed
+}
+
+/// Number of monthly contributions left, counting the current and target months
+/// Zero once the target month has passed
+pub fn months_until(as_of: NaiveDate, target_date: NaiveDate) -> i64 {
+    let from = as_of.year() as i64 * 12 + as_of.month0() as i64;
+    let to = target_date.year() as i64 * 12 + target_date.month0() as i64;
+    (to - from + 1).max(0)
+}
+
+/// Monthly set-aside needed to reach a goal by its target date (rounded up to the cent)
+/// With no months left the whole shortfall is due now
+pub fn calculate_monthly_contribution(
+    target_amount: &BigDecimal,
+    saved: &BigDecimal,
+    as_of: NaiveDate,
+    target_date: NaiveDate,
+) -> BigDecimal {
+    let shortfall = target_amount - saved;
+    if shortfall <= BigDecimal::from(0) {
+        return BigDecimal::from(0);
+    }
+
+    match months_until(as_of, target_date) {
+        0 => shortfall,
+        months => (shortfall / BigDecimal::from(months)).with_scale_round(2, RoundingMode::Up),
+    }
+}
+
+/// Percent of a goal saved so far, capped at 100
+pub fn calculate_goal_progress(target_amount: &BigDecimal, saved: &BigDecimal) -> BigDecimal {
+    if *target_amount <= BigDecimal::from(0) {
+        return BigDecimal::from(100);
+    }
+
+    let percent = (saved * BigDecimal::from(100) / target_amount).with_scale_round(0, RoundingMode::Down);
+    percent.min(BigDecimal::from(100))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node(id: i32, parent_id: Option<i32>, name: &str, budget: Option<i32>) -> CategoryNode {
+        CategoryNode {
+            id,
+            parent_id,
+            name: name.to_string(),
+            monthly_budget: budget.map(BigDecimal::from),
+        }
+    }
+
+    #[test]
+    fn test_order_category_tree_parent_first() {
+        let nodes = vec![
+            node(3, Some(1), "Water", None),
+            node(1, None, "Utilities", None),
+            node(2, Some(1), "Electric", None),
+            node(4, None, "Subscriptions", None),
+        ];
+
+        let ordered: Vec<(i32, usize)> = order_category_tree(&nodes)
+            .into_iter()
+            .map(|(n, depth)| (n.id, depth))
+            .collect();
+        assert_eq!(ordered, vec![(4, 0), (1, 0), (2, 1), (3, 1)]);
+    }
+
+    #[test]
+    fn test_summarize_rolls_children_into_parent() {
+        let nodes = vec![
+            node(1, None, "Utilities", Some(300)),
+            node(2, Some(1), "Electric", Some(150)),
+        ];
+        let items = vec![
+            (Some(1), BigDecimal::from(50), BigDecimal::from(50)),
+            (Some(2), BigDecimal::from(120), BigDecimal::from(0)),
+        ];
+
+        let rows = summarize_category_budgets(&nodes, &items);
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].planned, BigDecimal::from(170));
+        assert_eq!(rows[0].paid, BigDecimal::from(50));
+        assert_eq!(rows[0].remaining, Some(BigDecimal::from(250)));
+        assert_eq!(rows[1].planned, BigDecimal::from(120));
+        assert_eq!(rows[1].remaining, Some(BigDecimal::from(150)));
+    }
+
+    #[test]
+    fn test_summarize_skips_idle_categories_and_adds_uncategorized() {
+        let nodes = vec![
+            node(1, None, "Utilities", None),
+            node(2, None, "Subscriptions", Some(50)),
+        ];
+        let items = vec![(None, BigDecimal::from(80), BigDecimal::from(80))];
+
+        let rows = summarize_category_budgets(&nodes, &items);
+        let names: Vec<&str> = rows.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["Subscriptions", "Uncategorized"]);
+        assert_eq!(rows[1].planned, BigDecimal::from(80));
+    }
+
+    #[test]
+    fn test_envelope_balances_roll_over() {
+        // Groceries: 400 in, 350 out; then 400 in, 100 out so far
+        let periods = vec![
+            EnvelopePeriod {
+                allocations: vec![(1, BigDecimal::from(400))],
+                spending: vec![(1, BigDecimal::from(350))],
+            },
+            EnvelopePeriod {
+                allocations: vec![(1, BigDecimal::from(400))],
+                spending: vec![(1, BigDecimal::from(100))],
+            },
+        ];
+
+        let balances = roll_envelope_balances(&[1], &periods);
+        assert_eq!(balances[0].carried_in, BigDecimal::from(50));
+        assert_eq!(balances[0].allocated, BigDecimal::from(400));
+        assert_eq!(balances[0].spent, BigDecimal::from(100));
+        assert_eq!(balances[0].balance, BigDecimal::from(350));
+    }
+
+    #[test]
+    fn test_envelope_overspending_carries_forward() {
+        let periods = vec![
+            EnvelopePeriod {
+                allocations: vec![(1, BigDecimal::from(100))],
+                spending: vec![(1, BigDecimal::from(150))],
+            },
+            EnvelopePeriod::default(),
+        ];
+
+        let balances = roll_envelope_balances(&[1], &periods);
+        assert_eq!(balances[0].carried_in, BigDecimal::from(-50));
+        assert_eq!(balances[0].balance, BigDecimal::from(-50));
+    }
+
+    #[test]
+    fn test_to_be_budgeted() {
+        // 3000 total, 50 carried + 400 given to envelopes, 1200 rent outside any envelope
+        let balances = vec![EnvelopeBalance {
+            envelope_id: 1,
+            carried_in: BigDecimal::from(50),
+            allocated: BigDecimal::from(400),
+            spent: BigDecimal::from(100),
+            balance: BigDecimal::from(350),
+        }];
+
+        let result = calculate_to_be_budgeted(&BigDecimal::from(3000), &balances, &[BigDecimal::from(1200)]);
+        assert_eq!(result, BigDecimal::from(1350));
+    }
+
+    #[test]
+    fn test_months_until_counts_both_ends() {
+        let as_of = NaiveDate::from_ymd_opt(2024, 10, 18).unwrap();
+
+        assert_eq!(months_until(as_of, NaiveDate::from_ymd_opt(2024, 12, 1).unwrap()), 3);
+        assert_eq!(months_until(as_of, NaiveDate::from_ymd_opt(2024, 10, 31).unwrap()), 1);
+        assert_eq!(months_until(as_of, NaiveDate::from_ymd_opt(2024, 9, 30).unwrap()), 0);
+    }
+
+    #[test]
+    fn test_monthly_contribution_rounds_up() {
+        // 1000 insurance due in March, 100 saved, starting in January
+        let result = calculate_monthly_contribution(
+            &BigDecimal::from(1000),
+            &BigDecimal::from(100),
+            NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 3, 15).unwrap(),
+        );
+        assert_eq!(result, "300.00".parse::<BigDecimal>().unwrap());
+
+        let result = calculate_monthly_contribution(
+            &BigDecimal::from(100),
+            &BigDecimal::from(0),
+            NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 3, 15).unwrap(),
+        );
+        assert_eq!(result, "33.34".parse::<BigDecimal>().unwrap());
+    }
+
+    #[test]
+    fn test_monthly_contribution_when_met_or_overdue() {
+        let as_of = NaiveDate::from_ymd_opt(2025, 4, 1).unwrap();
+        let target_date = NaiveDate::from_ymd_opt(2025, 3, 15).unwrap();
+
+        assert_eq!(
+            calculate_monthly_contribution(&BigDecimal::from(1000), &BigDecimal::from(1200), as_of, target_date),
+            BigDecimal::from(0)
+        );
+        assert_eq!(
+            calculate_monthly_contribution(&BigDecimal::from(1000), &BigDecimal::from(600), as_of, target_date),
+            BigDecimal::from(400)
+        );
+    }
+
+    #[test]
+    fn test_goal_progress_capped() {
+        assert_eq!(calculate_goal_progress(&BigDecimal::from(1000), &BigDecimal::from(250)), BigDecimal::from(25));
+        assert_eq!(calculate_goal_progress(&BigDecimal::from(1000), &BigDecimal::from(1500)), BigDecimal::from(100));
+    }
+}
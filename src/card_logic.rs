@@ -0,0 +1,172 @@
+use bigdecimal::BigDecimal;
+use chrono::{Datelike, Months, NaiveDate};
+
+/// One statement period: charges from start through close are due on the due date
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StatementCycle {
+    pub start: NaiveDate,
+    pub close: NaiveDate,
+    pub due: NaiveDate,
+}
+
+/// A day of the month, pulled back to the month's last day when it's short
+pub fn day_in_month(year: i32, month: u32, day: u32) -> NaiveDate {
+    (1..=day.clamp(1, 31))
+        .rev()
+        .find_map(|d| NaiveDate::from_ymd_opt(year, month, d))
+        .unwrap_or_default()
+}
+
+fn shift_month(year: i32, month: u32, months: i32) -> (i32, u32) {
+    let first = NaiveDate::from_ymd_opt(year, month, 1).unwrap_or_default();
+    let shifted = if months < 0 {
+        first.checked_sub_months(Months::new(months.unsigned_abs()))
+    } else {
+        first.checked_add_months(Months::new(months as u32))
+    }
+    .unwrap_or(first);
+    (shifted.year(), shifted.month())
+}
+
+/// The cycle that closes in a given month; payment is due the next time the due day comes round
+pub fn cycle_closing_in(closing_day: u32, due_day: u32, year: i32, month: u32) -> StatementCycle {
+    let close = day_in_month(year, month, closing_day);
+    let (previous_year, previous_month) = shift_month(year, month, -1);
+    let previous_close = day_in_month(previous_year, previous_month, closing_day);
+
+    let due_this_month = day_in_month(year, month, due_day);
+    let due = if due_this_month > close {
+        due_this_month
+    } else {
+        let (next_year, next_month) = shift_month(year, month, 1);
+        day_in_month(next_year, next_month, due_day)
+    };
+
+    StatementCycle {
+        start: previous_close.succ_opt().unwrap_or(previous_close),
+        close,
+        due,
+    }
+}
+
+/// The statement whose payment falls due in a given month
+pub fn statement_due_in(closing_day: u32, due_day: u32, year: i32, month: u32) -> StatementCycle {
+    let cycle = cycle_closing_in(closing_day, due_day, year, month);
+    if cycle.due.year() == year && cycle.due.month() == month {
+        cycle
+    } else {
+        let (previous_year, previous_month) = shift_month(year, month, -1);
+        cycle_closing_in(closing_day, due_day, previous_year, previous_month)
+    }
+}
+
+/// The cycle a charge made on a date lands in
+pub fn cycle_containing(closing_day: u32, due_day: u32, date: NaiveDate) -> StatementCycle {
+    let cycle = cycle_closing_in(closing_day, due_day, date.year(), date.month());
+    if date <= cycle.close {
+        cycle
+    } else {
+        let (next_year, next_month) = shift_month(date.year(), date.month(), 1);
+        cycle_closing_in(closing_day, due_day, next_year, next_month)
+    }
+}
+
+/// Sum of charges posted within a cycle (refunds are negative charges)
+pub fn statement_balance(charges: &[(NaiveDate, BigDecimal)], cycle: &StatementCycle) -> BigDecimal {
+    charges
+        .iter()
+        .filter(|(date, _)| *date >= cycle.start && *date <= cycle.close)
+        .map(|(_, amount)| amount.clone())
+        .sum()
+}
+
+/// The last `count` closed statements as of a date with their balances, newest first
+pub fn recent_statements(
+    closing_day: u32,
+    due_day: u32,
+    charges: &[(NaiveDate, BigDecimal)],
+    as_of: NaiveDate,
+    count: u32,
+) -> Vec<(StatementCycle, BigDecimal)> {
+    let open_cycle = cycle_containing(closing_day, due_day, as_of);
+
+    (1..=count as i32)
+        .map(|months_back| {
+            let (year, month) = shift_month(open_cycle.close.year(), open_cycle.close.month(), -months_back);
+            let cycle = cycle_closing_in(closing_day, due_day, year, month);
+            let balance = statement_balance(charges, &cycle);
+            (cycle, balance)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_due_later_in_closing_month() {
+        let cycle = statement_due_in(5, 28, 2026, 3);
+
+        assert_eq!(cycle.start, date(2026, 2, 6));
+        assert_eq!(cycle.close, date(2026, 3, 5));
+        assert_eq!(cycle.due, date(2026, 3, 28));
+    }
+
+    #[test]
+    fn test_due_in_month_after_closing() {
+        let cycle = statement_due_in(20, 15, 2026, 3);
+
+        assert_eq!(cycle.start, date(2026, 1, 21));
+        assert_eq!(cycle.close, date(2026, 2, 20));
+        assert_eq!(cycle.due, date(2026, 3, 15));
+    }
+
+    #[test]
+    fn test_short_month_clamps_closing_day() {
+        let cycle = cycle_closing_in(31, 25, 2026, 2);
+
+        assert_eq!(cycle.start, date(2026, 2, 1));
+        assert_eq!(cycle.close, date(2026, 2, 28));
+        assert_eq!(cycle.due, date(2026, 3, 25));
+    }
+
+    #[test]
+    fn test_charge_after_close_rolls_to_next_cycle() {
+        let cycle = cycle_containing(20, 15, date(2026, 3, 21));
+
+        assert_eq!(cycle.close, date(2026, 4, 20));
+        assert_eq!(cycle.due, date(2026, 5, 15));
+    }
+
+    #[test]
+    fn test_statement_balance_includes_cycle_ends_and_refunds() {
+        let charges = vec![
+            (date(2026, 2, 20), BigDecimal::from(999)),
+            (date(2026, 2, 21), BigDecimal::from(100)),
+            (date(2026, 3, 10), BigDecimal::from(-30)),
+            (date(2026, 3, 20), BigDecimal::from(50)),
+            (date(2026, 3, 21), BigDecimal::from(999)),
+        ];
+        let cycle = cycle_closing_in(20, 15, 2026, 3);
+
+        assert_eq!(statement_balance(&charges, &cycle), BigDecimal::from(120));
+    }
+
+    #[test]
+    fn test_recent_statements_skip_open_cycle() {
+        let charges = vec![(date(2026, 3, 1), BigDecimal::from(40)), (date(2026, 3, 25), BigDecimal::from(60))];
+
+        let statements = recent_statements(20, 15, &charges, date(2026, 4, 2), 2);
+
+        assert_eq!(statements.len(), 2);
+        assert_eq!(statements[0].0.close, date(2026, 3, 20));
+        assert_eq!(statements[0].1, BigDecimal::from(40));
+        assert_eq!(statements[1].0.close, date(2026, 2, 20));
+        assert_eq!(statements[1].1, BigDecimal::from(0));
+    }
+}
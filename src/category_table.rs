@@ -0,0 +1,277 @@
+use std::cmp::Ordering;
+use std::rc::Rc;
+use std::str::FromStr;
+use bigdecimal::BigDecimal;
+use cursive::Cursive;
+use cursive::traits::*;
+use cursive::views::{Button, Dialog, EditView, HideableView, LinearLayout, ListView, Panel, SelectView};
+use cursive_table_view::{TableView, TableViewItem};
+use crate::budget_logic::{self, CategoryNode};
+use crate::models;
+use crate::repositories::CategoryRepo;
+use crate::ui_helpers::toggle_buttons_visible;
+
+// Button name constants
+const CATEGORY_EDIT_BUTTON: &str = "category_table_edit_button";
+const CATEGORY_DELETE_BUTTON: &str = "category_table_delete_button";
+const TOGGLE_BUTTONS: &[&str] = &[CATEGORY_EDIT_BUTTON, CATEGORY_DELETE_BUTTON];
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+enum BasicColumn {
+    Name,
+    Parent,
+    MonthlyBudget,
+}
+
+#[derive(Clone, Debug)]
+struct CategoryDisplay {
+    id: i32,
+    position: usize,
+    name: String,
+    depth: usize,
+    parent_id: Option<i32>,
+    parent_name: Option<String>,
+    monthly_budget: Option<BigDecimal>,
+}
+
+impl TableViewItem<BasicColumn> for CategoryDisplay {
+    fn to_column(&self, column: BasicColumn) -> String {
+        match column {
+            BasicColumn::Name => format!("{}{}", "  ".repeat(self.depth), self.name),
+            BasicColumn::Parent => self.parent_name.clone().unwrap_or_else(|| "-".to_string()),
+            BasicColumn::MonthlyBudget => self.monthly_budget.as_ref().map_or("-".to_string(), |b| format!("${}", b)),
+        }
+    }
+
+    fn cmp(&self, other: &Self, column: BasicColumn) -> Ordering
+    where
+        Self: Sized
+    {
+        match column {
+            // Keep the tree order so children stay under their parent
+            BasicColumn::Name => self.position.cmp(&other.position),
+            BasicColumn::Parent => self.parent_name.cmp(&other.parent_name),
+            BasicColumn::MonthlyBudget => self.monthly_budget.cmp(&other.monthly_budget),
+        }
+    }
+}
+
+/// Convert stored categories for the budgeting logic
+pub fn to_category_nodes(categories: &[models::Category]) -> Vec<CategoryNode> {
+    categories
+        .iter()
+        .map(|c| CategoryNode {
+            id: c.id,
+            parent_id: c.parent_id,
+            name: c.name.clone(),
+            monthly_budget: c.monthly_budget.clone(),
+        })
+        .collect()
+}
+
+/// Popup of categories, indented by hierarchy, with a "(None)" entry first
+/// `exclude_id` drops a category and everything beneath it (used when picking a parent)
+pub fn build_category_select(categories: &[models::Category], exclude_id: Option<i32>, selected_id: Option<i32>) -> SelectView<Option<i32>> {
+    let nodes = to_category_nodes(categories);
+    let excluded = exclude_id
+        .map(|eid| budget_logic::descendant_ids(&nodes, eid))
+        .unwrap_or_default();
+
+    let mut select = SelectView::new().popup();
+    select.add_item("(None)", None);
+
+    let mut selected_index = 0;
+    for (node, depth) in budget_logic::order_category_tree(&nodes) {
+        if excluded.contains(&node.id) {
+            continue;
+        }
+        if Some(node.id) == selected_id {
+            selected_index = select.len();
+        }
+        select.add_item(format!("{}{}", "  ".repeat(depth), node.name), Some(node.id));
+    }
+
+    select.selected(selected_index)
+}
+
+fn load_category_displays(category_repo: &CategoryRepo) -> Vec<CategoryDisplay> {
+    let categories = category_repo.find_all();
+    let nodes = to_category_nodes(&categories);
+
+    budget_logic::order_category_tree(&nodes)
+        .into_iter()
+        .enumerate()
+        .map(|(position, (node, depth))| CategoryDisplay {
+            id: node.id,
+            position,
+            parent_name: node.parent_id
+                .and_then(|pid| categories.iter().find(|c| c.id == pid))
+                .map(|c| c.name.clone()),
+            name: node.name,
+            depth,
+            parent_id: node.parent_id,
+            monthly_budget: node.monthly_budget,
+        })
+        .collect()
+}
+
+fn reload_table(siv: &mut Cursive, category_repo: &CategoryRepo) {
+    let category_displays = load_category_displays(category_repo);
+    let category_count = category_displays.len();
+
+    siv.call_on_name("category_table", |v: &mut TableView<CategoryDisplay, BasicColumn>| {
+        v.set_items(category_displays);
+    });
+
+    toggle_buttons_visible(siv, category_count, TOGGLE_BUTTONS);
+}
+
+/// Show the category screen; `on_close` runs after it is dismissed
+pub fn show_category_table<F>(siv: &mut Cursive, category_repo: &Rc<CategoryRepo>, on_close: F)
+where
+    F: Fn(&mut Cursive) + 'static,
+{
+    let category_displays = load_category_displays(category_repo);
+    let category_count = category_displays.len();
+
+    let table = TableView::<CategoryDisplay, BasicColumn>::new()
+        .column(BasicColumn::Name, "Name", |c| c.width_percent(40))
+        .column(BasicColumn::Parent, "Parent", |c| c.width_percent(30))
+        .column(BasicColumn::MonthlyBudget, "Monthly Budget", |c| c.width_percent(30))
+        .items(category_displays);
+
+    let repo_add = Rc::clone(category_repo);
+    let repo_edit = Rc::clone(category_repo);
+    let repo_delete = Rc::clone(category_repo);
+
+    let buttons = LinearLayout::horizontal()
+        .child(Button::new("Add", move |s| category_form(s, None, &repo_add)))
+        .child(HideableView::new(Button::new("Edit", move |s| {
+            let selected = s.call_on_name("category_table", |v: &mut TableView<CategoryDisplay, BasicColumn>| {
+                v.borrow_item(v.item().unwrap()).cloned()
+            }).flatten();
+
+            if let Some(category) = selected {
+                category_form(s, Some(category), &repo_edit);
+            }
+        })).with_name(CATEGORY_EDIT_BUTTON))
+        .child(HideableView::new(Button::new("Delete", move |s| delete_category(s, &repo_delete))).with_name(CATEGORY_DELETE_BUTTON))
+        .child(Button::new("Back", move |s| {
+            s.pop_layer();
+            on_close(s);
+        }));
+
+    let content = LinearLayout::vertical()
+        .child(Panel::new(
+            table
+                .with_name("category_table")
+                .min_size((60, 15))
+        ).full_screen())
+        .child(buttons);
+
+    let screen = crate::common_layout::create_screen(
+        "Categories",
+        content,
+        &crate::common_layout::view_footer()
+    );
+
+    siv.add_layer(screen);
+
+    toggle_buttons_visible(siv, category_count, TOGGLE_BUTTONS);
+}
+
+fn category_form(siv: &mut Cursive, existing: Option<CategoryDisplay>, category_repo: &Rc<CategoryRepo>) {
+    let is_edit = existing.is_some();
+    let title = if is_edit { "Edit Category" } else { "Add Category" };
+    let button_label = if is_edit { "Update" } else { "Ok" };
+
+    let name_value = existing
+        .as_ref()
+        .map(|c| c.name.clone())
+        .unwrap_or_default();
+
+    let budget_value = existing
+        .as_ref()
+        .and_then(|c| c.monthly_budget.as_ref().map(|b| b.to_string()))
+        .unwrap_or_default();
+
+    let parent_value = existing.as_ref().and_then(|c| c.parent_id);
+    let category_id = existing.map(|c| c.id);
+
+    let parent_select = build_category_select(&category_repo.find_all(), category_id, parent_value);
+
+    let repo = Rc::clone(category_repo);
+    siv.add_layer(
+        Dialog::new()
+            .title(title)
+            .button(button_label, move |s| {
+                let name_str = s.call_on_name("category_name_input", |v: &mut EditView| {
+                    v.get_content()
+                }).unwrap();
+
+                let budget_str = s.call_on_name("category_budget_input", |v: &mut EditView| {
+                    v.get_content()
+                }).unwrap();
+
+                let parent_id = s.call_on_name("category_parent_select", |v: &mut SelectView<Option<i32>>| {
+                    v.selection().and_then(|p| *p)
+                }).flatten();
+
+                if name_str.trim().is_empty() {
+                    s.add_layer(Dialog::info("Name cannot be empty"));
+                    return;
+                }
+
+                // Budget is optional
+                let monthly_budget = if budget_str.trim().is_empty() {
+                    None
+                } else {
+                    match BigDecimal::from_str(budget_str.trim()) {
+                        Ok(b) => Some(b),
+                        Err(_) => {
+                            s.add_layer(Dialog::info("Invalid budget format"));
+                            return;
+                        }
+                    }
+                };
+
+                if let Some(record_id) = category_id {
+                    repo.update(record_id, name_str.trim().to_string(), parent_id, monthly_budget);
+                } else {
+                    repo.create(name_str.trim().to_string(), parent_id, monthly_budget);
+                }
+
+                s.pop_layer();
+                reload_table(s, &repo);
+            })
+            .button("Cancel", |s| { s.pop_layer(); })
+            .content(
+                ListView::new()
+                    .child("Name", EditView::new().content(name_value).with_name("category_name_input").fixed_width(20))
+                    .child("Parent", parent_select.with_name("category_parent_select"))
+                    .child("Monthly Budget", EditView::new().content(budget_value).with_name("category_budget_input").fixed_width(20))
+            )
+    );
+}
+
+fn delete_category(siv: &mut Cursive, category_repo: &Rc<CategoryRepo>) {
+    let selected = siv.call_on_name("category_table", |v: &mut TableView<CategoryDisplay, BasicColumn>| {
+        v.borrow_item(v.item().unwrap()).cloned()
+    }).flatten();
+
+    if let Some(category) = selected {
+        let repo = Rc::clone(category_repo);
+        siv.add_layer(
+            Dialog::text(format!(
+                "Delete category '{}'?\nSubcategories move to the top level and bills become uncategorized.",
+                category.name
+            ))
+                .button("Yes", move |s| {
+                    repo.delete(category.id);
+                    s.pop_layer();
+                    reload_table(s, &repo);
+                })
+                .button("No", |s| { s.pop_layer(); })
+        );
+    }
+}
@@ -11,7 +11,15 @@ pub fn create_screen<V: View>(title: &str, content: V, footer_hint: &str) -> imp
 
 /// Standard footer hints
 pub fn standard_footer() -> String {
-    "q:Quit | h:Home | i:Income | b:Bills | l:Ledger | p:PTO".to_string()
+    "q:Quit | h:Home | i:Income | b:Bills | l:Ledger | p:PTO | s:Savings | d:Debts | n:Net Worth | t:Tax | r:Trends | f:Forecast | u:Subscriptions | a:Alerts".to_string()
+}
+
+/// Standard footer hints followed by a bill alert badge, e.g. "2 overdue"
+pub fn badged_footer(badge: Option<String>) -> String {
+    match badge {
+        Some(badge) => format!("{}  [! {}]", standard_footer(), badge),
+        None => standard_footer(),
+    }
 }
 
 pub fn view_footer() -> String {
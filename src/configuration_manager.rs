@@ -1,10 +1,23 @@
+use std::str::FromStr;
+use bigdecimal::BigDecimal;
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 
-#[derive(Default, Debug, Serialize, Deserialize)]
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct ConfigurationManager {
-    database_connection_string: Option<String>
+    database_connection_string: Option<String>,
+    cash_flow_buffer: Option<String>,
+    alert_look_ahead_days: Option<u32>,
+    alert_snooze_days: Option<u32>,
+    alerts_snoozed_until: Option<String>,
 }
 
+/// Days ahead bills count as due soon unless configured
+const DEFAULT_ALERT_LOOK_AHEAD_DAYS: u32 = 3;
+
+/// Days the Snooze button quiets the startup alert unless configured
+const DEFAULT_ALERT_SNOOZE_DAYS: u32 = 1;
+
 impl ConfigurationManager {
     pub fn new() -> Self {
         let config_name = if cfg!(debug_assertions) {
@@ -19,6 +32,37 @@ impl ConfigurationManager {
         self.database_connection_string.as_ref().map(|s| s.as_str())
     }
 
+    /// Minimum balance the cash-flow timeline warns about (defaults to 0)
+    pub fn get_cash_flow_buffer(&self) -> BigDecimal {
+        self.cash_flow_buffer
+            .as_ref()
+            .and_then(|s| BigDecimal::from_str(s.trim()).ok())
+            .unwrap_or_default()
+    }
+
+    /// How many days ahead an unpaid bill counts as due soon
+    pub fn get_alert_look_ahead_days(&self) -> u32 {
+        self.alert_look_ahead_days.unwrap_or(DEFAULT_ALERT_LOOK_AHEAD_DAYS)
+    }
+
+    pub fn get_alert_snooze_days(&self) -> u32 {
+        self.alert_snooze_days.unwrap_or(DEFAULT_ALERT_SNOOZE_DAYS)
+    }
+
+    /// Last day the startup bill alert stays hidden, if snoozed
+    pub fn get_alerts_snoozed_until(&self) -> Option<NaiveDate> {
+        self.alerts_snoozed_until
+            .as_ref()
+            .and_then(|s| NaiveDate::parse_from_str(s.trim(), "%Y-%m-%d").ok())
+    }
+
+    /// Hide the startup bill alert through a date and save the configuration file
+    pub fn snooze_alerts_until(&self, until: NaiveDate) -> Result<(), confy::ConfyError> {
+        let mut snoozed = self.clone();
+        snoozed.alerts_snoozed_until = Some(until.format("%Y-%m-%d").to_string());
+        confy::store(Self::get_config_name(), None, &snoozed)
+    }
+
     pub fn get_config_name() -> &'static str {
         if cfg!(debug_assertions) {
             "money-bae-dev"
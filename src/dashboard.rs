@@ -0,0 +1,203 @@
+use std::rc::Rc;
+use bigdecimal::BigDecimal;
+use chrono::{Datelike, Duration, Local};
+use cursive::Cursive;
+use cursive::traits::*;
+use cursive::views::{Button, LinearLayout, Panel, TextView};
+use cursive::View;
+
+use crate::dependency_container::DependencyContainer;
+use crate::models::{PtoPlan, PtoStatus};
+
+/// Days ahead the "due soon" panel looks
+const DUE_SOON_DAYS: i64 = 7;
+
+/// Bills listed before the panel summarizes the rest
+const MAX_LISTED: usize = 5;
+
+/// Home screen panels, each with a button into the view it summarizes
+pub fn build_dashboard(dc: &Rc<DependencyContainer>) -> impl View {
+    let left = LinearLayout::vertical()
+        .child(ledger_panel(dc))
+        .child(due_soon_panel(dc));
+    let right = LinearLayout::vertical()
+        .child(income_panel(dc))
+        .child(pto_panel(dc));
+
+    LinearLayout::horizontal()
+        .child(left.full_width())
+        .child(right.full_width())
+}
+
+/// The latest open ledger, or the latest ledger when every one is closed
+fn current_ledger(dc: &DependencyContainer) -> Option<crate::models::Ledger> {
+    let ledgers = dc.ledger_repo().find_all();
+    let latest_open = ledgers.iter().filter(|l| !l.is_closed).max_by_key(|l| l.date).cloned();
+    latest_open.or_else(|| ledgers.into_iter().max_by_key(|l| l.date))
+}
+
+fn ledger_panel(dc: &Rc<DependencyContainer>) -> impl View {
+    let ledger_repos = dc.ledger_repos();
+    let Some(ledger) = current_ledger(dc) else {
+        let dc_open = Rc::clone(dc);
+        return Panel::new(
+            LinearLayout::vertical()
+                .child(TextView::new("No ledgers yet."))
+                .child(Button::new("Ledgers", move |s| open_ledgers(s, &dc_open)))
+        ).title("Current Ledger");
+    };
+
+    let unpaid: Vec<BigDecimal> = ledger_repos
+        .ledger
+        .find_ledger_bills_with_bill_names(ledger.id)
+        .into_iter()
+        .filter(|(lb, _)| !lb.is_payed)
+        .map(|(lb, _)| lb.amount)
+        .collect();
+    let unpaid_total: BigDecimal = unpaid.iter().sum();
+    let net = ledger.net.clone().unwrap_or_else(|| &ledger.bank_balance + &ledger.income - &ledger.expenses);
+
+    let text = format!(
+        "{} ({}){}\nNet: ${}\nUnpaid bills: {} (${})",
+        ledger.name.clone().unwrap_or_else(|| "Unnamed".to_string()),
+        ledger.date.format("%d/%m/%Y"),
+        if ledger.is_closed { " - closed" } else { "" },
+        net,
+        unpaid.len(),
+        unpaid_total
+    );
+
+    let ledger_id = ledger.id;
+    Panel::new(
+        LinearLayout::vertical()
+            .child(TextView::new(text))
+            .child(Button::new("Open Ledger", move |s| {
+                crate::ledger_detail::show_ledger_detail(s, ledger_id, &ledger_repos);
+            }))
+    ).title("Current Ledger")
+}
+
+fn due_soon_panel(dc: &Rc<DependencyContainer>) -> impl View {
+    let ledger_repos = dc.ledger_repos();
+    let today = Local::now().date_naive();
+    let through = today + Duration::days(DUE_SOON_DAYS);
+
+    let mut due: Vec<(chrono::NaiveDate, String, BigDecimal, i32)> = ledger_repos
+        .ledger
+        .find_unpaid_bills_in_open_ledgers()
+        .into_iter()
+        .filter_map(|(lb, bill)| {
+            lb.due_day
+                .filter(|d| *d >= today && *d <= through)
+                .map(|d| (d, bill.name, lb.amount, lb.ledger_id))
+        })
+        .collect();
+    due.sort_by_key(|d| d.0);
+
+    let text = if due.is_empty() {
+        format!("Nothing due in the next {} days.", DUE_SOON_DAYS)
+    } else {
+        let mut lines: Vec<String> = due
+            .iter()
+            .take(MAX_LISTED)
+            .map(|(date, name, amount, _)| format!("{}  {:<18} ${}", date.format("%d/%m"), name, amount))
+            .collect();
+        if due.len() > MAX_LISTED {
+            lines.push(format!("...and {} more", due.len() - MAX_LISTED));
+        }
+        lines.join("\n")
+    };
+
+    // Jump to the ledger holding the next bill due
+    let first_ledger_id = due.first().map(|(_, _, _, lid)| *lid);
+    let dc_open = Rc::clone(dc);
+    Panel::new(
+        LinearLayout::vertical()
+            .child(TextView::new(text))
+            .child(Button::new("Open", move |s| match first_ledger_id {
+                Some(lid) => crate::ledger_detail::show_ledger_detail(s, lid, &ledger_repos),
+                None => open_ledgers(s, &dc_open),
+            }))
+    ).title(format!("Due in the Next {} Days", DUE_SOON_DAYS))
+}
+
+fn income_panel(dc: &Rc<DependencyContainer>) -> impl View {
+    let today = Local::now().date_naive();
+    let unassigned: Vec<crate::models::Income> = dc.income_repo()
+        .find_all()
+        .into_iter()
+        .filter(|i| i.ledger_id.is_none())
+        .filter(|i| i.date.year() == today.year() && i.date.month() == today.month())
+        .collect();
+    let total: BigDecimal = unassigned.iter().map(|i| i.amount.clone()).sum();
+
+    let text = if unassigned.is_empty() {
+        "Every income this month is in a ledger.".to_string()
+    } else {
+        let expected = unassigned.iter().filter(|i| i.is_expected).count();
+        format!(
+            "{} not in a ledger yet (${})\n{} of them expected",
+            unassigned.len(),
+            total,
+            expected
+        )
+    };
+
+    let income_repos = dc.income_repos();
+    Panel::new(
+        LinearLayout::vertical()
+            .child(TextView::new(text))
+            .child(Button::new("Open Income", move |s| {
+                crate::income_table::IncomeTableView::new(Rc::clone(&income_repos)).add_table(s);
+            }))
+    ).title("Unassigned Income This Month")
+}
+
+fn pto_panel(dc: &Rc<DependencyContainer>) -> impl View {
+    let today = Local::now().date_naive();
+    let pto_repo = dc.pto_repo();
+    let plan_repo = dc.pto_plan_repo();
+    let ptos = pto_repo.find_all();
+    let current = ptos.iter().find(|p| p.year == today.year());
+
+    // The soonest plan that hasn't started yet, in any year
+    let next_plan: Option<PtoPlan> = ptos
+        .iter()
+        .flat_map(|p| plan_repo.find_by_pto_id(p.id))
+        .filter(|plan| plan.start_date >= today && PtoStatus::from(plan.status.clone()) != PtoStatus::Completed)
+        .min_by_key(|plan| plan.start_date);
+
+    let mut text = match current {
+        Some(pto) => format!("{} hours remaining of {}", pto.hours_remaining, pto.available_hours),
+        None => format!("No PTO record for {}.", today.year()),
+    };
+    match next_plan {
+        Some(plan) => text.push_str(&format!(
+            "\nNext: {} {} - {} ({} hrs, {})",
+            plan.name,
+            plan.start_date.format("%d/%m"),
+            plan.end_date.format("%d/%m/%Y"),
+            plan.hours,
+            PtoStatus::from(plan.status)
+        )),
+        None => text.push_str("\nNo upcoming time off planned."),
+    }
+
+    let current_id = current.map(|p| p.id);
+    let holiday_repo = dc.holiday_hours_repo();
+    Panel::new(
+        LinearLayout::vertical()
+            .child(TextView::new(text))
+            .child(Button::new("Open PTO", move |s| {
+                s.pop_layer();
+                match current_id {
+                    Some(pto_id) => crate::pto_detail::show_pto_detail(s, pto_id, &pto_repo, &plan_repo, &holiday_repo),
+                    None => crate::pto_table::show_pto_table_view(s, &pto_repo, &plan_repo, &holiday_repo),
+                }
+            }))
+    ).title(format!("PTO {}", today.year()))
+}
+
+fn open_ledgers(siv: &mut Cursive, dc: &DependencyContainer) {
+    crate::ledger_table::LedgerTableView::new(dc.ledger_repos()).add_table(siv);
+}
@@ -0,0 +1,332 @@
+use bigdecimal::{BigDecimal, RoundingMode};
+use chrono::{Datelike, Months, NaiveDate};
+
+use crate::models::Loan;
+
+/// Give up on a plan that hasn't cleared the debts in 50 years
+const MAX_MONTHS: u32 = 600;
+
+/// Which debt gets the extra money first
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PayoffStrategy {
+    /// Smallest balance first
+    Snowball,
+    /// Highest APR first
+    Avalanche,
+}
+
+/// A debt as far as the planner is concerned (APR in percent)
+#[derive(Debug, Clone)]
+pub struct DebtInput {
+    pub id: i32,
+    pub balance: BigDecimal,
+    pub apr: BigDecimal,
+    pub minimum_payment: BigDecimal,
+}
+
+/// When one debt is cleared and what it cost; `months` is None if it never is
+#[derive(Debug, Clone, PartialEq)]
+pub struct DebtPayoff {
+    pub id: i32,
+    pub months: Option<u32>,
+    pub interest: BigDecimal,
+}
+
+/// Outcome of a simulated strategy
+#[derive(Debug, Clone, PartialEq)]
+pub struct PayoffPlan {
+    pub debts: Vec<DebtPayoff>,
+    pub months: Option<u32>,
+    pub total_interest: BigDecimal,
+}
+
+/// One month of interest on a balance at an APR (percent), to the cent
+pub fn calculate_monthly_interest(balance: &BigDecimal, apr: &BigDecimal) -> BigDecimal {
+    (balance * apr / BigDecimal::from(1200)).with_scale_round(2, RoundingMode::HalfEven)
+}
+
+/// Simulate paying debts month by month with minimums plus an extra amount
+/// Minimums freed by cleared debts roll into the extra payment
+pub fn simulate_payoff(debts: &[DebtInput], strategy: PayoffStrategy, extra_monthly: &BigDecimal) -> PayoffPlan {
+    let zero = BigDecimal::from(0);
+    let mut balances: Vec<BigDecimal> = debts.iter().map(|d| d.balance.clone()).collect();
+    let mut interest: Vec<BigDecimal> = vec![zero.clone(); debts.len()];
+    let mut payoff_months: Vec<Option<u32>> = balances
+        .iter()
+        .map(|b| if *b <= zero { Some(0) } else { None })
+        .collect();
+
+    let monthly_budget: BigDecimal = debts.iter().map(|d| d.minimum_payment.clone()).sum::<BigDecimal>() + extra_monthly;
+
+    let mut order: Vec<usize> = (0..debts.len()).collect();
+    match strategy {
+        PayoffStrategy::Snowball => order.sort_by(|&a, &b| {
+            debts[a].balance.cmp(&debts[b].balance).then(debts[b].apr.cmp(&debts[a].apr))
+        }),
+        PayoffStrategy::Avalanche => order.sort_by(|&a, &b| {
+            debts[b].apr.cmp(&debts[a].apr).then(debts[a].balance.cmp(&debts[b].balance))
+        }),
+    }
+
+    let mut month = 0;
+    while month < MAX_MONTHS && balances.iter().any(|b| *b > zero) {
+        month += 1;
+
+        for (i, debt) in debts.iter().enumerate() {
+            if balances[i] > zero {
+                let accrued = calculate_monthly_interest(&balances[i], &debt.apr);
+                balances[i] += accrued.clone();
+                interest[i] += accrued;
+            }
+        }
+
+        let mut available = monthly_budget.clone();
+
+        for (i, debt) in debts.iter().enumerate() {
+            if balances[i] > zero {
+                let payment = debt.minimum_payment.clone().min(balances[i].clone());
+                balances[i] -= payment.clone();
+                available -= payment;
+            }
+        }
+
+        for &i in &order {
+            if available <= zero {
+                break;
+            }
+            if balances[i] > zero {
+                let payment = available.clone().min(balances[i].clone());
+                balances[i] -= payment.clone();
+                available -= payment;
+            }
+        }
+
+        for (i, balance) in balances.iter().enumerate() {
+            if *balance <= zero && payoff_months[i].is_none() {
+                payoff_months[i] = Some(month);
+            }
+        }
+    }
+
+    let debt_payoffs: Vec<DebtPayoff> = debts
+        .iter()
+        .enumerate()
+        .map(|(i, debt)| DebtPayoff {
+            id: debt.id,
+            months: payoff_months[i],
+            interest: interest[i].clone(),
+        })
+        .collect();
+
+    let months = if payoff_months.iter().all(|m| m.is_some()) {
+        Some(payoff_months.iter().flatten().copied().max().unwrap_or(0))
+    } else {
+        None
+    };
+
+    PayoffPlan {
+        total_interest: interest.iter().sum(),
+        debts: debt_payoffs,
+        months,
+    }
+}
+
+/// Date a payoff lands on, counting months from a start date
+pub fn payoff_date(start: NaiveDate, months: u32) -> NaiveDate {
+    start.checked_add_months(Months::new(months)).unwrap_or(start)
+}
+
+/// One installment of an amortizing loan
+#[derive(Debug, Clone, PartialEq)]
+pub struct AmortizationRow {
+    pub number: u32,
+    pub date: NaiveDate,
+    pub payment: BigDecimal,
+    pub interest: BigDecimal,
+    pub principal: BigDecimal,
+    pub balance: BigDecimal,
+}
+
+/// Level monthly payment that clears a loan over its term (APR in percent), to the cent
+pub fn calculate_loan_payment(principal: &BigDecimal, annual_rate: &BigDecimal, term_months: u32) -> BigDecimal {
+    let zero = BigDecimal::from(0);
+    if term_months == 0 {
+        return principal.clone();
+    }
+    if *annual_rate <= zero {
+        return (principal / BigDecimal::from(term_months)).with_scale_round(2, RoundingMode::Up);
+    }
+
+    let monthly_rate = annual_rate / BigDecimal::from(1200);
+    let one_plus_rate = BigDecimal::from(1) + &monthly_rate;
+    let mut growth = BigDecimal::from(1);
+    for _ in 0..term_months {
+        growth = (growth * &one_plus_rate).with_scale_round(20, RoundingMode::HalfEven);
+    }
+
+    (principal * monthly_rate * &growth / (growth - BigDecimal::from(1))).with_scale_round(2, RoundingMode::HalfEven)
+}
+
+/// Full payment schedule, one row a month from the first payment date
+/// The last payment absorbs rounding so the balance ends at exactly zero
+pub fn amortization_schedule(principal: &BigDecimal, annual_rate: &BigDecimal, term_months: u32, first_payment: NaiveDate) -> Vec<AmortizationRow> {
+    let zero = BigDecimal::from(0);
+    let level_payment = calculate_loan_payment(principal, annual_rate, term_months);
+    let mut balance = principal.clone();
+    let mut schedule = Vec::new();
+
+    for number in 1..=term_months {
+        if balance <= zero {
+            break;
+        }
+
+        let interest = calculate_monthly_interest(&balance, annual_rate);
+        let mut principal_paid = &level_payment - &interest;
+        if number == term_months || principal_paid >= balance {
+            principal_paid = balance.clone();
+        }
+        balance -= &principal_paid;
+
+        schedule.push(AmortizationRow {
+            number,
+            date: payoff_date(first_payment, number - 1),
+            payment: &principal_paid + &interest,
+            interest,
+            principal: principal_paid,
+            balance: balance.clone(),
+        });
+    }
+
+    schedule
+}
+
+/// Amortization schedule for a bill's loan terms
+pub fn loan_schedule(loan: &Loan) -> Vec<AmortizationRow> {
+    amortization_schedule(&loan.principal, &loan.annual_rate, loan.term_months.max(0) as u32, loan.start_date)
+}
+
+/// The installment due in the same month as a date, if any
+pub fn payment_in_month(schedule: &[AmortizationRow], date: NaiveDate) -> Option<&AmortizationRow> {
+    schedule.iter().find(|r| r.date.year() == date.year() && r.date.month() == date.month())
+}
+
+/// Balance left after every installment due on or before a date
+pub fn remaining_balance(principal: &BigDecimal, schedule: &[AmortizationRow], as_of: NaiveDate) -> BigDecimal {
+    schedule
+        .iter()
+        .rev()
+        .find(|r| r.date <= as_of)
+        .map_or_else(|| principal.clone(), |r| r.balance.clone())
+}
+
+/// True once a date's month is past the final installment
+pub fn is_loan_finished(schedule: &[AmortizationRow], date: NaiveDate) -> bool {
+    schedule
+        .last()
+        .is_some_and(|last| (last.date.year(), last.date.month()) < (date.year(), date.month()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn debt(id: i32, balance: i32, apr: i32, minimum_payment: i32) -> DebtInput {
+        DebtInput {
+            id,
+            balance: BigDecimal::from(balance),
+            apr: BigDecimal::from(apr),
+            minimum_payment: BigDecimal::from(minimum_payment),
+        }
+    }
+
+    #[test]
+    fn test_interest_free_debt_pays_off_on_schedule() {
+        let plan = simulate_payoff(&[debt(1, 1000, 0, 100)], PayoffStrategy::Snowball, &BigDecimal::from(0));
+
+        assert_eq!(plan.months, Some(10));
+        assert_eq!(plan.total_interest, BigDecimal::from(0));
+    }
+
+    #[test]
+    fn test_extra_payment_shortens_payoff() {
+        let plan = simulate_payoff(&[debt(1, 1000, 0, 100)], PayoffStrategy::Snowball, &BigDecimal::from(150));
+
+        assert_eq!(plan.months, Some(4));
+    }
+
+    #[test]
+    fn test_avalanche_saves_interest_snowball_clears_small_debt_first() {
+        let debts = vec![debt(1, 500, 5, 25), debt(2, 2000, 20, 50)];
+        let extra = BigDecimal::from(100);
+
+        let snowball = simulate_payoff(&debts, PayoffStrategy::Snowball, &extra);
+        let avalanche = simulate_payoff(&debts, PayoffStrategy::Avalanche, &extra);
+
+        assert!(avalanche.total_interest < snowball.total_interest);
+        assert!(snowball.debts[0].months < avalanche.debts[0].months);
+    }
+
+    #[test]
+    fn test_minimum_below_interest_never_pays_off() {
+        // 2% a month on 10,000 is 200, more than the 100 minimum
+        let plan = simulate_payoff(&[debt(1, 10000, 24, 100)], PayoffStrategy::Avalanche, &BigDecimal::from(0));
+
+        assert_eq!(plan.months, None);
+        assert_eq!(plan.debts[0].months, None);
+    }
+
+    #[test]
+    fn test_monthly_interest() {
+        let result = calculate_monthly_interest(&BigDecimal::from(1200), &BigDecimal::from(18));
+        assert_eq!(result, "18.00".parse::<BigDecimal>().unwrap());
+    }
+
+    #[test]
+    fn test_loan_payment() {
+        let payment = calculate_loan_payment(&BigDecimal::from(10000), &BigDecimal::from(6), 12);
+        assert_eq!(payment, "860.66".parse::<BigDecimal>().unwrap());
+    }
+
+    #[test]
+    fn test_interest_free_loan_payment() {
+        let payment = calculate_loan_payment(&BigDecimal::from(1000), &BigDecimal::from(0), 3);
+        assert_eq!(payment, "333.34".parse::<BigDecimal>().unwrap());
+    }
+
+    #[test]
+    fn test_amortization_schedule_clears_balance() {
+        let principal = BigDecimal::from(10000);
+        let first_payment = NaiveDate::from_ymd_opt(2026, 1, 15).unwrap();
+        let schedule = amortization_schedule(&principal, &BigDecimal::from(6), 12, first_payment);
+
+        assert_eq!(schedule.len(), 12);
+        assert_eq!(schedule[0].interest, "50.00".parse::<BigDecimal>().unwrap());
+        assert_eq!(schedule[0].principal, "810.66".parse::<BigDecimal>().unwrap());
+        assert_eq!(schedule[11].date, NaiveDate::from_ymd_opt(2026, 12, 15).unwrap());
+        assert_eq!(schedule[11].balance, BigDecimal::from(0));
+
+        let principal_paid: BigDecimal = schedule.iter().map(|r| r.principal.clone()).sum();
+        assert_eq!(principal_paid, principal);
+    }
+
+    #[test]
+    fn test_remaining_balance_as_of_date() {
+        let principal = BigDecimal::from(1200);
+        let first_payment = NaiveDate::from_ymd_opt(2026, 1, 10).unwrap();
+        let schedule = amortization_schedule(&principal, &BigDecimal::from(0), 12, first_payment);
+
+        assert_eq!(remaining_balance(&principal, &schedule, NaiveDate::from_ymd_opt(2025, 12, 31).unwrap()), principal);
+        assert_eq!(remaining_balance(&principal, &schedule, NaiveDate::from_ymd_opt(2026, 3, 9).unwrap()), BigDecimal::from(1000));
+        assert_eq!(remaining_balance(&principal, &schedule, NaiveDate::from_ymd_opt(2026, 3, 10).unwrap()), BigDecimal::from(900));
+    }
+
+    #[test]
+    fn test_loan_finished_after_final_month() {
+        let first_payment = NaiveDate::from_ymd_opt(2026, 1, 10).unwrap();
+        let schedule = amortization_schedule(&BigDecimal::from(1200), &BigDecimal::from(0), 12, first_payment);
+
+        assert!(!is_loan_finished(&schedule, NaiveDate::from_ymd_opt(2026, 12, 1).unwrap()));
+        assert!(is_loan_finished(&schedule, NaiveDate::from_ymd_opt(2027, 1, 1).unwrap()));
+        assert!(payment_in_month(&schedule, NaiveDate::from_ymd_opt(2026, 12, 1).unwrap()).is_some());
+    }
+}
@@ -0,0 +1,349 @@
+use std::cmp::Ordering;
+use std::rc::Rc;
+use std::str::FromStr;
+use bigdecimal::BigDecimal;
+use chrono::Local;
+use cursive::Cursive;
+use cursive::traits::*;
+use cursive::views::{Button, Dialog, EditView, HideableView, LinearLayout, ListView, Panel, SelectView, TextArea, TextView};
+use cursive_table_view::{TableView, TableViewItem};
+
+use crate::debt_logic::{self, DebtInput, PayoffPlan, PayoffStrategy};
+use crate::models;
+use crate::repositories::{BillRepo, DebtRepo};
+use crate::ui_helpers::toggle_buttons_visible;
+
+const DEBT_EDIT_BUTTON: &str = "debt_edit_button";
+const DEBT_PAYMENTS_BUTTON: &str = "debt_payments_button";
+const DEBT_PLAN_BUTTON: &str = "debt_plan_button";
+const DEBT_DELETE_BUTTON: &str = "debt_delete_button";
+const TOGGLE_BUTTONS: &[&str] = &[DEBT_EDIT_BUTTON, DEBT_PAYMENTS_BUTTON, DEBT_PLAN_BUTTON, DEBT_DELETE_BUTTON];
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+enum DebtColumn {
+    Name,
+    Balance,
+    Apr,
+    Minimum,
+    Bill,
+}
+
+#[derive(Clone, Debug)]
+struct DebtDisplay {
+    id: i32,
+    name: String,
+    balance: BigDecimal,
+    apr: BigDecimal,
+    minimum_payment: BigDecimal,
+    bill_id: Option<i32>,
+    bill_name: Option<String>,
+    notes: Option<String>,
+}
+
+impl TableViewItem<DebtColumn> for DebtDisplay {
+    fn to_column(&self, column: DebtColumn) -> String {
+        match column {
+            DebtColumn::Name => self.name.clone(),
+            DebtColumn::Balance => format!("${}", self.balance),
+            DebtColumn::Apr => format!("{}%", self.apr),
+            DebtColumn::Minimum => format!("${}", self.minimum_payment),
+            DebtColumn::Bill => self.bill_name.clone().unwrap_or_else(|| "-".to_string()),
+        }
+    }
+
+    fn cmp(&self, other: &Self, column: DebtColumn) -> Ordering where Self: Sized {
+        match column {
+            DebtColumn::Name => self.name.cmp(&other.name),
+            DebtColumn::Balance => self.balance.cmp(&other.balance),
+            DebtColumn::Apr => self.apr.cmp(&other.apr),
+            DebtColumn::Minimum => self.minimum_payment.cmp(&other.minimum_payment),
+            DebtColumn::Bill => self.bill_name.cmp(&other.bill_name),
+        }
+    }
+}
+
+fn load_debt_displays(debt_repo: &DebtRepo, bill_repo: &BillRepo) -> Vec<DebtDisplay> {
+    let bills = bill_repo.find_all();
+
+    debt_repo.find_all()
+        .into_iter()
+        .map(|d: models::Debt| DebtDisplay {
+            id: d.id,
+            bill_name: d.bill_id
+                .and_then(|bid| bills.iter().find(|b| b.id == bid))
+                .map(|b| b.name.clone()),
+            name: d.name,
+            balance: d.balance,
+            apr: d.apr,
+            minimum_payment: d.minimum_payment,
+            bill_id: d.bill_id,
+            notes: d.notes,
+        })
+        .collect()
+}
+
+pub fn show_debt_table(siv: &mut Cursive, debt_repo: &Rc<DebtRepo>, bill_repo: &Rc<BillRepo>) {
+    siv.pop_layer();
+
+    let debt_displays = load_debt_displays(debt_repo, bill_repo);
+    let debt_count = debt_displays.len();
+    let total_balance: BigDecimal = debt_displays.iter().map(|d| d.balance.clone()).sum();
+
+    let table = TableView::<DebtDisplay, DebtColumn>::new()
+        .column(DebtColumn::Name, "Debt", |c| c)
+        .column(DebtColumn::Balance, "Balance", |c| c.width(14))
+        .column(DebtColumn::Apr, "APR", |c| c.width(10))
+        .column(DebtColumn::Minimum, "Minimum", |c| c.width(12))
+        .column(DebtColumn::Bill, "Bill", |c| c.width(18))
+        .items(debt_displays);
+
+    let repo_add = Rc::clone(debt_repo);
+    let bill_repo_add = Rc::clone(bill_repo);
+    let repo_edit = Rc::clone(debt_repo);
+    let bill_repo_edit = Rc::clone(bill_repo);
+    let repo_payments = Rc::clone(debt_repo);
+    let repo_plan = Rc::clone(debt_repo);
+    let repo_delete = Rc::clone(debt_repo);
+    let bill_repo_delete = Rc::clone(bill_repo);
+
+    let buttons = LinearLayout::horizontal()
+        .child(Button::new("Add", move |s| debt_form(s, None, &repo_add, &bill_repo_add)))
+        .child(HideableView::new(Button::new("Edit", move |s| {
+            if let Some(debt) = selected_debt(s) {
+                debt_form(s, Some(debt), &repo_edit, &bill_repo_edit);
+            }
+        })).with_name(DEBT_EDIT_BUTTON))
+        .child(HideableView::new(Button::new("Payments", move |s| {
+            if let Some(debt) = selected_debt(s) {
+                show_debt_payments(s, &debt, &repo_payments);
+            }
+        })).with_name(DEBT_PAYMENTS_BUTTON))
+        .child(HideableView::new(Button::new("Plan", move |s| plan_payoff(s, &repo_plan))).with_name(DEBT_PLAN_BUTTON))
+        .child(HideableView::new(Button::new("Delete", move |s| delete_debt(s, &repo_delete, &bill_repo_delete))).with_name(DEBT_DELETE_BUTTON));
+
+    let content = LinearLayout::vertical()
+        .child(Panel::new(table.with_name("debt_table").min_size((60, 15))).full_screen())
+        .child(TextView::new(format!("Total Owed: ${}", total_balance)))
+        .child(buttons);
+
+    let screen = crate::common_layout::create_screen(
+        "Debts",
+        content,
+        &crate::common_layout::view_footer()
+    );
+
+    siv.add_layer(screen);
+
+    toggle_buttons_visible(siv, debt_count, TOGGLE_BUTTONS);
+}
+
+fn selected_debt(siv: &mut Cursive) -> Option<DebtDisplay> {
+    siv.call_on_name("debt_table", |v: &mut TableView<DebtDisplay, DebtColumn>| {
+        v.item().and_then(|i| v.borrow_item(i).cloned())
+    }).flatten()
+}
+
+fn debt_form(siv: &mut Cursive, existing: Option<DebtDisplay>, debt_repo: &Rc<DebtRepo>, bill_repo: &Rc<BillRepo>) {
+    let title = if existing.is_some() { "Edit Debt" } else { "Add Debt" };
+
+    let name_value = existing.as_ref().map(|d| d.name.clone()).unwrap_or_default();
+    let balance_value = existing.as_ref().map(|d| d.balance.to_string()).unwrap_or_default();
+    let apr_value = existing.as_ref().map(|d| d.apr.to_string()).unwrap_or_default();
+    let minimum_value = existing.as_ref().map(|d| d.minimum_payment.to_string()).unwrap_or_default();
+    let notes_value = existing.as_ref().and_then(|d| d.notes.clone()).unwrap_or_default();
+    let bill_value = existing.as_ref().and_then(|d| d.bill_id);
+    let debt_id = existing.map(|d| d.id);
+
+    // Paying this bill in a ledger pays down the debt
+    let bills = bill_repo.find_all();
+    let mut bill_select = SelectView::new().popup();
+    bill_select.add_item("(None)", None);
+    for bill in &bills {
+        bill_select.add_item(bill.name.clone(), Some(bill.id));
+    }
+    let bill_index = bill_value
+        .and_then(|bid| bills.iter().position(|b| b.id == bid))
+        .map_or(0, |i| i + 1);
+    let bill_select = bill_select.selected(bill_index);
+
+    let repo = Rc::clone(debt_repo);
+    let repo_bill = Rc::clone(bill_repo);
+    siv.add_layer(
+        Dialog::new()
+            .title(title)
+            .button("Ok", move |s| {
+                let name_str = s.call_on_name("debt_name_input", |v: &mut EditView| v.get_content()).unwrap();
+                let balance_str = s.call_on_name("debt_balance_input", |v: &mut EditView| v.get_content()).unwrap();
+                let apr_str = s.call_on_name("debt_apr_input", |v: &mut EditView| v.get_content()).unwrap();
+                let minimum_str = s.call_on_name("debt_minimum_input", |v: &mut EditView| v.get_content()).unwrap();
+                let notes_str = s.call_on_name("debt_notes_input", |v: &mut TextArea| v.get_content().to_string()).unwrap();
+                let bill_id = s.call_on_name("debt_bill_select", |v: &mut SelectView<Option<i32>>| {
+                    v.selection().and_then(|b| *b)
+                }).flatten();
+
+                if name_str.trim().is_empty() {
+                    s.add_layer(Dialog::info("Name cannot be empty"));
+                    return;
+                }
+
+                let (balance, apr, minimum_payment) = match (
+                    BigDecimal::from_str(balance_str.trim()),
+                    BigDecimal::from_str(apr_str.trim()),
+                    BigDecimal::from_str(minimum_str.trim()),
+                ) {
+                    (Ok(b), Ok(a), Ok(m)) => (b, a, m),
+                    _ => {
+                        s.add_layer(Dialog::info("Balance, APR and minimum payment must be numbers"));
+                        return;
+                    }
+                };
+
+                let debt = models::NewDebt {
+                    name: name_str.trim().to_string(),
+                    balance,
+                    apr,
+                    minimum_payment,
+                    bill_id,
+                    notes: if notes_str.is_empty() { None } else { Some(notes_str) },
+                };
+                if let Some(record_id) = debt_id {
+                    repo.update(record_id, debt);
+                } else {
+                    repo.create(debt);
+                }
+
+                s.pop_layer();
+                show_debt_table(s, &repo, &repo_bill);
+            })
+            .button("Cancel", |s| { s.pop_layer(); })
+            .content(
+                ListView::new()
+                    .child("Name", EditView::new().content(name_value).with_name("debt_name_input").fixed_width(20))
+                    .child("Balance", EditView::new().content(balance_value).with_name("debt_balance_input").fixed_width(20))
+                    .child("APR (%)", EditView::new().content(apr_value).with_name("debt_apr_input").fixed_width(20))
+                    .child("Minimum Payment", EditView::new().content(minimum_value).with_name("debt_minimum_input").fixed_width(20))
+                    .child("Bill", bill_select.with_name("debt_bill_select"))
+                    .child("Notes", TextArea::new().content(notes_value).with_name("debt_notes_input").min_size((40, 3)))
+            )
+    );
+}
+
+fn show_debt_payments(siv: &mut Cursive, debt: &DebtDisplay, debt_repo: &Rc<DebtRepo>) {
+    let payments = debt_repo.find_payments_by_debt(debt.id);
+
+    if payments.is_empty() {
+        siv.add_layer(Dialog::info("No payments yet. Payments are applied when the linked bill is marked paid in a ledger."));
+        return;
+    }
+
+    let mut content = format!("{:<24}{:>12}{:>12}{:>12}\n", "LEDGER", "PAID", "INTEREST", "PRINCIPAL");
+    content.push_str("────────────────────────────────────────────────────────────\n");
+    for (payment, ledger_date, ledger_name) in payments {
+        let ledger_str = format!("{} {}", ledger_date.format("%d/%m/%Y"), ledger_name.unwrap_or_default());
+        let amount_str = format!("${}", payment.amount);
+        let interest_str = format!("${}", payment.interest);
+        let principal_str = format!("${}", payment.principal);
+        content.push_str(&format!("{:<24}{:>12}{:>12}{:>12}\n", ledger_str, amount_str, interest_str, principal_str));
+    }
+
+    siv.add_layer(
+        Dialog::around(TextView::new(content).scrollable())
+            .title(format!("{} Payments", debt.name))
+            .button("Close", |s| { s.pop_layer(); })
+    );
+}
+
+fn plan_payoff(siv: &mut Cursive, debt_repo: &Rc<DebtRepo>) {
+    let repo = Rc::clone(debt_repo);
+    siv.add_layer(
+        Dialog::around(
+            ListView::new()
+                .child("Extra per Month", EditView::new().content("0").with_name("debt_extra_input").fixed_width(20))
+        )
+        .title("Payoff Planner")
+        .button("Simulate", move |s| {
+            let extra_str = s.call_on_name("debt_extra_input", |v: &mut EditView| v.get_content()).unwrap();
+
+            let extra = match BigDecimal::from_str(extra_str.trim()) {
+                Ok(e) => e,
+                Err(_) => {
+                    s.add_layer(Dialog::info("Invalid amount format"));
+                    return;
+                }
+            };
+
+            let debts = repo.find_all();
+            s.pop_layer();
+            show_payoff_comparison(s, &debts, &extra);
+        })
+        .button("Cancel", |s| { s.pop_layer(); })
+    );
+}
+
+fn show_payoff_comparison(siv: &mut Cursive, debts: &[models::Debt], extra: &BigDecimal) {
+    let inputs: Vec<DebtInput> = debts
+        .iter()
+        .map(|d| DebtInput {
+            id: d.id,
+            balance: d.balance.clone(),
+            apr: d.apr.clone(),
+            minimum_payment: d.minimum_payment.clone(),
+        })
+        .collect();
+
+    let snowball = debt_logic::simulate_payoff(&inputs, PayoffStrategy::Snowball, extra);
+    let avalanche = debt_logic::simulate_payoff(&inputs, PayoffStrategy::Avalanche, extra);
+
+    let today = Local::now().date_naive();
+    let when = |months: Option<u32>| {
+        months.map_or("Never".to_string(), |m| debt_logic::payoff_date(today, m).format("%m/%Y").to_string())
+    };
+    let months_for = |plan: &PayoffPlan, debt_id: i32| {
+        plan.debts.iter().find(|p| p.id == debt_id).and_then(|p| p.months)
+    };
+
+    let mut content = format!("Extra per month: ${}\n\n", extra);
+    content.push_str(&format!("{:<20}{:>14}{:>14}\n", "", "SNOWBALL", "AVALANCHE"));
+    content.push_str("────────────────────────────────────────────────\n");
+    content.push_str(&format!("{:<20}{:>14}{:>14}\n", "Debt-free", when(snowball.months), when(avalanche.months)));
+    let snowball_interest = format!("${}", snowball.total_interest);
+    let avalanche_interest = format!("${}", avalanche.total_interest);
+    content.push_str(&format!("{:<20}{:>14}{:>14}\n\n", "Total interest", snowball_interest, avalanche_interest));
+
+    content.push_str("PAYOFF BY DEBT\n");
+    for debt in debts {
+        content.push_str(&format!(
+            "{:<20}{:>14}{:>14}\n",
+            debt.name,
+            when(months_for(&snowball, debt.id)),
+            when(months_for(&avalanche, debt.id))
+        ));
+    }
+
+    if snowball.months.is_none() || avalanche.months.is_none() {
+        content.push_str("\n\"Never\" means the payments don't cover the interest within 50 years.");
+    }
+
+    siv.add_layer(
+        Dialog::around(TextView::new(content).scrollable())
+            .title("Snowball vs. Avalanche")
+            .button("Close", |s| { s.pop_layer(); })
+    );
+}
+
+fn delete_debt(siv: &mut Cursive, debt_repo: &Rc<DebtRepo>, bill_repo: &Rc<BillRepo>) {
+    if let Some(debt) = selected_debt(siv) {
+        let repo = Rc::clone(debt_repo);
+        let repo_bill = Rc::clone(bill_repo);
+        siv.add_layer(
+            Dialog::text(format!("Delete debt '{}' and its payment history?", debt.name))
+                .button("Yes", move |s| {
+                    repo.delete(debt.id);
+
+                    s.pop_layer();
+                    show_debt_table(s, &repo, &repo_bill);
+                })
+                .button("No", |s| { s.pop_layer(); })
+        );
+    }
+}
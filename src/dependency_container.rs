@@ -4,15 +4,52 @@ use crate::configuration_manager::ConfigurationManager;
 use crate::db::PgConnector;
 use crate::repositories::*;
 
+/// The repos the ledger screens work with, shared so each screen can hand them on
+pub struct LedgerRepos {
+    pub ledger: Rc<LedgerRepo>,
+    pub account: Rc<AccountRepo>,
+    pub category: Rc<CategoryRepo>,
+    pub credit_card: Rc<CreditCardRepo>,
+    pub envelope: Rc<EnvelopeRepo>,
+    pub income_source: Rc<IncomeSourceRepo>,
+    pub loan: Rc<LoanRepo>,
+    pub savings_goal: Rc<SavingsGoalRepo>,
+    pub config: Rc<ConfigurationManager>,
+}
+
+pub struct IncomeRepos {
+    pub income: Rc<IncomeRepo>,
+    pub income_source: Rc<IncomeSourceRepo>,
+    pub account: Rc<AccountRepo>,
+}
+
+pub struct AccountRepos {
+    pub account: Rc<AccountRepo>,
+    pub credit_card: Rc<CreditCardRepo>,
+    pub bill: Rc<BillRepo>,
+}
+
 pub struct DependencyContainer{
-    configuration_manager: OnceCell<ConfigurationManager>,
+    configuration_manager: OnceCell<Rc<ConfigurationManager>>,
     pg_connector: OnceCell<Rc<PgConnector>>,
     income_repo: OnceCell<Rc<IncomeRepo>>,
+    income_source_repo: OnceCell<Rc<IncomeSourceRepo>>,
     bill_repo: OnceCell<Rc<BillRepo>>,
     ledger_repo: OnceCell<Rc<LedgerRepo>>,
     pto_repo: OnceCell<Rc<PtoRepo>>,
     pto_plan_repo: OnceCell<Rc<PtoPlanRepo>>,
     holiday_hours_repo: OnceCell<Rc<HolidayHoursRepo>>,
+    category_repo: OnceCell<Rc<CategoryRepo>>,
+    envelope_repo: OnceCell<Rc<EnvelopeRepo>>,
+    savings_goal_repo: OnceCell<Rc<SavingsGoalRepo>>,
+    debt_repo: OnceCell<Rc<DebtRepo>>,
+    loan_repo: OnceCell<Rc<LoanRepo>>,
+    account_repo: OnceCell<Rc<AccountRepo>>,
+    credit_card_repo: OnceCell<Rc<CreditCardRepo>>,
+    tax_repo: OnceCell<Rc<TaxRepo>>,
+    ledger_repos: OnceCell<Rc<LedgerRepos>>,
+    income_repos: OnceCell<Rc<IncomeRepos>>,
+    account_repos: OnceCell<Rc<AccountRepos>>,
 }
 
 impl DependencyContainer {
@@ -21,16 +58,28 @@ impl DependencyContainer {
             configuration_manager: OnceCell::new(),
             pg_connector: OnceCell::new(),
             income_repo: OnceCell::new(),
+            income_source_repo: OnceCell::new(),
             bill_repo: OnceCell::new(),
             ledger_repo: OnceCell::new(),
             pto_repo: OnceCell::new(),
             pto_plan_repo: OnceCell::new(),
             holiday_hours_repo: OnceCell::new(),
+            category_repo: OnceCell::new(),
+            envelope_repo: OnceCell::new(),
+            savings_goal_repo: OnceCell::new(),
+            debt_repo: OnceCell::new(),
+            loan_repo: OnceCell::new(),
+            account_repo: OnceCell::new(),
+            credit_card_repo: OnceCell::new(),
+            tax_repo: OnceCell::new(),
+            ledger_repos: OnceCell::new(),
+            income_repos: OnceCell::new(),
+            account_repos: OnceCell::new(),
         }
     }
 
-    pub fn configuration_manager(&self) -> &ConfigurationManager {
-        self.configuration_manager.get_or_init(|| ConfigurationManager::new())
+    pub fn configuration_manager(&self) -> Rc<ConfigurationManager> {
+        Rc::clone(self.configuration_manager.get_or_init(|| Rc::new(ConfigurationManager::new())))
     }
     
     pub fn pg_connector(&self) -> Rc<PgConnector> {
@@ -52,6 +101,12 @@ impl DependencyContainer {
         }))
     }
 
+    pub fn income_source_repo(&self) -> Rc<IncomeSourceRepo> {
+        Rc::clone(self.income_source_repo.get_or_init(|| {
+            Rc::new(IncomeSourceRepo::new(self.pg_connector()))
+        }))
+    }
+
     pub fn bill_repo(&self) -> Rc<BillRepo> {
         Rc::clone(self.bill_repo.get_or_init(|| {
             Rc::new(BillRepo::new(self.pg_connector()))
@@ -81,4 +136,88 @@ impl DependencyContainer {
             Rc::new(HolidayHoursRepo::new(self.pg_connector()))
         }))
     }
+
+    pub fn category_repo(&self) -> Rc<CategoryRepo> {
+        Rc::clone(self.category_repo.get_or_init(|| {
+            Rc::new(CategoryRepo::new(self.pg_connector()))
+        }))
+    }
+
+    pub fn envelope_repo(&self) -> Rc<EnvelopeRepo> {
+        Rc::clone(self.envelope_repo.get_or_init(|| {
+            Rc::new(EnvelopeRepo::new(self.pg_connector()))
+        }))
+    }
+
+    pub fn savings_goal_repo(&self) -> Rc<SavingsGoalRepo> {
+        Rc::clone(self.savings_goal_repo.get_or_init(|| {
+            Rc::new(SavingsGoalRepo::new(self.pg_connector()))
+        }))
+    }
+
+    pub fn debt_repo(&self) -> Rc<DebtRepo> {
+        Rc::clone(self.debt_repo.get_or_init(|| {
+            Rc::new(DebtRepo::new(self.pg_connector()))
+        }))
+    }
+
+    pub fn loan_repo(&self) -> Rc<LoanRepo> {
+        Rc::clone(self.loan_repo.get_or_init(|| {
+            Rc::new(LoanRepo::new(self.pg_connector()))
+        }))
+    }
+
+    pub fn account_repo(&self) -> Rc<AccountRepo> {
+        Rc::clone(self.account_repo.get_or_init(|| {
+            Rc::new(AccountRepo::new(self.pg_connector()))
+        }))
+    }
+
+    pub fn credit_card_repo(&self) -> Rc<CreditCardRepo> {
+        Rc::clone(self.credit_card_repo.get_or_init(|| {
+            Rc::new(CreditCardRepo::new(self.pg_connector()))
+        }))
+    }
+
+    pub fn tax_repo(&self) -> Rc<TaxRepo> {
+        Rc::clone(self.tax_repo.get_or_init(|| {
+            Rc::new(TaxRepo::new(self.pg_connector()))
+        }))
+    }
+
+    pub fn ledger_repos(&self) -> Rc<LedgerRepos> {
+        Rc::clone(self.ledger_repos.get_or_init(|| {
+            Rc::new(LedgerRepos {
+                ledger: self.ledger_repo(),
+                account: self.account_repo(),
+                category: self.category_repo(),
+                credit_card: self.credit_card_repo(),
+                envelope: self.envelope_repo(),
+                income_source: self.income_source_repo(),
+                loan: self.loan_repo(),
+                savings_goal: self.savings_goal_repo(),
+                config: self.configuration_manager(),
+            })
+        }))
+    }
+
+    pub fn income_repos(&self) -> Rc<IncomeRepos> {
+        Rc::clone(self.income_repos.get_or_init(|| {
+            Rc::new(IncomeRepos {
+                income: self.income_repo(),
+                income_source: self.income_source_repo(),
+                account: self.account_repo(),
+            })
+        }))
+    }
+
+    pub fn account_repos(&self) -> Rc<AccountRepos> {
+        Rc::clone(self.account_repos.get_or_init(|| {
+            Rc::new(AccountRepos {
+                account: self.account_repo(),
+                credit_card: self.credit_card_repo(),
+                bill: self.bill_repo(),
+            })
+        }))
+    }
 }
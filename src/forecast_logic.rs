@@ -0,0 +1,208 @@
+use bigdecimal::BigDecimal;
+use chrono::{Datelike, Months, NaiveDate};
+
+use crate::card_logic::day_in_month;
+use crate::income_logic;
+use crate::models::PaySchedule;
+
+/// A bill repeating every month until its last payment month, if it has one
+#[derive(Debug, Clone)]
+pub struct BillPlan {
+    pub amount: BigDecimal,
+    pub last_month: Option<NaiveDate>,
+}
+
+/// An income source paying its expected amount on its schedule
+#[derive(Debug, Clone)]
+pub struct IncomePlan {
+    pub schedule: PaySchedule,
+    pub anchor: NaiveDate,
+    pub second_day: Option<u32>,
+    pub amount: BigDecimal,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WhatIfKind {
+    Raise,
+    NewBill,
+    OneTimeExpense,
+}
+
+impl WhatIfKind {
+    pub fn all() -> Vec<WhatIfKind> {
+        vec![WhatIfKind::Raise, WhatIfKind::NewBill, WhatIfKind::OneTimeExpense]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            WhatIfKind::Raise => "Raise",
+            WhatIfKind::NewBill => "New Bill",
+            WhatIfKind::OneTimeExpense => "One-Time Expense",
+        }
+    }
+}
+
+/// A hypothetical change applied from a month onward (or only that month for one-time expenses)
+#[derive(Debug, Clone, PartialEq)]
+pub struct WhatIf {
+    pub kind: WhatIfKind,
+    pub amount: BigDecimal,
+    pub month: NaiveDate,
+}
+
+impl WhatIf {
+    /// Effect on a month's balance: raises add, bills and expenses subtract
+    pub fn effect_in(&self, month: NaiveDate) -> BigDecimal {
+        let applies = match self.kind {
+            WhatIfKind::Raise | WhatIfKind::NewBill => same_or_later_month(month, self.month),
+            WhatIfKind::OneTimeExpense => same_month(month, self.month),
+        };
+        if !applies {
+            return BigDecimal::from(0);
+        }
+
+        match self.kind {
+            WhatIfKind::Raise => self.amount.clone(),
+            WhatIfKind::NewBill | WhatIfKind::OneTimeExpense => -self.amount.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ForecastMonth {
+    pub month: NaiveDate,
+    pub income: BigDecimal,
+    pub bills: BigDecimal,
+    pub adjustments: BigDecimal,
+    pub balance: BigDecimal,
+}
+
+impl ForecastMonth {
+    pub fn is_negative(&self) -> bool {
+        let zero = BigDecimal::from(0);
+        self.balance < zero
+    }
+}
+
+fn same_month(a: NaiveDate, b: NaiveDate) -> bool {
+    (a.year(), a.month()) == (b.year(), b.month())
+}
+
+fn same_or_later_month(a: NaiveDate, b: NaiveDate) -> bool {
+    (a.year(), a.month()) >= (b.year(), b.month())
+}
+
+/// First day of each of the `count` months after the month of `after`
+pub fn forecast_months(after: NaiveDate, count: u32) -> Vec<NaiveDate> {
+    let start = after.with_day(1).unwrap_or(after);
+    (1..=count)
+        .filter_map(|i| start.checked_add_months(Months::new(i)))
+        .collect()
+}
+
+/// Expected pay landing in a month across every plan
+pub fn income_in_month(plans: &[IncomePlan], month: NaiveDate) -> BigDecimal {
+    let first = day_in_month(month.year(), month.month(), 1);
+    let last = day_in_month(month.year(), month.month(), 31);
+    plans
+        .iter()
+        .map(|plan| {
+            let paydays = income_logic::pay_dates(plan.schedule, plan.anchor, plan.second_day, first, last).len();
+            &plan.amount * BigDecimal::from(paydays as u32)
+        })
+        .sum()
+}
+
+/// Bills still running in a month
+pub fn bills_in_month(plans: &[BillPlan], month: NaiveDate) -> BigDecimal {
+    plans
+        .iter()
+        .filter(|plan| plan.last_month.is_none_or(|last| same_or_later_month(last, month)))
+        .map(|plan| plan.amount.clone())
+        .sum()
+}
+
+/// Month-end balances starting from an opening balance, carrying each month into the next
+pub fn project(opening: &BigDecimal, months: &[NaiveDate], bills: &[BillPlan], incomes: &[IncomePlan], what_ifs: &[WhatIf]) -> Vec<ForecastMonth> {
+    let mut balance = opening.clone();
+    months
+        .iter()
+        .map(|month| {
+            let income = income_in_month(incomes, *month);
+            let bill_total = bills_in_month(bills, *month);
+            let adjustments: BigDecimal = what_ifs.iter().map(|w| w.effect_in(*month)).sum();
+            balance = &balance + &income - &bill_total + &adjustments;
+            ForecastMonth {
+                month: *month,
+                income,
+                bills: bill_total,
+                adjustments,
+                balance: balance.clone(),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_forecast_months_start_after_the_ledger_month() {
+        let months = forecast_months(date(2026, 11, 20), 3);
+
+        assert_eq!(months, vec![date(2026, 12, 1), date(2027, 1, 1), date(2027, 2, 1)]);
+    }
+
+    #[test]
+    fn test_bi_weekly_income_counts_paydays_in_month() {
+        let plans = vec![IncomePlan {
+            schedule: PaySchedule::BiWeekly,
+            anchor: date(2026, 1, 2),
+            second_day: None,
+            amount: BigDecimal::from(1000),
+        }];
+
+        // January 2026 has paydays on the 2nd, 16th and 30th
+        assert_eq!(income_in_month(&plans, date(2026, 1, 1)), BigDecimal::from(3000));
+        assert_eq!(income_in_month(&plans, date(2026, 2, 1)), BigDecimal::from(2000));
+    }
+
+    #[test]
+    fn test_finished_bills_drop_off() {
+        let plans = vec![
+            BillPlan { amount: BigDecimal::from(100), last_month: None },
+            BillPlan { amount: BigDecimal::from(250), last_month: Some(date(2026, 3, 15)) },
+        ];
+
+        assert_eq!(bills_in_month(&plans, date(2026, 3, 1)), BigDecimal::from(350));
+        assert_eq!(bills_in_month(&plans, date(2026, 4, 1)), BigDecimal::from(100));
+    }
+
+    #[test]
+    fn test_project_carries_balance_and_applies_what_ifs() {
+        let months = forecast_months(date(2026, 1, 1), 3);
+        let bills = vec![BillPlan { amount: BigDecimal::from(1500), last_month: None }];
+        let incomes = vec![IncomePlan {
+            schedule: PaySchedule::Monthly,
+            anchor: date(2026, 1, 25),
+            second_day: None,
+            amount: BigDecimal::from(1400),
+        }];
+        let what_ifs = vec![
+            WhatIf { kind: WhatIfKind::OneTimeExpense, amount: BigDecimal::from(300), month: date(2026, 2, 1) },
+            WhatIf { kind: WhatIfKind::Raise, amount: BigDecimal::from(200), month: date(2026, 3, 1) },
+        ];
+
+        let forecast = project(&BigDecimal::from(250), &months, &bills, &incomes, &what_ifs);
+
+        let balances: Vec<BigDecimal> = forecast.iter().map(|m| m.balance.clone()).collect();
+        assert_eq!(balances, vec![BigDecimal::from(-150), BigDecimal::from(-50), BigDecimal::from(50)]);
+        assert!(forecast[0].is_negative());
+        assert_eq!(forecast[0].adjustments, BigDecimal::from(-300));
+    }
+}
@@ -0,0 +1,245 @@
+use std::rc::Rc;
+use std::str::FromStr;
+use bigdecimal::BigDecimal;
+use chrono::NaiveDate;
+use cursive::Cursive;
+use cursive::theme::{BaseColor, Color};
+use cursive::traits::*;
+use cursive::utils::markup::StyledString;
+use cursive::views::{Button, Dialog, EditView, LinearLayout, ListView, Panel, SelectView, TextView};
+
+use crate::configuration_manager::ConfigurationManager;
+use crate::forecast_logic::{self, BillPlan, IncomePlan, WhatIf, WhatIfKind};
+use crate::models::{Ledger, PaySchedule};
+use crate::repositories::{BillRepo, IncomeSourceRepo, LedgerRepo, LoanRepo};
+
+/// Months projected when the forecast first opens
+const DEFAULT_FORECAST_MONTHS: u32 = 12;
+
+/// Everything the projection starts from, loaded once per visit
+struct ForecastBase {
+    latest: Ledger,
+    opening: BigDecimal,
+    bills: Vec<BillPlan>,
+    incomes: Vec<IncomePlan>,
+    buffer: BigDecimal,
+}
+
+fn load_forecast_base(ledger_repo: &LedgerRepo, bill_repo: &BillRepo, loan_repo: &LoanRepo, income_source_repo: &IncomeSourceRepo, config: &ConfigurationManager) -> Option<ForecastBase> {
+    let latest = ledger_repo.find_all().into_iter().max_by_key(|l| l.date)?;
+    let opening = latest.net.clone().unwrap_or_else(|| &latest.bank_balance + &latest.income - &latest.expenses);
+
+    // Installment bills stop after their loan's final payment
+    let loans = loan_repo.find_all();
+    let bills = bill_repo
+        .find_all()
+        .into_iter()
+        .map(|bill| BillPlan {
+            last_month: loans
+                .iter()
+                .find(|l| l.bill_id == bill.id)
+                .and_then(|loan| crate::debt_logic::loan_schedule(loan).last().map(|row| row.date)),
+            amount: bill.amount,
+        })
+        .collect();
+
+    let incomes = income_source_repo
+        .find_all()
+        .into_iter()
+        .filter(|source| source.is_active)
+        .map(|source| IncomePlan {
+            schedule: PaySchedule::from(source.schedule),
+            anchor: source.anchor_date,
+            second_day: source.second_day.map(|d| d as u32),
+            amount: source.expected_amount,
+        })
+        .collect();
+
+    Some(ForecastBase {
+        latest,
+        opening,
+        bills,
+        incomes,
+        buffer: config.get_cash_flow_buffer(),
+    })
+}
+
+pub fn show_forecast(siv: &mut Cursive, ledger_repo: &Rc<LedgerRepo>, bill_repo: &Rc<BillRepo>, loan_repo: &Rc<LoanRepo>, income_source_repo: &Rc<IncomeSourceRepo>, config: &ConfigurationManager) {
+    let Some(base) = load_forecast_base(ledger_repo, bill_repo, loan_repo, income_source_repo, config) else {
+        siv.add_layer(Dialog::info("Create a ledger first; the forecast starts from the latest ledger's net"));
+        return;
+    };
+    let base = Rc::new(base);
+
+    siv.pop_layer();
+
+    let starting_point = format!(
+        "Starting from {} ({}) with a net of ${}. Bills repeat monthly; income comes from active income sources.",
+        base.latest.name.clone().unwrap_or_else(|| "the latest ledger".to_string()),
+        base.latest.date.format("%d/%m/%Y"),
+        base.opening
+    );
+
+    let base_recalculate = Rc::clone(&base);
+    let base_add = Rc::clone(&base);
+    let base_remove = Rc::clone(&base);
+
+    let months_row = LinearLayout::horizontal()
+        .child(TextView::new("Months: "))
+        .child(EditView::new().content(DEFAULT_FORECAST_MONTHS.to_string()).with_name("forecast_months").fixed_width(5))
+        .child(Button::new("Recalculate", move |s| refresh_forecast(s, &base_recalculate)));
+
+    let what_if_buttons = LinearLayout::horizontal()
+        .child(Button::new("Add What-If", move |s| what_if_form(s, &base_add)))
+        .child(Button::new("Remove", move |s| {
+            let selected = s.call_on_name("forecast_what_ifs", |v: &mut SelectView<WhatIf>| v.selected_id()).flatten();
+            if let Some(index) = selected {
+                s.call_on_name("forecast_what_ifs", |v: &mut SelectView<WhatIf>| {
+                    let _ = v.remove_item(index);
+                });
+                refresh_forecast(s, &base_remove);
+            }
+        }));
+
+    let content = LinearLayout::vertical()
+        .child(TextView::new(starting_point))
+        .child(months_row)
+        .child(Panel::new(
+            LinearLayout::vertical()
+                .child(SelectView::<WhatIf>::new().with_name("forecast_what_ifs").min_height(3))
+                .child(what_if_buttons)
+        ).title("What-Ifs (not saved)"))
+        .child(Panel::new(TextView::new("").with_name("forecast_text").scrollable()).full_screen());
+
+    let screen = crate::common_layout::create_screen(
+        "Cash-Flow Forecast",
+        content,
+        &crate::common_layout::view_footer()
+    );
+
+    siv.add_layer(screen);
+    refresh_forecast(siv, &base);
+}
+
+/// Re-run the projection with the current month count and what-ifs
+fn refresh_forecast(siv: &mut Cursive, base: &ForecastBase) {
+    let months_str = siv.call_on_name("forecast_months", |v: &mut EditView| v.get_content()).unwrap();
+    let month_count = match months_str.trim().parse::<u32>() {
+        Ok(n) if n > 0 => n,
+        _ => {
+            siv.add_layer(Dialog::info("Months must be a whole number above zero"));
+            return;
+        }
+    };
+
+    let what_ifs: Vec<WhatIf> = siv.call_on_name("forecast_what_ifs", |v: &mut SelectView<WhatIf>| {
+        v.iter().map(|(_, w)| w.clone()).collect()
+    }).unwrap_or_default();
+
+    let months = forecast_logic::forecast_months(base.latest.date, month_count);
+    let forecast = forecast_logic::project(&base.opening, &months, &base.bills, &base.incomes, &what_ifs);
+    let text = format_forecast(&forecast, &base.buffer);
+
+    siv.call_on_name("forecast_text", |v: &mut TextView| v.set_content(text));
+}
+
+/// One row per month, red when projected negative and yellow when under the cash-flow buffer
+fn format_forecast(forecast: &[forecast_logic::ForecastMonth], buffer: &BigDecimal) -> StyledString {
+    let negative_months: Vec<&forecast_logic::ForecastMonth> = forecast.iter().filter(|m| m.is_negative()).collect();
+    let lowest = forecast.iter().min_by(|a, b| a.balance.cmp(&b.balance));
+
+    let mut content = StyledString::new();
+    if negative_months.is_empty() {
+        content.append_plain("No months are projected to go negative.\n");
+    } else {
+        content.append_styled(
+            format!("{} of {} months projected negative.\n", negative_months.len(), forecast.len()),
+            Color::Light(BaseColor::Red)
+        );
+    }
+    if let Some(month) = lowest {
+        content.append_plain(format!("Lowest month-end balance: ${} in {}\n\n", month.balance, month.month.format("%m/%Y")));
+    }
+
+    content.append_plain(format!("{:<10}{:>14}{:>14}{:>14}{:>14}\n", "MONTH", "INCOME", "BILLS", "WHAT-IF", "BALANCE"));
+    content.append_plain("──────────────────────────────────────────────────────────────\n");
+    for month in forecast {
+        let income_str = format!("${}", month.income);
+        let bills_str = format!("${}", month.bills);
+        let adjustments_str = format!("${}", month.adjustments);
+        let balance_str = format!("${}", month.balance);
+        let marker = if month.is_negative() { "  ◀ negative" } else { "" };
+        let line = format!(
+            "{:<10}{:>14}{:>14}{:>14}{:>14}{}\n",
+            month.month.format("%m/%Y").to_string(),
+            income_str,
+            bills_str,
+            adjustments_str,
+            balance_str,
+            marker
+        );
+
+        if month.is_negative() {
+            content.append_styled(line, Color::Light(BaseColor::Red));
+        } else if month.balance < *buffer {
+            content.append_styled(line, Color::Light(BaseColor::Yellow));
+        } else {
+            content.append_plain(line);
+        }
+    }
+    content
+}
+
+fn what_if_form(siv: &mut Cursive, base: &Rc<ForecastBase>) {
+    let mut kind_select = SelectView::new().popup();
+    for kind in WhatIfKind::all() {
+        kind_select.add_item(kind.label(), kind);
+    }
+
+    let first_month = forecast_logic::forecast_months(base.latest.date, 1)
+        .first()
+        .map_or(String::new(), |m| m.format("%m/%Y").to_string());
+
+    let base_save = Rc::clone(base);
+    siv.add_layer(
+        Dialog::around(
+            ListView::new()
+                .child("Type", kind_select.with_name("what_if_kind"))
+                .child("Amount", EditView::new().with_name("what_if_amount").fixed_width(14))
+                .child("Month (MM/YYYY)", EditView::new().content(first_month).with_name("what_if_month").fixed_width(14))
+        )
+        .title("Add What-If")
+        .button("Ok", move |s| {
+            let kind = s.call_on_name("what_if_kind", |v: &mut SelectView<WhatIfKind>| {
+                v.selection().map(|k| *k)
+            }).flatten().unwrap_or(WhatIfKind::Raise);
+            let amount_str = s.call_on_name("what_if_amount", |v: &mut EditView| v.get_content()).unwrap();
+            let month_str = s.call_on_name("what_if_month", |v: &mut EditView| v.get_content()).unwrap();
+
+            let zero = BigDecimal::from(0);
+            let amount = match BigDecimal::from_str(amount_str.trim()) {
+                Ok(a) if a > zero => a,
+                _ => {
+                    s.add_layer(Dialog::info("Amount must be a number above zero"));
+                    return;
+                }
+            };
+            let Ok(month) = NaiveDate::parse_from_str(&format!("01/{}", month_str.trim()), "%d/%m/%Y") else {
+                s.add_layer(Dialog::info("Invalid month format. Use MM/YYYY"));
+                return;
+            };
+
+            let label = match kind {
+                WhatIfKind::OneTimeExpense => format!("{}: ${} in {}", kind.label(), amount, month.format("%m/%Y")),
+                _ => format!("{}: ${}/month from {}", kind.label(), amount, month.format("%m/%Y")),
+            };
+            s.call_on_name("forecast_what_ifs", |v: &mut SelectView<WhatIf>| {
+                v.add_item(label, WhatIf { kind, amount, month });
+            });
+
+            s.pop_layer();
+            refresh_forecast(s, &base_save);
+        })
+        .button("Cancel", |s| { s.pop_layer(); })
+    );
+}
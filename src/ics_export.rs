@@ -0,0 +1,33 @@
+use chrono::Utc;
+
+use crate::ics_logic::{self, BillReminder};
+use crate::repositories::LedgerRepo;
+
+/// Where `--export-ics` writes when no path is given
+pub fn default_export_path() -> String {
+    std::env::var("HOME")
+        .map(|h| format!("{}/money-bae-bills.ics", h))
+        .unwrap_or_else(|_| "money-bae-bills.ics".to_string())
+}
+
+/// Write every unpaid bill with a due date in an open ledger as an all-day calendar event, soonest first, returning how many were written
+pub fn export_bill_reminders(ledger_repo: &LedgerRepo, path: &str) -> std::io::Result<usize> {
+    let mut reminders: Vec<BillReminder> = ledger_repo
+        .find_unpaid_bills_in_open_ledgers()
+        .into_iter()
+        .filter_map(|(lb, bill)| {
+            lb.due_day.map(|due| BillReminder {
+                ledger_bill_id: lb.id,
+                name: bill.name,
+                amount: lb.amount,
+                due,
+                is_auto_pay: bill.is_auto_pay,
+            })
+        })
+        .collect();
+    reminders.sort_by_key(|r| r.due);
+
+    std::fs::write(path, ics_logic::calendar(&reminders, Utc::now().naive_utc()))?;
+    log::info!("Exported {} bill reminders to {}", reminders.len(), path);
+    Ok(reminders.len())
+}
@@ -0,0 +1,151 @@
+use bigdecimal::BigDecimal;
+use chrono::{Duration, NaiveDate, NaiveDateTime};
+
+/// Longest content line allowed by RFC 5545, in octets, before folding
+const MAX_LINE_OCTETS: usize = 75;
+
+/// An unpaid ledger bill to remind about on its due date
+#[derive(Debug, Clone, PartialEq)]
+pub struct BillReminder {
+    pub ledger_bill_id: i32,
+    pub name: String,
+    pub amount: BigDecimal,
+    pub due: NaiveDate,
+    pub is_auto_pay: bool,
+}
+
+impl BillReminder {
+    /// "Rent $1200.00" or "Phone $45.00 (autopay)"
+    pub fn summary(&self) -> String {
+        if self.is_auto_pay {
+            format!("{} ${} (autopay)", self.name, self.amount)
+        } else {
+            format!("{} ${}", self.name, self.amount)
+        }
+    }
+
+    pub fn description(&self) -> String {
+        let payment = if self.is_auto_pay { "Paid automatically" } else { "Pay manually" };
+        format!("{} due: ${}\n{}", self.name, self.amount, payment)
+    }
+}
+
+/// Escape text values: backslashes, semicolons, commas and newlines
+pub fn escape_text(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Split a content line into 75-octet pieces, continuing each with a leading space
+pub fn fold_line(line: &str) -> String {
+    let mut folded = String::new();
+    let mut octets = 0;
+    // Continuation lines spend one octet on the leading space
+    let mut limit = MAX_LINE_OCTETS;
+    for c in line.chars() {
+        if octets + c.len_utf8() > limit {
+            folded.push_str("\r\n ");
+            octets = 0;
+            limit = MAX_LINE_OCTETS - 1;
+        }
+        folded.push(c);
+        octets += c.len_utf8();
+    }
+    folded
+}
+
+/// One all-day VEVENT with a morning-of display alarm
+fn event_lines(reminder: &BillReminder, stamp: NaiveDateTime) -> Vec<String> {
+    vec![
+        "BEGIN:VEVENT".to_string(),
+        format!("UID:ledger-bill-{}@money-bae", reminder.ledger_bill_id),
+        format!("DTSTAMP:{}", stamp.format("%Y%m%dT%H%M%SZ")),
+        format!("DTSTART;VALUE=DATE:{}", reminder.due.format("%Y%m%d")),
+        format!("DTEND;VALUE=DATE:{}", (reminder.due + Duration::days(1)).format("%Y%m%d")),
+        format!("SUMMARY:{}", escape_text(&reminder.summary())),
+        format!("DESCRIPTION:{}", escape_text(&reminder.description())),
+        "TRANSP:TRANSPARENT".to_string(),
+        "BEGIN:VALARM".to_string(),
+        "ACTION:DISPLAY".to_string(),
+        format!("DESCRIPTION:{}", escape_text(&reminder.summary())),
+        "TRIGGER;RELATED=START:PT9H".to_string(),
+        "END:VALARM".to_string(),
+        "END:VEVENT".to_string(),
+    ]
+}
+
+/// A complete VCALENDAR with CRLF line endings; stamp is the export time in UTC
+pub fn calendar(reminders: &[BillReminder], stamp: NaiveDateTime) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//money-bae//Bill Reminders//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+        "X-WR-CALNAME:money-bae bills".to_string(),
+    ];
+    for reminder in reminders {
+        lines.extend(event_lines(reminder, stamp));
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    lines.iter().map(|line| format!("{}\r\n", fold_line(line))).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reminder(name: &str, is_auto_pay: bool) -> BillReminder {
+        BillReminder {
+            ledger_bill_id: 7,
+            name: name.to_string(),
+            amount: "45.00".parse().unwrap(),
+            due: NaiveDate::from_ymd_opt(2026, 10, 31).unwrap(),
+            is_auto_pay,
+        }
+    }
+
+    #[test]
+    fn test_calendar_has_all_day_event_per_reminder() {
+        let stamp = NaiveDate::from_ymd_opt(2026, 10, 18).unwrap().and_hms_opt(12, 0, 0).unwrap();
+
+        let ics = calendar(&[reminder("Phone", true)], stamp);
+
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+        assert!(ics.contains("UID:ledger-bill-7@money-bae\r\n"));
+        assert!(ics.contains("DTSTAMP:20261018T120000Z\r\n"));
+        assert!(ics.contains("DTSTART;VALUE=DATE:20261031\r\n"));
+        assert!(ics.contains("DTEND;VALUE=DATE:20261101\r\n"));
+        assert!(ics.contains("SUMMARY:Phone $45.00 (autopay)\r\n"));
+    }
+
+    #[test]
+    fn test_text_is_escaped() {
+        assert_eq!(escape_text("Gas, Electric; Water\nMonthly"), "Gas\\, Electric\\; Water\\nMonthly");
+        assert_eq!(reminder("Rent", false).summary(), "Rent $45.00");
+    }
+
+    #[test]
+    fn test_long_lines_fold_at_75_octets() {
+        let line = format!("SUMMARY:{}", "x".repeat(150));
+
+        let folded = fold_line(&line);
+        let pieces: Vec<&str> = folded.split("\r\n").collect();
+
+        assert_eq!(pieces.len(), 3);
+        assert_eq!(pieces[0].len(), 75);
+        assert!(pieces[1].starts_with(' ') && pieces[1].len() == 75);
+        assert_eq!(pieces.concat().replace(' ', ""), line);
+    }
+}
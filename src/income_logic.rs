@@ -0,0 +1,252 @@
+use std::collections::BTreeMap;
+use bigdecimal::BigDecimal;
+use chrono::{Datelike, Duration, Months, NaiveDate};
+
+use crate::card_logic::day_in_month;
+use crate::models::{IncomeCategory, PaySchedule, Paystub};
+
+/// Every pay date a schedule produces between two dates (inclusive), oldest first
+/// Nothing is paid before the anchor date; semi-monthly pays on the anchor's day and `second_day`
+pub fn pay_dates(schedule: PaySchedule, anchor: NaiveDate, second_day: Option<u32>, from: NaiveDate, to: NaiveDate) -> Vec<NaiveDate> {
+    let start = from.max(anchor);
+    if start > to {
+        return Vec::new();
+    }
+
+    match schedule {
+        PaySchedule::Weekly => every_n_days(anchor, 7, start, to),
+        PaySchedule::BiWeekly => every_n_days(anchor, 14, start, to),
+        PaySchedule::Monthly => monthly_days(&[anchor.day()], start, to),
+        PaySchedule::SemiMonthly => {
+            let days = match second_day {
+                Some(day) => vec![anchor.day(), day],
+                None => vec![anchor.day()],
+            };
+            monthly_days(&days, start, to)
+        }
+    }
+}
+
+fn every_n_days(anchor: NaiveDate, step: i64, start: NaiveDate, to: NaiveDate) -> Vec<NaiveDate> {
+    // Jump straight to the first pay date on or after the start
+    let elapsed = (start - anchor).num_days();
+    let periods = (elapsed + step - 1) / step;
+    let mut date = anchor + Duration::days(periods * step);
+
+    let mut dates = Vec::new();
+    while date <= to {
+        dates.push(date);
+        date += Duration::days(step);
+    }
+    dates
+}
+
+fn monthly_days(days: &[u32], start: NaiveDate, to: NaiveDate) -> Vec<NaiveDate> {
+    let mut dates = Vec::new();
+    let mut month = start.with_day(1).unwrap_or(start);
+
+    while month <= to {
+        for day in days {
+            let date = day_in_month(month.year(), month.month(), *day);
+            if date >= start && date <= to {
+                dates.push(date);
+            }
+        }
+        month = match month.checked_add_months(Months::new(1)) {
+            Some(next) => next,
+            None => break,
+        };
+    }
+
+    dates.sort();
+    dates.dedup();
+    dates
+}
+
+/// An income's own category, falling back to its source's; None when it has neither
+pub fn income_category(own: Option<&str>, source: Option<&str>) -> Option<IncomeCategory> {
+    own.or(source).map(|c| IncomeCategory::from(c.to_string()))
+}
+
+/// Total and count of incomes per key (a source or category), largest total first
+pub fn totals_by<K: Clone + Ord>(incomes: &[(K, BigDecimal)]) -> Vec<(K, BigDecimal, usize)> {
+    let mut grouped: BTreeMap<K, (BigDecimal, usize)> = BTreeMap::new();
+    for (key, amount) in incomes {
+        let entry = grouped.entry(key.clone()).or_insert((BigDecimal::from(0), 0));
+        entry.0 += amount;
+        entry.1 += 1;
+    }
+
+    let mut totals: Vec<(K, BigDecimal, usize)> = grouped
+        .into_iter()
+        .map(|(key, (total, count))| (key, total, count))
+        .collect();
+    totals.sort_by(|a, b| b.1.cmp(&a.1));
+    totals
+}
+
+/// A paycheck broken down; every line after gross is a deduction
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PaystubLines {
+    pub gross: BigDecimal,
+    pub federal_tax: BigDecimal,
+    pub state_tax: BigDecimal,
+    pub social_security: BigDecimal,
+    pub medicare: BigDecimal,
+    pub retirement_401k: BigDecimal,
+    pub hsa: BigDecimal,
+    pub insurance: BigDecimal,
+    pub other_deductions: BigDecimal,
+}
+
+impl PaystubLines {
+    /// Each deduction with its label, in paystub order
+    pub fn deduction_lines(&self) -> Vec<(&'static str, &BigDecimal)> {
+        vec![
+            ("Federal Tax", &self.federal_tax),
+            ("State Tax", &self.state_tax),
+            ("Social Security", &self.social_security),
+            ("Medicare", &self.medicare),
+            ("401k", &self.retirement_401k),
+            ("HSA", &self.hsa),
+            ("Insurance", &self.insurance),
+            ("Other", &self.other_deductions),
+        ]
+    }
+
+    pub fn deductions(&self) -> BigDecimal {
+        self.deduction_lines().into_iter().map(|(_, amount)| amount.clone()).sum()
+    }
+
+    /// Take-home pay; must match the income's amount
+    pub fn net(&self) -> BigDecimal {
+        &self.gross - self.deductions()
+    }
+}
+
+pub fn to_paystub_lines(paystub: &Paystub) -> PaystubLines {
+    PaystubLines {
+        gross: paystub.gross.clone(),
+        federal_tax: paystub.federal_tax.clone(),
+        state_tax: paystub.state_tax.clone(),
+        social_security: paystub.social_security.clone(),
+        medicare: paystub.medicare.clone(),
+        retirement_401k: paystub.retirement_401k.clone(),
+        hsa: paystub.hsa.clone(),
+        insurance: paystub.insurance.clone(),
+        other_deductions: paystub.other_deductions.clone(),
+    }
+}
+
+/// Line-by-line totals across paystubs, e.g. year to date
+pub fn paystub_totals(paystubs: &[PaystubLines]) -> PaystubLines {
+    paystubs.iter().fold(PaystubLines::default(), |mut totals, stub| {
+        totals.gross += &stub.gross;
+        totals.federal_tax += &stub.federal_tax;
+        totals.state_tax += &stub.state_tax;
+        totals.social_security += &stub.social_security;
+        totals.medicare += &stub.medicare;
+        totals.retirement_401k += &stub.retirement_401k;
+        totals.hsa += &stub.hsa;
+        totals.insurance += &stub.insurance;
+        totals.other_deductions += &stub.other_deductions;
+        totals
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_bi_weekly_counts_from_anchor() {
+        let dates = pay_dates(PaySchedule::BiWeekly, date(2026, 1, 2), None, date(2026, 3, 1), date(2026, 3, 31));
+
+        assert_eq!(dates, vec![date(2026, 3, 13), date(2026, 3, 27)]);
+    }
+
+    #[test]
+    fn test_weekly_includes_start_day() {
+        let dates = pay_dates(PaySchedule::Weekly, date(2026, 1, 2), None, date(2026, 1, 9), date(2026, 1, 20));
+
+        assert_eq!(dates, vec![date(2026, 1, 9), date(2026, 1, 16)]);
+    }
+
+    #[test]
+    fn test_semi_monthly_clamps_to_month_end() {
+        let dates = pay_dates(PaySchedule::SemiMonthly, date(2026, 1, 15), Some(31), date(2026, 2, 1), date(2026, 3, 31));
+
+        assert_eq!(dates, vec![date(2026, 2, 15), date(2026, 2, 28), date(2026, 3, 15), date(2026, 3, 31)]);
+    }
+
+    #[test]
+    fn test_monthly_skips_dates_before_anchor() {
+        let dates = pay_dates(PaySchedule::Monthly, date(2026, 2, 25), None, date(2026, 1, 1), date(2026, 4, 30));
+
+        assert_eq!(dates, vec![date(2026, 2, 25), date(2026, 3, 25), date(2026, 4, 25)]);
+    }
+
+    #[test]
+    fn test_income_category_overrides_source() {
+        assert_eq!(income_category(Some("Reimbursement"), Some("Salary")), Some(IncomeCategory::Reimbursement));
+        assert_eq!(income_category(None, Some("Side Gig")), Some(IncomeCategory::SideGig));
+        assert_eq!(income_category(Some("Interest"), None), Some(IncomeCategory::Interest));
+        assert_eq!(income_category(None, None), None);
+    }
+
+    #[test]
+    fn test_totals_by_source_largest_first() {
+        let incomes = vec![
+            (Some(1), BigDecimal::from(100)),
+            (None, BigDecimal::from(50)),
+            (Some(2), BigDecimal::from(2000)),
+            (Some(1), BigDecimal::from(150)),
+        ];
+
+        let totals = totals_by(&incomes);
+
+        assert_eq!(totals, vec![
+            (Some(2), BigDecimal::from(2000), 1),
+            (Some(1), BigDecimal::from(250), 2),
+            (None, BigDecimal::from(50), 1),
+        ]);
+    }
+
+    fn paystub(gross: i32, federal_tax: i32, retirement_401k: i32) -> PaystubLines {
+        PaystubLines {
+            gross: BigDecimal::from(gross),
+            federal_tax: BigDecimal::from(federal_tax),
+            retirement_401k: BigDecimal::from(retirement_401k),
+            ..PaystubLines::default()
+        }
+    }
+
+    #[test]
+    fn test_paystub_net_is_gross_less_deductions() {
+        let stub = paystub(3000, 400, 150);
+
+        assert_eq!(stub.deductions(), BigDecimal::from(550));
+        assert_eq!(stub.net(), BigDecimal::from(2450));
+    }
+
+    #[test]
+    fn test_paystub_totals_sum_each_line() {
+        let totals = paystub_totals(&[paystub(3000, 400, 150), paystub(3200, 420, 160)]);
+
+        assert_eq!(totals.gross, BigDecimal::from(6200));
+        assert_eq!(totals.federal_tax, BigDecimal::from(820));
+        assert_eq!(totals.retirement_401k, BigDecimal::from(310));
+        assert_eq!(totals.net(), BigDecimal::from(5070));
+    }
+
+    #[test]
+    fn test_range_before_anchor_is_empty() {
+        let dates = pay_dates(PaySchedule::Weekly, date(2026, 6, 1), None, date(2026, 1, 1), date(2026, 5, 31));
+
+        assert!(dates.is_empty());
+    }
+}
@@ -2,26 +2,38 @@ use std::cmp::Ordering;
 use std::rc::Rc;
 use std::str::FromStr;
 use bigdecimal::BigDecimal;
-use chrono::{Local, NaiveDate};
+use chrono::{Datelike, Local, Months, NaiveDate};
 use cursive::Cursive;
 use cursive::traits::*;
-use cursive::views::{Button, Dialog, EditView, HideableView, LinearLayout, ListView, Panel, TextArea};
+use cursive::views::{Button, Checkbox, Dialog, EditView, HideableView, LinearLayout, ListView, Panel, SelectView, TextArea, TextView};
 use cursive_table_view::{TableView, TableViewItem};
 
-use crate::models;
+use crate::income_logic::{self, PaystubLines};
+use crate::models::{self, IncomeCategory, PaySchedule};
+use crate::dependency_container::IncomeRepos;
 use crate::repositories::IncomeRepo;
 use crate::ui_helpers::toggle_buttons_visible;
 
 // Button name constants
 const INCOME_EDIT_BUTTON: &str = "income_table_edit_button";
+const INCOME_CONFIRM_BUTTON: &str = "income_table_confirm_button";
+const INCOME_PAYSTUB_BUTTON: &str = "income_table_paystub_button";
 const INCOME_DUPLICATE_BUTTON: &str = "income_table_duplicate_button";
 const INCOME_DELETE_BUTTON: &str = "income_table_delete_button";
-const TOGGLE_BUTTONS: &[&str] = &[INCOME_EDIT_BUTTON, INCOME_DUPLICATE_BUTTON, INCOME_DELETE_BUTTON];
+const TOGGLE_BUTTONS: &[&str] = &[INCOME_EDIT_BUTTON, INCOME_CONFIRM_BUTTON, INCOME_PAYSTUB_BUTTON, INCOME_DUPLICATE_BUTTON, INCOME_DELETE_BUTTON];
+
+/// Months ahead expected paychecks are generated for by default
+const DEFAULT_GENERATE_MONTHS: u32 = 3;
+
+/// Gross pay plus each deduction on the paystub form
+const PAYSTUB_LINE_COUNT: usize = 9;
 
 #[derive(Copy, Clone, PartialEq, Eq, Hash)]
 enum BasicColumn {
     Date,
+    Source,
     Amount,
+    Status,
 }
 
 #[derive(Clone, Debug)]
@@ -30,24 +42,105 @@ struct IncomeDisplay {
     date: NaiveDate,
     amount: BigDecimal,
     notes: Option<String>,
+    account_id: Option<i32>,
+    income_source_id: Option<i32>,
+    source: String,
+    is_expected: bool,
+    scheduled_date: Option<NaiveDate>,
+    tax_category: Option<String>,
+    category: Option<String>,
+}
+
+/// Which incomes the table shows
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum SourceFilter {
+    All,
+    Unassigned,
+    Source(i32),
+}
+
+impl SourceFilter {
+    fn matches(&self, income_source_id: Option<i32>) -> bool {
+        match self {
+            SourceFilter::All => true,
+            SourceFilter::Unassigned => income_source_id.is_none(),
+            SourceFilter::Source(sid) => income_source_id == Some(*sid),
+        }
+    }
+}
+
+fn fill_source_filter(select: &mut SelectView<SourceFilter>, sources: &[models::IncomeSource], selected: SourceFilter) {
+    select.clear();
+    select.add_item("All Sources", SourceFilter::All);
+    select.add_item("(No Source)", SourceFilter::Unassigned);
+    for source in sources {
+        select.add_item(source.name.clone(), SourceFilter::Source(source.id));
+    }
+
+    let selected_index = match selected {
+        SourceFilter::All => 0,
+        SourceFilter::Unassigned => 1,
+        SourceFilter::Source(sid) => sources.iter().position(|src| src.id == sid).map_or(0, |i| i + 2),
+    };
+    let _ = select.set_selection(selected_index);
 }
 
-impl From<models::Income> for IncomeDisplay {
-    fn from(income: models::Income) -> Self {
-        IncomeDisplay {
+/// Incomes matching the filter with their source names, ready for the table
+/// Incomes without a source show their own category instead
+fn load_income_displays(income_repo: &IncomeRepo, sources: &[models::IncomeSource], filter: SourceFilter) -> Vec<IncomeDisplay> {
+    income_repo.find_all()
+        .into_iter()
+        .filter(|income| filter.matches(income.income_source_id))
+        .map(|income| IncomeDisplay {
             id: income.id,
             date: income.date,
             amount: income.amount,
             notes: income.notes,
-        }
-    }
+            account_id: income.account_id,
+            income_source_id: income.income_source_id,
+            source: income.income_source_id
+                .and_then(|sid| sources.iter().find(|src| src.id == sid))
+                .map(|src| src.name.clone())
+                .or_else(|| income.category.as_ref().map(|c| format!("({})", IncomeCategory::from(c.clone()))))
+                .unwrap_or_default(),
+            is_expected: income.is_expected,
+            scheduled_date: income.scheduled_date,
+            tax_category: income.tax_category,
+            category: income.category,
+        })
+        .collect()
+}
+
+fn reload_income_table(siv: &mut Cursive, repos: &IncomeRepos) {
+    let sources = repos.income_source.find_all();
+    let filter = siv.call_on_name("income_source_filter", |v: &mut SelectView<SourceFilter>| {
+        v.selection().map(|f| *f)
+    }).flatten().unwrap_or(SourceFilter::All);
+    siv.call_on_name("income_source_filter", |v: &mut SelectView<SourceFilter>| {
+        fill_source_filter(v, &sources, filter);
+    });
+
+    let income_displays = load_income_displays(&repos.income, &sources, filter);
+    let income_count = income_displays.len();
+
+    siv.call_on_name("income_table", |v: &mut TableView<IncomeDisplay, BasicColumn>| {
+        v.set_items(income_displays);
+    });
+
+    toggle_buttons_visible(siv, income_count, TOGGLE_BUTTONS);
 }
 
 impl TableViewItem<BasicColumn> for IncomeDisplay {
     fn to_column(&self, column: BasicColumn) -> String {
         match column {
             BasicColumn::Date => self.date.format("%d/%m/%Y").to_string(),
+            BasicColumn::Source => self.source.clone(),
             BasicColumn::Amount => self.amount.to_string(),
+            BasicColumn::Status => if self.is_expected {
+                format!("Expected {}", self.scheduled_date.unwrap_or(self.date).format("%d/%m"))
+            } else {
+                String::new()
+            },
         }
     }
 
@@ -57,41 +150,46 @@ impl TableViewItem<BasicColumn> for IncomeDisplay {
     {
         match column {
             BasicColumn::Date => self.date.cmp(&other.date),
+            BasicColumn::Source => self.source.cmp(&other.source),
             BasicColumn::Amount => self.amount.cmp(&other.amount),
+            BasicColumn::Status => self.is_expected.cmp(&other.is_expected),
         }
     }
 }
 
 pub struct IncomeTableView {
     table: TableView<IncomeDisplay,BasicColumn>,
-    income_repo: Rc<IncomeRepo>,
+    repos: Rc<IncomeRepos>,
 }
 
 impl IncomeTableView {
-    pub fn new(income_repo: Rc<IncomeRepo>) -> Self {
-        let results = income_repo.find_all();
-
-        let income_displays: Vec<IncomeDisplay> = results
-            .into_iter()
-            .map(|i| i.into())
-            .collect();
+    pub fn new(repos: Rc<IncomeRepos>) -> Self {
+        let sources = repos.income_source.find_all();
+        let income_displays = load_income_displays(&repos.income, &sources, SourceFilter::All);
 
         Self {
             table: TableView::<IncomeDisplay,BasicColumn>::new()
-                .column(BasicColumn::Date, "Date", |c| c.width_percent(40))
-                .column(BasicColumn::Amount, "Amount", |c| c.width_percent(60))
+                .column(BasicColumn::Date, "Date", |c| c.width_percent(25))
+                .column(BasicColumn::Source, "Source", |c| c.width_percent(35))
+                .column(BasicColumn::Amount, "Amount", |c| c.width_percent(20))
+                .column(BasicColumn::Status, "Status", |c| c.width_percent(20))
                 .items(income_displays),
-            income_repo,
+            repos,
         }
     }
 
     pub fn add_table(self, siv: &mut Cursive) {
         siv.pop_layer();
 
-        let repo_add = Rc::clone(&self.income_repo);
-        let repo_edit = Rc::clone(&self.income_repo);
-        let repo_duplicate = Rc::clone(&self.income_repo);
-        let repo_delete = Rc::clone(&self.income_repo);
+        let repo_add = Rc::clone(&self.repos);
+        let repo_edit = Rc::clone(&self.repos);
+        let repo_confirm = Rc::clone(&self.repos);
+        let repo_paystub = Rc::clone(&self.repos);
+        let repo_duplicate = Rc::clone(&self.repos);
+        let repo_delete = Rc::clone(&self.repos);
+        let repo_sources = Rc::clone(&self.repos);
+        let repo_totals = Rc::clone(&self.repos);
+        let repo_filter = Rc::clone(&self.repos);
 
         let buttons = LinearLayout::horizontal()
             .child(Button::new("Add", move |s| income_form(s, None, &repo_add)))
@@ -104,11 +202,32 @@ impl IncomeTableView {
                     income_form(s, Some(income), &repo_edit);
                 }
             })).with_name(INCOME_EDIT_BUTTON))
+            .child(HideableView::new(Button::new("Confirm", move |s| confirm_income(s, &repo_confirm))).with_name(INCOME_CONFIRM_BUTTON))
+            .child(HideableView::new(Button::new("Paystub", move |s| {
+                let selected = s.call_on_name("income_table", |v: &mut TableView<IncomeDisplay, BasicColumn>| {
+                    v.borrow_item(v.item().unwrap()).cloned()
+                }).flatten();
+
+                if let Some(income) = selected {
+                    paystub_form(s, &income, &repo_paystub);
+                }
+            })).with_name(INCOME_PAYSTUB_BUTTON))
             .child(HideableView::new(Button::new("Duplicate", move |s| duplicate_income(s, &repo_duplicate))).with_name(INCOME_DUPLICATE_BUTTON))
-            .child(HideableView::new(Button::new("Delete", move |s| delete_income(s, &repo_delete))).with_name(INCOME_DELETE_BUTTON));
+            .child(HideableView::new(Button::new("Delete", move |s| delete_income(s, &repo_delete))).with_name(INCOME_DELETE_BUTTON))
+            .child(Button::new("Sources", move |s| show_income_sources(s, &repo_sources)))
+            .child(Button::new("Totals", move |s| show_income_totals(s, &repo_totals)));
+
+        // Narrow the table to one source; sorting by the Source column groups the rest
+        let sources = self.repos.income_source.find_all();
+        let mut source_filter = SelectView::new().popup();
+        fill_source_filter(&mut source_filter, &sources, SourceFilter::All);
+        let source_filter = source_filter.on_submit(move |s, _: &SourceFilter| reload_income_table(s, &repo_filter));
 
         let income_count = self.table.len();
         let content = LinearLayout::vertical()
+            .child(LinearLayout::horizontal()
+                .child(TextView::new("Source: "))
+                .child(source_filter.with_name("income_source_filter")))
             .child(Panel::new(
                 self.table
                     .with_name("income_table")
@@ -128,7 +247,7 @@ impl IncomeTableView {
     }
 }
 
-fn income_form(siv: &mut Cursive, existing: Option<IncomeDisplay>, income_repo: &Rc<IncomeRepo>) {
+fn income_form(siv: &mut Cursive, existing: Option<IncomeDisplay>, repos: &Rc<IncomeRepos>) {
     let is_edit = existing.is_some();
     let title = if is_edit { "Edit Income" } else { "Add Income" };
     let button_label = if is_edit { "Update" } else { "Ok" };
@@ -149,8 +268,49 @@ fn income_form(siv: &mut Cursive, existing: Option<IncomeDisplay>, income_repo:
         .and_then(|i| i.notes.clone())
         .unwrap_or_default();
 
+    // Account the income lands in (None uses the ledger's account)
+    let account_value = existing
+        .as_ref()
+        .and_then(|i| i.account_id);
+    let accounts = repos.account.find_all();
+    let account_select = crate::account_table::build_account_select(&accounts, account_value);
+
+    let source_value = existing
+        .as_ref()
+        .and_then(|i| i.income_source_id);
+    let sources = repos.income_source.find_all();
+    let mut source_select = SelectView::new().popup();
+    source_select.add_item("(None)", None);
+    for source in &sources {
+        source_select.add_item(format!("{} ({})", source.name, IncomeCategory::from(source.category.clone())), Some(source.id));
+    }
+    let source_index = source_value
+        .and_then(|sid| sources.iter().position(|src| src.id == sid))
+        .map_or(0, |i| i + 1);
+    let source_select = source_select.selected(source_index);
+
+    // Tags one-off income like a reimbursement; (From Source) uses the source's category
+    let category_value = existing
+        .as_ref()
+        .and_then(|i| i.category.clone())
+        .map(IncomeCategory::from);
+    let mut category_select = SelectView::new().popup();
+    category_select.add_item("(From Source)", None);
+    for category in IncomeCategory::all() {
+        category_select.add_item(category.to_string(), Some(category));
+    }
+    let category_index = category_value
+        .and_then(|value| IncomeCategory::all().iter().position(|c| *c == value))
+        .map_or(0, |i| i + 1);
+    let category_select = category_select.selected(category_index);
+
+    let tax_value = existing
+        .as_ref()
+        .and_then(|i| i.tax_category.clone());
+    let tax_select = crate::tax_report::build_tax_category_select("(None)", tax_value.as_deref());
+
     let income_id = existing.map(|i| i.id);
-    let repo_form = Rc::clone(income_repo);
+    let repo_form = Rc::clone(repos);
 
     siv.add_layer(
         Dialog::new()
@@ -168,6 +328,22 @@ fn income_form(siv: &mut Cursive, existing: Option<IncomeDisplay>, income_repo:
                     v.get_content().to_string()
                 }).unwrap();
 
+                let account_id = s.call_on_name("income_account_select", |v: &mut SelectView<Option<i32>>| {
+                    v.selection().and_then(|a| *a)
+                }).flatten();
+
+                let source_id = s.call_on_name("income_source_select", |v: &mut SelectView<Option<i32>>| {
+                    v.selection().and_then(|src| *src)
+                }).flatten();
+
+                let tax_category = s.call_on_name("income_tax_select", |v: &mut SelectView<Option<String>>| {
+                    v.selection().and_then(|t| (*t).clone())
+                }).flatten();
+
+                let category = s.call_on_name("income_category_select", |v: &mut SelectView<Option<IncomeCategory>>| {
+                    v.selection().and_then(|c| *c)
+                }).flatten();
+
                 // Validate date format DD/MM/YYYY
                 let parsed_date = NaiveDate::parse_from_str(&date_str, "%d/%m/%Y");
                 let amount_bd = BigDecimal::from_str(&amount_str);
@@ -182,62 +358,61 @@ fn income_form(siv: &mut Cursive, existing: Option<IncomeDisplay>, income_repo:
                     return;
                 }
 
-                let notes_opt = if notes_str.is_empty() { None } else { Some(notes_str.to_string()) };
+                let income = models::NewIncome {
+                    date: parsed_date.unwrap(),
+                    amount: amount_bd.unwrap(),
+                    notes: if notes_str.is_empty() { None } else { Some(notes_str.to_string()) },
+                    account_id,
+                    income_source_id: source_id,
+                    is_expected: false,
+                    tax_category,
+                    scheduled_date: None,
+                    category: category.map(String::from),
+                };
 
                 if let Some(record_id) = income_id {
-                    repo_form.update(record_id, parsed_date.unwrap(), amount_bd.unwrap(), notes_opt);
+                    if let Err(e) = repo_form.income.update(record_id, income) {
+                        s.add_layer(Dialog::info(e.to_string()));
+                        return;
+                    }
                 } else {
-                    repo_form.create(parsed_date.unwrap(), amount_bd.unwrap(), notes_opt);
+                    repo_form.income.create(income);
                 }
 
-                // Reload table
-                let income_displays = repo_form.find_all()
-                    .into_iter()
-                    .map(|i| IncomeDisplay::from(i))
-                    .collect::<Vec<_>>();
-                let income_count = income_displays.len();
-
-                s.call_on_name("income_table", |v: &mut TableView<IncomeDisplay, BasicColumn>| {
-                    v.set_items(income_displays);
-                });
+                reload_income_table(s, &repo_form);
                 s.pop_layer();
-
-                toggle_buttons_visible(s, income_count, TOGGLE_BUTTONS);
             })
             .button("Cancel", |s| { s.pop_layer(); })
             .content(
                 ListView::new()
                     .child("Date (DD/MM/YYYY)", EditView::new().content(date_value).with_name("date_input").fixed_width(20))
                     .child("Amount", EditView::new().content(amount_value).with_name("amount_input").fixed_width(20))
+                    .child("Source", source_select.with_name("income_source_select"))
+                    .child("Category", category_select.with_name("income_category_select"))
+                    .child("Account", account_select.with_name("income_account_select"))
+                    .child("Tax", tax_select.with_name("income_tax_select"))
                     .child("Notes", TextArea::new().content(notes_value).with_name("notes_input").min_size((40, 3)))
             )
     );
 }
 
-fn delete_income(siv: &mut Cursive, income_repo: &Rc<IncomeRepo>) {
+fn delete_income(siv: &mut Cursive, repos: &Rc<IncomeRepos>) {
     let selected = siv.call_on_name("income_table", |v: &mut TableView<IncomeDisplay, BasicColumn>| {
         v.borrow_item(v.item().unwrap()).cloned()
     }).flatten();
 
     if let Some(income) = selected {
-        let repo_delete = Rc::clone(income_repo);
+        let repo_delete = Rc::clone(repos);
         siv.add_layer(
             Dialog::text("Delete this income?")
                 .button("Yes", move |s| {
-                    repo_delete.delete(income.id);
-
-                    // Reload table
-                    let income_displays = repo_delete.find_all()
-                        .into_iter()
-                        .map(|i| IncomeDisplay::from(i))
-                        .collect::<Vec<_>>();
-                    let income_count = income_displays.len();
+                    if let Err(e) = repo_delete.income.delete(income.id) {
+                        s.pop_layer();
+                        s.add_layer(Dialog::info(e.to_string()));
+                        return;
+                    }
 
-                    s.call_on_name("income_table", |v: &mut TableView<IncomeDisplay, BasicColumn>| {
-                        v.set_items(income_displays);
-                    });
-
-                    toggle_buttons_visible(s, income_count, TOGGLE_BUTTONS);
+                    reload_income_table(s, &repo_delete);
 
                     s.pop_layer();
                 })
@@ -246,25 +421,535 @@ fn delete_income(siv: &mut Cursive, income_repo: &Rc<IncomeRepo>) {
     }
 }
 
-fn duplicate_income(siv: &mut Cursive, income_repo: &Rc<IncomeRepo>) {
+fn duplicate_income(siv: &mut Cursive, repos: &Rc<IncomeRepos>) {
     let selected = siv.call_on_name("income_table", |v: &mut TableView<IncomeDisplay, BasicColumn>| {
         v.borrow_item(v.item().unwrap()).cloned()
     }).flatten();
 
     if let Some(income) = selected {
-        income_repo.create(Local::now().date_naive(), income.amount, None);
+        repos.income.create(models::NewIncome {
+            date: Local::now().date_naive(),
+            amount: income.amount,
+            notes: None,
+            account_id: income.account_id,
+            income_source_id: income.income_source_id,
+            is_expected: false,
+            tax_category: income.tax_category,
+            scheduled_date: None,
+            category: income.category,
+        });
+
+        reload_income_table(siv, repos);
+    }
+}
+
+/// Replace an expected paycheck's estimate with what actually arrived
+fn confirm_income(siv: &mut Cursive, repos: &Rc<IncomeRepos>) {
+    let selected = siv.call_on_name("income_table", |v: &mut TableView<IncomeDisplay, BasicColumn>| {
+        v.borrow_item(v.item().unwrap()).cloned()
+    }).flatten();
+
+    let Some(income) = selected else {
+        return;
+    };
+
+    if !income.is_expected {
+        siv.add_layer(Dialog::info("This income is already confirmed"));
+        return;
+    }
+
+    let income_id = income.id;
+    let repo_confirm = Rc::clone(repos);
+    siv.add_layer(
+        Dialog::around(
+            ListView::new()
+                .child("Date (DD/MM/YYYY)", EditView::new()
+                    .content(income.date.format("%d/%m/%Y").to_string())
+                    .with_name("confirm_date_input")
+                    .fixed_width(20))
+                .child("Actual Amount", EditView::new()
+                    .content(income.amount.to_string())
+                    .with_name("confirm_amount_input")
+                    .fixed_width(20))
+        )
+        .title(format!("Confirm {}", if income.source.is_empty() { "Income" } else { income.source.as_str() }))
+        .button("Confirm", move |s| {
+            let date_str = s.call_on_name("confirm_date_input", |v: &mut EditView| v.get_content()).unwrap();
+            let amount_str = s.call_on_name("confirm_amount_input", |v: &mut EditView| v.get_content()).unwrap();
+
+            let date = match NaiveDate::parse_from_str(date_str.trim(), "%d/%m/%Y") {
+                Ok(d) => d,
+                Err(_) => {
+                    s.add_layer(Dialog::info("Invalid date format. Use DD/MM/YYYY"));
+                    return;
+                }
+            };
+
+            let amount = match BigDecimal::from_str(amount_str.trim()) {
+                Ok(a) => a,
+                Err(_) => {
+                    s.add_layer(Dialog::info("Invalid amount format"));
+                    return;
+                }
+            };
 
-        // Reload table
-        let income_displays = income_repo.find_all()
-            .into_iter()
-            .map(|i| IncomeDisplay::from(i))
-            .collect::<Vec<_>>();
-        let income_count = income_displays.len();
+            if let Err(e) = repo_confirm.income.confirm(income_id, date, amount) {
+                s.add_layer(Dialog::info(e.to_string()));
+                return;
+            }
+
+            reload_income_table(s, &repo_confirm);
+            s.pop_layer();
+        })
+        .button("Cancel", |s| { s.pop_layer(); })
+    );
+}
+
+fn show_income_sources(siv: &mut Cursive, repos: &Rc<IncomeRepos>) {
+    let source_repo = Rc::clone(&repos.income_source);
+    let sources = source_repo.find_all();
+
+    let mut select = SelectView::<i32>::new();
+    for source in &sources {
+        select.add_item(
+            format!(
+                "{} ({}) - {} - ${}{}",
+                source.name,
+                IncomeCategory::from(source.category.clone()),
+                PaySchedule::from(source.schedule.clone()),
+                source.expected_amount,
+                if source.is_active { "" } else { " (inactive)" }
+            ),
+            source.id
+        );
+    }
+
+    let sources_edit = sources.clone();
+    let repo_add = Rc::clone(repos);
+    let repo_edit = Rc::clone(repos);
+    let repo_generate = Rc::clone(repos);
+    let repo_delete = Rc::clone(repos);
+    let source_repo_delete = Rc::clone(&source_repo);
+
+    siv.add_layer(
+        Dialog::around(select.with_name("income_source_select").scrollable().min_size((50, 8)))
+            .title("Income Sources")
+            .button("Add", move |s| income_source_form(s, None, &repo_add))
+            .button("Edit", move |s| {
+                let source_id = s.call_on_name("income_source_select", |v: &mut SelectView<i32>| {
+                    v.selection().map(|id| *id)
+                }).flatten();
 
-        siv.call_on_name("income_table", |v: &mut TableView<IncomeDisplay, BasicColumn>| {
-            v.set_items(income_displays);
+                if let Some(source) = source_id.and_then(|sid| sources_edit.iter().find(|src| src.id == sid)) {
+                    income_source_form(s, Some(source.clone()), &repo_edit);
+                }
+            })
+            .button("Generate", move |s| generate_expected_incomes(s, &repo_generate))
+            .button("Delete", move |s| {
+                let source_id = s.call_on_name("income_source_select", |v: &mut SelectView<i32>| {
+                    v.selection().map(|id| *id)
+                }).flatten();
+
+                if let Some(sid) = source_id {
+                    source_repo_delete.delete(sid);
+                    s.pop_layer();
+                    reload_income_table(s, &repo_delete);
+                    show_income_sources(s, &repo_delete);
+                }
+            })
+            .button("Close", |s| { s.pop_layer(); })
+    );
+}
+
+fn income_source_form(siv: &mut Cursive, existing: Option<models::IncomeSource>, repos: &Rc<IncomeRepos>) {
+    let title = if existing.is_some() { "Edit Income Source" } else { "Add Income Source" };
+
+    let name_value = existing.as_ref().map(|src| src.name.clone()).unwrap_or_default();
+    let schedule_value = existing.as_ref().map_or(PaySchedule::Monthly, |src| PaySchedule::from(src.schedule.clone()));
+    let category_value = existing.as_ref().map_or(IncomeCategory::Salary, |src| IncomeCategory::from(src.category.clone()));
+    let anchor_value = existing
+        .as_ref()
+        .map(|src| src.anchor_date)
+        .unwrap_or_else(|| Local::now().date_naive())
+        .format("%d/%m/%Y")
+        .to_string();
+    let second_day_value = existing.as_ref().and_then(|src| src.second_day).map(|d| d.to_string()).unwrap_or_default();
+    let amount_value = existing.as_ref().map(|src| src.expected_amount.to_string()).unwrap_or_default();
+    let active_value = existing.as_ref().is_none_or(|src| src.is_active);
+    let notes_value = existing.as_ref().and_then(|src| src.notes.clone()).unwrap_or_default();
+    let account_value = existing.as_ref().and_then(|src| src.account_id);
+    let source_id = existing.map(|src| src.id);
+
+    let mut schedule_select = SelectView::new().popup();
+    for schedule in PaySchedule::all() {
+        schedule_select.add_item(schedule.to_string(), schedule);
+    }
+    let schedule_index = PaySchedule::all().iter().position(|sch| *sch == schedule_value).unwrap_or(0);
+    let schedule_select = schedule_select.selected(schedule_index);
+
+    let mut category_select = SelectView::new().popup();
+    for category in IncomeCategory::all() {
+        category_select.add_item(category.to_string(), category);
+    }
+    let category_index = IncomeCategory::all().iter().position(|c| *c == category_value).unwrap_or(0);
+    let category_select = category_select.selected(category_index);
+
+    let accounts = repos.account.find_all();
+    let account_select = crate::account_table::build_account_select(&accounts, account_value);
+
+    let repo = Rc::clone(repos);
+    siv.add_layer(
+        Dialog::new()
+            .title(title)
+            .button("Ok", move |s| {
+                let name_str = s.call_on_name("source_name_input", |v: &mut EditView| v.get_content()).unwrap();
+                let anchor_str = s.call_on_name("source_anchor_input", |v: &mut EditView| v.get_content()).unwrap();
+                let second_day_str = s.call_on_name("source_second_day_input", |v: &mut EditView| v.get_content()).unwrap();
+                let amount_str = s.call_on_name("source_amount_input", |v: &mut EditView| v.get_content()).unwrap();
+                let notes_str = s.call_on_name("source_notes_input", |v: &mut TextArea| v.get_content().to_string()).unwrap();
+                let is_active = s.call_on_name("source_active_checkbox", |v: &mut Checkbox| v.is_checked()).unwrap();
+                let schedule = s.call_on_name("source_schedule_select", |v: &mut SelectView<PaySchedule>| {
+                    v.selection().map(|sch| *sch)
+                }).flatten().unwrap_or(PaySchedule::Monthly);
+                let category = s.call_on_name("source_category_select", |v: &mut SelectView<IncomeCategory>| {
+                    v.selection().map(|c| *c)
+                }).flatten().unwrap_or(IncomeCategory::Other);
+                let account_id = s.call_on_name("source_account_select", |v: &mut SelectView<Option<i32>>| {
+                    v.selection().and_then(|a| *a)
+                }).flatten();
+
+                if name_str.trim().is_empty() {
+                    s.add_layer(Dialog::info("Name cannot be empty"));
+                    return;
+                }
+
+                let anchor_date = match NaiveDate::parse_from_str(anchor_str.trim(), "%d/%m/%Y") {
+                    Ok(d) => d,
+                    Err(_) => {
+                        s.add_layer(Dialog::info("Invalid first pay date. Use DD/MM/YYYY"));
+                        return;
+                    }
+                };
+
+                let second_day = if schedule == PaySchedule::SemiMonthly {
+                    match second_day_str.trim().parse::<i32>().ok().filter(|d| (1..=31).contains(d)) {
+                        Some(d) => Some(d),
+                        None => {
+                            s.add_layer(Dialog::info("Semi-monthly sources need a second pay day between 1 and 31"));
+                            return;
+                        }
+                    }
+                } else {
+                    None
+                };
+
+                let expected_amount = match BigDecimal::from_str(amount_str.trim()) {
+                    Ok(a) => a,
+                    Err(_) => {
+                        s.add_layer(Dialog::info("Invalid amount format"));
+                        return;
+                    }
+                };
+
+                let source = models::NewIncomeSource {
+                    name: name_str.trim().to_string(),
+                    schedule: schedule.into(),
+                    anchor_date,
+                    second_day,
+                    expected_amount,
+                    account_id,
+                    is_active,
+                    notes: if notes_str.is_empty() { None } else { Some(notes_str) },
+                    category: category.into(),
+                };
+
+                let source_repo = &repo.income_source;
+                if let Some(sid) = source_id {
+                    source_repo.update(sid, source);
+                } else {
+                    source_repo.create(source);
+                }
+
+                s.pop_layer(); // Close form
+                s.pop_layer(); // Close sources
+                reload_income_table(s, &repo);
+                show_income_sources(s, &repo);
+            })
+            .button("Cancel", |s| { s.pop_layer(); })
+            .content(
+                ListView::new()
+                    .child("Name", EditView::new().content(name_value).with_name("source_name_input").fixed_width(20))
+                    .child("Category", category_select.with_name("source_category_select"))
+                    .child("Schedule", schedule_select.with_name("source_schedule_select"))
+                    .child("First Pay Date", EditView::new().content(anchor_value).with_name("source_anchor_input").fixed_width(20))
+                    .child("Second Day (semi-monthly)", EditView::new().content(second_day_value).with_name("source_second_day_input").fixed_width(20))
+                    .child("Expected Amount", EditView::new().content(amount_value).with_name("source_amount_input").fixed_width(20))
+                    .child("Account", account_select.with_name("source_account_select"))
+                    .child("Active", Checkbox::new().with_checked(active_value).with_name("source_active_checkbox"))
+                    .child("Notes", TextArea::new().content(notes_value).with_name("source_notes_input").min_size((40, 3)))
+            )
+    );
+}
+
+/// Fill in expected paychecks for active sources from the start of this month
+fn generate_expected_incomes(siv: &mut Cursive, repos: &Rc<IncomeRepos>) {
+    let repo = Rc::clone(repos);
+    siv.add_layer(
+        Dialog::around(
+            ListView::new()
+                .child("Months Ahead", EditView::new()
+                    .content(DEFAULT_GENERATE_MONTHS.to_string())
+                    .with_name("generate_months_input")
+                    .fixed_width(10))
+        )
+        .title("Generate Expected Income")
+        .button("Generate", move |s| {
+            let months_str = s.call_on_name("generate_months_input", |v: &mut EditView| v.get_content()).unwrap();
+            let months = match months_str.trim().parse::<u32>() {
+                Ok(m) => m,
+                Err(_) => {
+                    s.add_layer(Dialog::info("Months must be a whole number"));
+                    return;
+                }
+            };
+
+            let today = Local::now().date_naive();
+            let from = today.with_day(1).unwrap_or(today);
+            let through = from
+                .checked_add_months(Months::new(months + 1))
+                .and_then(|d| d.pred_opt())
+                .unwrap_or(today);
+
+            let source_repo = &repo.income_source;
+            let created: usize = source_repo.find_all()
+                .iter()
+                .filter(|src| src.is_active)
+                .map(|src| source_repo.generate_expected(src, from, through))
+                .sum();
+
+            s.pop_layer();
+            reload_income_table(s, &repo);
+            s.add_layer(Dialog::info(format!("Added {} expected incomes through {}", created, through.format("%d/%m/%Y"))));
+        })
+        .button("Cancel", |s| { s.pop_layer(); })
+    );
+}
+
+/// Gross pay and deductions behind an income's net amount
+fn paystub_form(siv: &mut Cursive, income: &IncomeDisplay, repos: &Rc<IncomeRepos>) {
+    let existing = repos.income.find_paystub(income.id);
+    let lines = existing.as_ref().map(income_logic::to_paystub_lines).unwrap_or_else(|| PaystubLines {
+        gross: income.amount.clone(),
+        ..PaystubLines::default()
+    });
+
+    // Gross first, then each deduction; empty fields count as zero
+    let mut form = ListView::new()
+        .child("Gross Pay", EditView::new().content(lines.gross.to_string()).with_name("paystub_line_0").fixed_width(20));
+    let zero = BigDecimal::from(0);
+    for (index, (label, amount)) in lines.deduction_lines().into_iter().enumerate() {
+        let value = if *amount == zero { String::new() } else { amount.to_string() };
+        form.add_child(label, EditView::new().content(value).with_name(format!("paystub_line_{}", index + 1)).fixed_width(20));
+    }
+
+    let income_id = income.id;
+    let income_date = income.date;
+    let net_amount = income.amount.clone();
+    let repo_save = Rc::clone(repos);
+    let repo_ytd = Rc::clone(repos);
+
+    let mut dialog = Dialog::around(
+        LinearLayout::vertical()
+            .child(TextView::new(format!("Net pay: ${}", income.amount)))
+            .child(form)
+    )
+    .title(format!("Paystub {}", income.date.format("%d/%m/%Y")))
+    .button("Save", move |s| {
+        let lines = match get_paystub_form_values(s) {
+            Ok(values) => values,
+            Err(message) => {
+                s.add_layer(Dialog::info(message));
+                return;
+            }
+        };
+
+        if lines.net() != net_amount {
+            s.add_layer(Dialog::info(format!(
+                "Gross less deductions is ${} but this income is ${}",
+                lines.net(),
+                net_amount
+            )));
+            return;
+        }
+
+        let saved = repo_save.income.save_paystub(models::NewPaystub {
+            income_id,
+            gross: lines.gross,
+            federal_tax: lines.federal_tax,
+            state_tax: lines.state_tax,
+            social_security: lines.social_security,
+            medicare: lines.medicare,
+            retirement_401k: lines.retirement_401k,
+            hsa: lines.hsa,
+            insurance: lines.insurance,
+            other_deductions: lines.other_deductions,
         });
+        if let Err(e) = saved {
+            s.add_layer(Dialog::info(e.to_string()));
+            return;
+        }
 
-        toggle_buttons_visible(siv, income_count, TOGGLE_BUTTONS);
+        s.pop_layer();
+    })
+    .button("Year to Date", move |s| show_paystub_ytd(s, income_date, &repo_ytd));
+
+    if existing.is_some() {
+        let repo_remove = Rc::clone(repos);
+        dialog.add_button("Remove", move |s| {
+            if let Err(e) = repo_remove.income.delete_paystub(income_id) {
+                s.add_layer(Dialog::info(e.to_string()));
+                return;
+            }
+            s.pop_layer();
+        });
+    }
+
+    siv.add_layer(dialog.button("Cancel", |s| { s.pop_layer(); }));
+}
+
+fn get_paystub_form_values(s: &mut Cursive) -> Result<PaystubLines, String> {
+    let mut values = Vec::new();
+    for index in 0..PAYSTUB_LINE_COUNT {
+        let value_str = s.call_on_name(&format!("paystub_line_{}", index), |v: &mut EditView| v.get_content()).unwrap();
+        let value = if value_str.trim().is_empty() {
+            BigDecimal::from(0)
+        } else {
+            BigDecimal::from_str(value_str.trim()).map_err(|_| format!("Invalid amount: {}", value_str))?
+        };
+        values.push(value);
+    }
+
+    let [gross, federal_tax, state_tax, social_security, medicare, retirement_401k, hsa, insurance, other_deductions]: [BigDecimal; PAYSTUB_LINE_COUNT] =
+        values.try_into().map_err(|_| "Missing paystub line".to_string())?;
+    Ok(PaystubLines {
+        gross,
+        federal_tax,
+        state_tax,
+        social_security,
+        medicare,
+        retirement_401k,
+        hsa,
+        insurance,
+        other_deductions,
+    })
+}
+
+/// Each paystub line totalled from January 1 through a paycheck's date
+fn show_paystub_ytd(siv: &mut Cursive, through: NaiveDate, repos: &Rc<IncomeRepos>) {
+    let year_start = NaiveDate::from_ymd_opt(through.year(), 1, 1).unwrap_or(through);
+    let paystubs: Vec<PaystubLines> = repos.income
+        .find_paystubs_between(year_start, through)
+        .iter()
+        .map(income_logic::to_paystub_lines)
+        .collect();
+
+    if paystubs.is_empty() {
+        siv.add_layer(Dialog::info("No paystubs recorded this year yet"));
+        return;
+    }
+
+    let totals = income_logic::paystub_totals(&paystubs);
+    let mut text = format!("{} paystubs through {}\n\n", paystubs.len(), through.format("%d/%m/%Y"));
+    let gross_str = format!("${}", totals.gross);
+    text.push_str(&format!("{:<18}{:>14}\n", "Gross Pay", gross_str));
+    for (label, amount) in totals.deduction_lines() {
+        let amount_str = format!("${}", amount);
+        text.push_str(&format!("{:<18}{:>14}\n", label, amount_str));
     }
+    let net_str = format!("${}", totals.net());
+    text.push_str("────────────────────────────────\n");
+    text.push_str(&format!("{:<18}{:>14}\n", "Net Pay", net_str));
+
+    siv.add_layer(
+        Dialog::around(TextView::new(text))
+            .title(format!("{} Year to Date", through.year()))
+            .button("Close", |s| { s.pop_layer(); })
+    );
+}
+
+/// Confirmed income for a year totalled by source and by category
+fn show_income_totals(siv: &mut Cursive, repos: &Rc<IncomeRepos>) {
+    let incomes: Vec<models::Income> = repos.income.find_all().into_iter().filter(|i| !i.is_expected).collect();
+    let sources = repos.income_source.find_all();
+
+    let mut years: Vec<i32> = incomes.iter().map(|i| i.date.year()).collect();
+    years.sort_unstable_by(|a, b| b.cmp(a));
+    years.dedup();
+
+    let Some(first_year) = years.first().copied() else {
+        siv.add_layer(Dialog::info("No confirmed income yet"));
+        return;
+    };
+
+    let initial_text = format_income_totals(&incomes, &sources, first_year);
+
+    let mut year_select = SelectView::new().popup();
+    for year in &years {
+        year_select.add_item(year.to_string(), *year);
+    }
+    let year_select = year_select.on_submit(move |s, year: &i32| {
+        let text = format_income_totals(&incomes, &sources, *year);
+        s.call_on_name("income_totals_text", |v: &mut TextView| v.set_content(text));
+    });
+
+    siv.add_layer(
+        Dialog::around(
+            LinearLayout::vertical()
+                .child(LinearLayout::horizontal()
+                    .child(TextView::new("Year: "))
+                    .child(year_select))
+                .child(TextView::new(initial_text).with_name("income_totals_text").scrollable())
+        )
+        .title("Income Totals")
+        .button("Close", |s| { s.pop_layer(); })
+    );
+}
+
+fn format_income_totals(incomes: &[models::Income], sources: &[models::IncomeSource], year: i32) -> String {
+    let year_incomes: Vec<&models::Income> = incomes.iter().filter(|i| i.date.year() == year).collect();
+
+    let by_source: Vec<(Option<i32>, BigDecimal)> = year_incomes
+        .iter()
+        .map(|i| (i.income_source_id, i.amount.clone()))
+        .collect();
+    let by_category: Vec<(String, BigDecimal)> = year_incomes
+        .iter()
+        .map(|i| {
+            let source_category = i.income_source_id
+                .and_then(|sid| sources.iter().find(|src| src.id == sid))
+                .map(|src| src.category.as_str());
+            let category = income_logic::income_category(i.category.as_deref(), source_category)
+                .map_or("Uncategorized".to_string(), |c| c.to_string());
+            (category, i.amount.clone())
+        })
+        .collect();
+
+    let mut text = format!("{:<24}{:>8}{:>14}\n", "SOURCE", "COUNT", "TOTAL");
+    for (source_id, total, count) in income_logic::totals_by(&by_source) {
+        let name = source_id
+            .and_then(|sid| sources.iter().find(|src| src.id == sid))
+            .map_or("(No Source)".to_string(), |src| src.name.clone());
+        let total_str = format!("${}", total);
+        text.push_str(&format!("{:<24}{:>8}{:>14}\n", name, count, total_str));
+    }
+
+    text.push_str(&format!("\n{:<24}{:>8}{:>14}\n", "CATEGORY", "COUNT", "TOTAL"));
+    for (category, total, count) in income_logic::totals_by(&by_category) {
+        let total_str = format!("${}", total);
+        text.push_str(&format!("{:<24}{:>8}{:>14}\n", category, count, total_str));
+    }
+
+    let year_total: BigDecimal = year_incomes.iter().map(|i| i.amount.clone()).sum();
+    text.push_str(&format!("\nTotal {}: ${}", year, year_total));
+    text
 }
@@ -3,21 +3,30 @@ use std::rc::Rc;
 use bigdecimal::BigDecimal;
 use chrono::{Datelike, NaiveDate};
 use cursive::Cursive;
+use cursive::theme::{BaseColor, Color};
 use cursive::traits::*;
+use cursive::utils::markup::StyledString;
 use cursive::views::{Button, Checkbox, Dialog, EditView, HideableView, LinearLayout, ListView, Panel, SelectView, TextArea, TextView};
 use cursive_table_view::{TableView, TableViewItem};
 use diesel::prelude::*;
 
 use crate::models;
 use crate::schema;
-use crate::repositories::ledger_repo::LedgerRepo;
+use crate::dependency_container::LedgerRepos;
 use crate::ui_helpers::toggle_buttons_visible;
 
 // Button name constants
 const BILL_EDIT_BUTTON: &str = "ledger_bill_edit_button";
 const BILL_TOGGLE_PAID_BUTTON: &str = "ledger_bill_toggle_paid_button";
 const BILL_DELETE_BUTTON: &str = "ledger_bill_delete_button";
-const BILL_TOGGLE_BUTTONS: &[&str] = &[BILL_EDIT_BUTTON, BILL_TOGGLE_PAID_BUTTON, BILL_DELETE_BUTTON];
+const BILL_ALLOCATE_BUTTON: &str = "ledger_bill_allocate_button";
+const BILL_PAYMENTS_BUTTON: &str = "ledger_bill_payments_button";
+const BILL_TOGGLE_BUTTONS: &[&str] = &[BILL_EDIT_BUTTON, BILL_TOGGLE_PAID_BUTTON, BILL_DELETE_BUTTON, BILL_ALLOCATE_BUTTON, BILL_PAYMENTS_BUTTON];
+
+const SAVINGS_TRANSFERRED_BUTTON: &str = "ledger_savings_transferred_button";
+const SAVINGS_EDIT_BUTTON: &str = "ledger_savings_edit_button";
+const SAVINGS_REMOVE_BUTTON: &str = "ledger_savings_remove_button";
+const SAVINGS_TOGGLE_BUTTONS: &[&str] = &[SAVINGS_TRANSFERRED_BUTTON, SAVINGS_EDIT_BUTTON, SAVINGS_REMOVE_BUTTON];
 
 const INCOME_DELETE_BUTTON: &str = "ledger_income_delete_button";
 const INCOME_TOGGLE_BUTTONS: &[&str] = &[INCOME_DELETE_BUTTON];
@@ -28,12 +37,14 @@ enum BillColumn {
     Amount,
     DueDay,
     Paid,
+    Paycheck,
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, Hash)]
 enum IncomeColumn {
     Date,
     Amount,
+    Remaining,
 }
 
 #[derive(Clone, Debug)]
@@ -44,7 +55,12 @@ struct LedgerBillDisplay {
     amount: BigDecimal,
     due_day: String,
     is_payed: bool,
+    paid_amount: BigDecimal,
     notes: Option<String>,
+    income_id: Option<i32>,
+    paycheck: String,
+    category_id: Option<i32>,
+    envelope_id: Option<i32>,
 }
 
 #[derive(Clone, Debug)]
@@ -52,6 +68,8 @@ struct IncomeDisplay {
     id: i32,
     date: String,
     amount: BigDecimal,
+    remaining: BigDecimal,
+    is_expected: bool,
 }
 
 impl TableViewItem<BillColumn> for LedgerBillDisplay {
@@ -60,7 +78,14 @@ impl TableViewItem<BillColumn> for LedgerBillDisplay {
             BillColumn::Name => self.bill_name.clone(),
             BillColumn::Amount => format!("${}", self.amount),
             BillColumn::DueDay => self.due_day.clone(),
-            BillColumn::Paid => if self.is_payed { "✓" } else { "" }.to_string(),
+            BillColumn::Paid => if self.is_payed {
+                "✓".to_string()
+            } else if self.paid_amount > BigDecimal::from(0) {
+                format!("${}", self.paid_amount)
+            } else {
+                String::new()
+            },
+            BillColumn::Paycheck => self.paycheck.clone(),
         }
     }
 
@@ -69,7 +94,8 @@ impl TableViewItem<BillColumn> for LedgerBillDisplay {
             BillColumn::Name => self.bill_name.cmp(&other.bill_name),
             BillColumn::Amount => self.amount.cmp(&other.amount),
             BillColumn::DueDay => self.due_day.cmp(&other.due_day),
-            BillColumn::Paid => self.is_payed.cmp(&other.is_payed),
+            BillColumn::Paid => self.is_payed.cmp(&other.is_payed).then(self.paid_amount.cmp(&other.paid_amount)),
+            BillColumn::Paycheck => self.paycheck.cmp(&other.paycheck),
         }
     }
 }
@@ -78,7 +104,12 @@ impl TableViewItem<IncomeColumn> for IncomeDisplay {
     fn to_column(&self, column: IncomeColumn) -> String {
         match column {
             IncomeColumn::Date => self.date.clone(),
-            IncomeColumn::Amount => format!("${}", self.amount),
+            IncomeColumn::Amount => if self.is_expected {
+                format!("${} (expected)", self.amount)
+            } else {
+                format!("${}", self.amount)
+            },
+            IncomeColumn::Remaining => format!("${}", self.remaining),
         }
     }
 
@@ -86,14 +117,15 @@ impl TableViewItem<IncomeColumn> for IncomeDisplay {
         match column {
             IncomeColumn::Date => self.date.cmp(&other.date),
             IncomeColumn::Amount => self.amount.cmp(&other.amount),
+            IncomeColumn::Remaining => self.remaining.cmp(&other.remaining),
         }
     }
 }
 
-pub fn show_ledger_detail(siv: &mut Cursive, target_ledger_id: i32, ledger_repo: &Rc<LedgerRepo>) {
+pub fn show_ledger_detail(siv: &mut Cursive, target_ledger_id: i32, repos: &Rc<LedgerRepos>) {
     siv.pop_layer();
 
-    let ledger = match ledger_repo.find_by_id(target_ledger_id) {
+    let ledger = match repos.ledger.find_by_id(target_ledger_id) {
         Some(l) => l,
         None => {
             siv.add_layer(Dialog::info("Error loading ledger"));
@@ -102,7 +134,13 @@ pub fn show_ledger_detail(siv: &mut Cursive, target_ledger_id: i32, ledger_repo:
     };
 
     // Load ledger bills with bill names
-    let ledger_bill_data = ledger_repo.find_ledger_bills_with_bill_names(target_ledger_id);
+    let ledger_bill_data = repos.ledger.find_ledger_bills_with_bill_names(target_ledger_id);
+
+    // Load incomes for this ledger
+    let ledger_incomes = repos.ledger.find_incomes_by_ledger(target_ledger_id);
+
+    // Load payments recorded against this ledger's bills
+    let ledger_payments = repos.ledger.find_payments_by_ledger(target_ledger_id);
 
     let bill_displays: Vec<LedgerBillDisplay> = ledger_bill_data
         .into_iter()
@@ -110,15 +148,32 @@ pub fn show_ledger_detail(siv: &mut Cursive, target_ledger_id: i32, ledger_repo:
             id: lb.id,
             bill_id: b.id,
             bill_name: b.name,
+            category_id: b.category_id,
+            envelope_id: b.envelope_id,
             amount: lb.amount.clone(),
             due_day: lb.due_day.map_or("-".to_string(), |d| d.format("%d/%m").to_string()),
             is_payed: lb.is_payed,
+            paid_amount: {
+                let bill_payments: Vec<BigDecimal> = ledger_payments
+                    .iter()
+                    .filter(|p| p.ledger_bill_id == lb.id)
+                    .map(|p| p.amount.clone())
+                    .collect();
+                crate::ledger_logic::calculate_bill_paid_amount(&lb.amount, lb.is_payed, &bill_payments)
+            },
             notes: lb.notes,
+            income_id: lb.income_id,
+            paycheck: lb.income_id
+                .and_then(|iid| ledger_incomes.iter().find(|i| i.id == iid))
+                .map_or("-".to_string(), |i| i.date.format("%d/%m").to_string()),
         })
         .collect();
 
-    // Load incomes for this ledger
-    let ledger_incomes = ledger_repo.find_incomes_by_ledger(target_ledger_id);
+    // Amounts allocated to each paycheck
+    let allocations: Vec<(Option<i32>, BigDecimal)> = bill_displays
+        .iter()
+        .map(|b| (b.income_id, b.amount.clone()))
+        .collect();
 
     let income_displays: Vec<IncomeDisplay> = ledger_incomes
         .iter()
@@ -126,6 +181,8 @@ pub fn show_ledger_detail(siv: &mut Cursive, target_ledger_id: i32, ledger_repo:
             id: i.id,
             date: i.date.format("%d/%m/%Y").to_string(),
             amount: i.amount.clone(),
+            remaining: crate::ledger_logic::calculate_income_remaining(i.id, &i.amount, &allocations),
+            is_expected: i.is_expected,
         })
         .collect();
 
@@ -134,12 +191,10 @@ pub fn show_ledger_detail(siv: &mut Cursive, target_ledger_id: i32, ledger_repo:
         .map(|b| &b.amount)
         .sum();
     let paid_bills_amount: BigDecimal = bill_displays.iter()
-        .filter(|b| b.is_payed)
-        .map(|b| &b.amount)
+        .map(|b| &b.paid_amount)
         .sum();
     let unpaid_bills_amount: BigDecimal = bill_displays.iter()
-        .filter(|b| !b.is_payed)
-        .map(|b| &b.amount)
+        .map(|b| crate::ledger_logic::calculate_bill_remaining(&b.amount, &b.paid_amount))
         .sum();
     let paid_bills_count = bill_displays.iter().filter(|b| b.is_payed).count();
     let unpaid_bills_count = bill_displays.iter().filter(|b| !b.is_payed).count();
@@ -147,20 +202,74 @@ pub fn show_ledger_detail(siv: &mut Cursive, target_ledger_id: i32, ledger_repo:
 
     let income_count = income_displays.len();
 
+    // Budget vs. actual per category
+    let categories = repos.category.find_all();
+    let budget_items: Vec<(Option<i32>, BigDecimal, BigDecimal)> = bill_displays
+        .iter()
+        .map(|b| (b.category_id, b.amount.clone(), b.paid_amount.clone()))
+        .collect();
+    let budget_rows = crate::budget_logic::summarize_category_budgets(
+        &crate::category_table::to_category_nodes(&categories),
+        &budget_items
+    );
+
+    // Savings set-asides for this ledger
+    let savings_goal_repo = &repos.savings_goal;
+    let savings_goals = savings_goal_repo.find_all();
+    let contributions = savings_goal_repo.find_contributions_by_ledger(target_ledger_id);
+    let contribution_count = contributions.len();
+    let savings_total: BigDecimal = contributions.iter().map(|c| c.amount.clone()).sum();
+    let savings_section = if contributions.is_empty() {
+        String::new()
+    } else {
+        format!("Savings Set-Asides: ${}\n", savings_total)
+    };
+
+    // Zero-based budgeting: funds not yet given to an envelope or an unenveloped bill
+    let envelope_repo = &repos.envelope;
+    let has_envelopes = !envelope_repo.find_all().is_empty();
+    let to_be_budgeted_section = if has_envelopes {
+        let envelope_balances = envelope_repo.find_balances(&ledger_chain_ids(target_ledger_id, repos));
+        let unenveloped_spending: Vec<BigDecimal> = bill_displays
+            .iter()
+            .filter(|b| b.envelope_id.is_none())
+            .map(|b| b.amount.clone())
+            .chain(contributions.iter().map(|c| c.amount.clone()))
+            .collect();
+        let to_be_budgeted = crate::budget_logic::calculate_to_be_budgeted(
+            &ledger.total.clone().unwrap_or(BigDecimal::from(0)),
+            &envelope_balances,
+            &unenveloped_spending
+        );
+        format!("To Be Budgeted: ${}\n", to_be_budgeted)
+    } else {
+        String::new()
+    };
+
+    // Installment loans among this ledger's bills
+    let loans = repos.loan.find_all();
+    let ledger_loans: Vec<(String, &models::Loan)> = bill_displays
+        .iter()
+        .filter_map(|b| loans.iter().find(|l| l.bill_id == b.bill_id).map(|l| (b.bill_name.clone(), l)))
+        .collect();
+    let loan_text = format_loan_rows(ledger.date, &ledger_loans);
+
     // Create bills table
     let bills_table = TableView::<LedgerBillDisplay, BillColumn>::new()
         .column(BillColumn::Name, "Bill", |c| c)
         .column(BillColumn::Amount, "Amount", |c| c.width(16))
         .column(BillColumn::DueDay, "Due", |c| c.width(10))
         .column(BillColumn::Paid, "✓", |c| c.width(6))
+        .column(BillColumn::Paycheck, "Paycheck", |c| c.width(10))
         .items(bill_displays)
         .with_name("bills_table")
         .min_size((40, 15));
 
     // Create income table
     let income_table = TableView::<IncomeDisplay, IncomeColumn>::new()
-        .column(IncomeColumn::Date, "Date", |c| c.width_percent(50))
-        .column(IncomeColumn::Amount, "Amount", |c| c.width_percent(50))
+        .column(IncomeColumn::Date, "Date", |c| c.width_percent(34))
+        .column(IncomeColumn::Amount, "Amount", |c| c.width_percent(33))
+        .column(IncomeColumn::Remaining, "Remaining", |c| c.width_percent(33))
         .items(income_displays)
         .with_name("income_table")
         .min_height(5)
@@ -177,34 +286,114 @@ pub fn show_ledger_detail(siv: &mut Cursive, target_ledger_id: i32, ledger_repo:
         String::new()
     };
     
+    // Most recent reconciliation against the real bank balance
+    let reconciliation_section = match repos.ledger.find_reconciliations_by_ledger(target_ledger_id).first() {
+        Some(r) => format!(
+            "\nLast Reconciled: {} (Difference: ${})",
+            r.date.format("%d/%m/%Y"),
+            r.delta.clone().unwrap_or(BigDecimal::from(0))
+        ),
+        None => String::new(),
+    };
+
+    // Opening balance chain
+    let successors = repos.ledger.find_successors(target_ledger_id);
+    let has_successors = !successors.is_empty();
+    let closing_balance = repos.ledger.closing_balance(target_ledger_id);
+    let carry_section = match successors.first() {
+        Some(next) if next.bank_balance != closing_balance => format!(
+            "\nNext ledger '{}' opens at ${} (out of date)",
+            next.name.clone().unwrap_or_default(),
+            next.bank_balance
+        ),
+        _ => String::new(),
+    };
+
+    let status_section = match ledger.closed_at {
+        Some(closed_at) if ledger.is_closed => format!("Status: CLOSED ({})\n\n", closed_at.format("%d/%m/%Y")),
+        _ if ledger.is_closed => "Status: CLOSED\n\n".to_string(),
+        _ => String::new(),
+    };
+
+    // Account this ledger draws from, and each account's net when it's split across several
+    let accounts = repos.account.find_all();
+    let account_section = ledger.account_id
+        .and_then(|aid| accounts.iter().find(|a| a.id == aid))
+        .map_or(String::new(), |a| format!("Account: {}\n", a.name));
+    let account_nets = repos.ledger.find_account_nets(target_ledger_id);
+    let account_nets_section = if account_nets.len() > 1 {
+        let mut section = String::from("\nACCOUNT           NET\n");
+        for account_net in &account_nets {
+            let net_str = format!("${}", account_net.net);
+            section.push_str(&format!("{:<16}{:>14}\n", account_name(&accounts, account_net.account_id), net_str));
+        }
+        section
+    } else {
+        String::new()
+    };
+
+    // Income split by source once any of it is tagged; income without a source groups by its own category
+    let income_sources_section = if ledger_incomes.iter().any(|i| i.income_source_id.is_some() || i.category.is_some()) {
+        let sources = repos.income_source.find_all();
+        let by_source: Vec<(String, BigDecimal)> = ledger_incomes
+            .iter()
+            .map(|i| {
+                let name = i.income_source_id
+                    .and_then(|sid| sources.iter().find(|src| src.id == sid))
+                    .map(|src| src.name.clone())
+                    .or_else(|| i.category.as_ref().map(|c| format!("({})", crate::models::IncomeCategory::from(c.clone()))))
+                    .unwrap_or_else(|| "(No Source)".to_string());
+                (name, i.amount.clone())
+            })
+            .collect();
+        let mut section = String::new();
+        for (name, total, _) in crate::income_logic::totals_by(&by_source) {
+            let total_str = format!("${}", total);
+            section.push_str(&format!("  {:<18}{:>12}\n", name, total_str));
+        }
+        section
+    } else {
+        String::new()
+    };
+
     let summary_text = format!(
-        "Bank Balance: ${}\n\
+        "{}{}Bank Balance: ${}\n\
          Income: ${} ({} items)\n\
-         Available Funds: ${}\n\n\
+         {}Available Funds: ${}\n\
+         {}\n\
          BILLS         PLANNED     PAID\n\
          ─────────────────────────────\n\
          Amount        ${}    ${}\n\
          Count         {}          {}\n\n\
+         {}\
          Total Expenses: ${}\n\n\
          ───────────────────────────────\n\
          Net: ${}\n\
-         {}",
+         {}{}{}{}",
+        status_section,
+        account_section,
         ledger.bank_balance,
         ledger.income,
         income_count,
+        income_sources_section,
         ledger.total.unwrap_or(BigDecimal::from(0)),
+        to_be_budgeted_section,
         unpaid_bills_amount,
         paid_bills_amount,
         unpaid_bills_count,
         paid_bills_count,
+        savings_section,
         ledger.expenses,
         ledger.net.unwrap_or(BigDecimal::from(0)),
-        notes_section
+        account_nets_section,
+        notes_section,
+        reconciliation_section,
+        carry_section
     );
 
     // Create income section with buttons
-    let repo_add_income = Rc::clone(ledger_repo);
-    let repo_delete_income = Rc::clone(ledger_repo);
+    let repo_add_income = Rc::clone(repos);
+    let repo_delete_income = Rc::clone(repos);
     let income_buttons = LinearLayout::horizontal()
         .child(Button::new("Add", move |s| add_income_to_ledger(s, target_ledger_id, &repo_add_income)))
         .child(HideableView::new(Button::new("Delete", move |s| delete_income_from_ledger(s, target_ledger_id, &repo_delete_income))).with_name(INCOME_DELETE_BUTTON));
@@ -215,30 +404,72 @@ pub fn show_ledger_detail(siv: &mut Cursive, target_ledger_id: i32, ledger_repo:
         .min_height(6);
 
     // Create bills section with buttons
-    let repo_add_bill = Rc::clone(ledger_repo);
-    let repo_edit_bill = Rc::clone(ledger_repo);
-    let repo_toggle_bill = Rc::clone(ledger_repo);
-    let repo_delete_bill = Rc::clone(ledger_repo);
+    let repo_add_bill = Rc::clone(repos);
+    let repo_edit_bill = Rc::clone(repos);
+    let repo_toggle_bill = Rc::clone(repos);
+    let repo_delete_bill = Rc::clone(repos);
+    let repo_allocate_bill = Rc::clone(repos);
+    let repo_auto_allocate = Rc::clone(repos);
+    let repo_payments = Rc::clone(repos);
     let bill_buttons = LinearLayout::horizontal()
         .child(Button::new("Add", move |s| add_bill_to_ledger(s, target_ledger_id, &repo_add_bill)))
         .child(HideableView::new(Button::new("Edit", move |s| edit_ledger_bill(s, target_ledger_id, &repo_edit_bill))).with_name(BILL_EDIT_BUTTON))
         .child(HideableView::new(Button::new("Toggle Paid", move |s| toggle_bill_paid(s, target_ledger_id, &repo_toggle_bill))).with_name(BILL_TOGGLE_PAID_BUTTON))
-        .child(HideableView::new(Button::new("Delete", move |s| delete_bill_from_ledger(s, target_ledger_id, &repo_delete_bill))).with_name(BILL_DELETE_BUTTON));
+        .child(HideableView::new(Button::new("Payments", move |s| {
+            let selected = s.call_on_name("bills_table", |v: &mut TableView<LedgerBillDisplay, BillColumn>| {
+                v.borrow_item(v.item().unwrap()).cloned()
+            }).flatten();
+
+            if let Some(bill) = selected {
+                show_bill_payments(s, target_ledger_id, bill.id, &bill.bill_name, &repo_payments);
+            }
+        })).with_name(BILL_PAYMENTS_BUTTON))
+        .child(HideableView::new(Button::new("Delete", move |s| delete_bill_from_ledger(s, target_ledger_id, &repo_delete_bill))).with_name(BILL_DELETE_BUTTON))
+        .child(HideableView::new(Button::new("Allocate", move |s| allocate_bill_to_paycheck(s, target_ledger_id, &repo_allocate_bill))).with_name(BILL_ALLOCATE_BUTTON))
+        .child(Button::new("Auto-Allocate", move |s| auto_allocate_bills(s, target_ledger_id, &repo_auto_allocate)));
 
     let bills_section = LinearLayout::vertical()
         .child(bills_table)
         .child(bill_buttons);
 
     // Create summary section with update button
-    let repo_update = Rc::clone(ledger_repo);
+    let repo_update = Rc::clone(repos);
+    let repo_timeline = Rc::clone(repos);
+    let repo_reconcile = Rc::clone(repos);
+    let repo_history = Rc::clone(repos);
+    let repo_close = Rc::clone(repos);
+    let repo_envelopes = Rc::clone(repos);
+    let repo_accounts = Rc::clone(repos);
+    let is_closed = ledger.is_closed;
+    let mut summary_buttons = LinearLayout::horizontal()
+        .child(Button::new("Edit", move |s| update_ledger(s, target_ledger_id, &repo_update)))
+        .child(Button::new("Timeline", move |s| show_cash_flow_timeline(s, target_ledger_id, &repo_timeline)))
+        .child(Button::new("Reconcile", move |s| reconcile_ledger(s, target_ledger_id, &repo_reconcile)))
+        .child(Button::new("History", move |s| show_reconciliation_history(s, target_ledger_id, &repo_history)))
+        .child(Button::new("Envelopes", move |s| show_envelopes(s, target_ledger_id, &repo_envelopes)))
+        .child(Button::new("Accounts", move |s| show_ledger_accounts(s, target_ledger_id, &repo_accounts)))
+        .child(Button::new(if is_closed { "Reopen" } else { "Close" }, move |s| toggle_ledger_closed(s, target_ledger_id, is_closed, &repo_close)));
+    if has_successors {
+        let repo_ripple = Rc::clone(repos);
+        summary_buttons.add_child(Button::new("Ripple Forward", move |s| ripple_opening_balances(s, target_ledger_id, &repo_ripple)));
+    }
     let summary_content = LinearLayout::vertical()
         .child(TextView::new(summary_text))
-        .child(Button::new("Edit", move |s| update_ledger(s, target_ledger_id, &repo_update)));
+        .child(summary_buttons);
 
     // Stack income and summary vertically in right column
-    let right_column = LinearLayout::vertical()
+    let mut right_column = LinearLayout::vertical()
         .child(Panel::new(income_section).title("Incomes"))
         .child(Panel::new(summary_content).title("Summary"));
+    if !categories.is_empty() {
+        right_column.add_child(Panel::new(TextView::new(format_budget_rows(&budget_rows))).title("Budget"));
+    }
+    if !ledger_loans.is_empty() {
+        right_column.add_child(Panel::new(TextView::new(loan_text)).title("Loans"));
+    }
+    if !savings_goals.is_empty() || !contributions.is_empty() {
+        right_column.add_child(Panel::new(build_savings_section(target_ledger_id, &savings_goals, &contributions, repos)).title("Savings"));
+    }
 
     // Create two-column layout: Bills | (Incomes + Summary)
     let content = LinearLayout::horizontal()
@@ -258,11 +489,191 @@ pub fn show_ledger_detail(siv: &mut Cursive, target_ledger_id: i32, ledger_repo:
     // Toggle button visibility based on item counts
     toggle_buttons_visible(siv, total_bills, BILL_TOGGLE_BUTTONS);
     toggle_buttons_visible(siv, income_count, INCOME_TOGGLE_BUTTONS);
+    toggle_buttons_visible(siv, contribution_count, SAVINGS_TOGGLE_BUTTONS);
+}
+
+/// Contribution lines for this ledger with buttons to manage them
+fn build_savings_section(
+    ledger_id: i32,
+    savings_goals: &[models::SavingsGoal],
+    contributions: &[models::LedgerSavingsContribution],
+    repos: &Rc<LedgerRepos>,
+) -> LinearLayout {
+    let mut select = SelectView::<i32>::new();
+    for contribution in contributions {
+        let goal_name = savings_goals
+            .iter()
+            .find(|g| g.id == contribution.savings_goal_id)
+            .map_or("-".to_string(), |g| g.name.clone());
+        let amount = format!("${}", contribution.amount);
+        select.add_item(
+            format!("{:<20}{:>12} {}", goal_name, amount, if contribution.is_transferred { "✓" } else { "" }),
+            contribution.id
+        );
+    }
+
+    let repo_toggle = Rc::clone(repos);
+    let repo_edit = Rc::clone(repos);
+    let repo_remove = Rc::clone(repos);
+    let repo_generate = Rc::clone(repos);
+    let buttons = LinearLayout::horizontal()
+        .child(Button::new("Generate", move |s| {
+            let ledger = match repo_generate.ledger.find_by_id(ledger_id) {
+                Some(l) => l,
+                None => return,
+            };
+            if let Err(e) = repo_generate.savings_goal.generate_contributions(ledger_id, ledger.date) {
+                s.add_layer(Dialog::info(e.to_string()));
+                return;
+            }
+            show_ledger_detail(s, ledger_id, &repo_generate);
+        }))
+        .child(HideableView::new(Button::new("Transferred", move |s| {
+            let selected = s.call_on_name("savings_select", |v: &mut SelectView<i32>| {
+                v.selection().map(|c| *c)
+            }).flatten();
+
+            if let Some(contribution_id) = selected {
+                let savings_goal_repo = &repo_toggle.savings_goal;
+                let is_transferred = savings_goal_repo.find_contributions_by_ledger(ledger_id)
+                    .into_iter()
+                    .find(|c| c.id == contribution_id)
+                    .is_some_and(|c| c.is_transferred);
+                if let Err(e) = savings_goal_repo.set_contribution_transferred(contribution_id, !is_transferred) {
+                    s.add_layer(Dialog::info(e.to_string()));
+                    return;
+                }
+                show_ledger_detail(s, ledger_id, &repo_toggle);
+            }
+        })).with_name(SAVINGS_TRANSFERRED_BUTTON))
+        .child(HideableView::new(Button::new("Edit", move |s| {
+            let selected = s.call_on_name("savings_select", |v: &mut SelectView<i32>| {
+                v.selection().map(|c| *c)
+            }).flatten();
+
+            if let Some(contribution_id) = selected {
+                edit_savings_contribution(s, ledger_id, contribution_id, &repo_edit);
+            }
+        })).with_name(SAVINGS_EDIT_BUTTON))
+        .child(HideableView::new(Button::new("Remove", move |s| {
+            let selected = s.call_on_name("savings_select", |v: &mut SelectView<i32>| {
+                v.selection().map(|c| *c)
+            }).flatten();
+
+            if let Some(contribution_id) = selected {
+                if let Err(e) = repo_remove.savings_goal.delete_contribution(contribution_id) {
+                    s.add_layer(Dialog::info(e.to_string()));
+                    return;
+                }
+                show_ledger_detail(s, ledger_id, &repo_remove);
+            }
+        })).with_name(SAVINGS_REMOVE_BUTTON));
+
+    LinearLayout::vertical()
+        .child(select.with_name("savings_select").scrollable().min_height(2))
+        .child(buttons)
 }
 
-fn add_income_to_ledger(siv: &mut Cursive, ledger_id: i32, ledger_repo: &Rc<LedgerRepo>) {
-    let pg_connector = ledger_repo.pg_connector();
-    let (ledger_month, ledger_year, month_incomes) = {
+fn edit_savings_contribution(siv: &mut Cursive, ledger_id: i32, contribution_id: i32, repos: &Rc<LedgerRepos>) {
+    let current = repos.savings_goal
+        .find_contributions_by_ledger(ledger_id)
+        .into_iter()
+        .find(|c| c.id == contribution_id)
+        .map_or(BigDecimal::from(0), |c| c.amount);
+
+    let repo_save = Rc::clone(repos);
+    siv.add_layer(
+        Dialog::around(
+            ListView::new()
+                .child("Amount", EditView::new()
+                    .content(current.to_string())
+                    .with_name("savings_amount_input")
+                    .fixed_width(20))
+        )
+        .title("Edit Set-Aside")
+        .button("Save", move |s| {
+            let amount_str = s.call_on_name("savings_amount_input", |v: &mut EditView| {
+                v.get_content()
+            }).unwrap();
+
+            let amount = match amount_str.to_string().parse::<BigDecimal>() {
+                Ok(a) => a,
+                Err(_) => {
+                    s.add_layer(Dialog::info("Invalid amount format"));
+                    return;
+                }
+            };
+
+            if let Err(e) = repo_save.savings_goal.update_contribution(contribution_id, amount) {
+                s.add_layer(Dialog::info(e.to_string()));
+                return;
+            }
+
+            s.pop_layer(); // Close dialog
+            show_ledger_detail(s, ledger_id, &repo_save); // Refresh view
+        })
+        .button("Cancel", |s| { s.pop_layer(); })
+    );
+}
+
+/// This month's installment split and the balance left for each loan bill
+fn format_loan_rows(ledger_date: NaiveDate, loans: &[(String, &models::Loan)]) -> String {
+    let mut text = String::new();
+    for (bill_name, loan) in loans {
+        let schedule = crate::debt_logic::loan_schedule(loan);
+        let balance = crate::debt_logic::remaining_balance(&loan.principal, &schedule, ledger_date);
+
+        match crate::debt_logic::payment_in_month(&schedule, ledger_date) {
+            Some(row) => text.push_str(&format!(
+                "{} (payment {} of {})\n  Interest ${}  Principal ${}\n  Balance ${} → ${}\n",
+                bill_name,
+                row.number,
+                loan.term_months,
+                row.interest,
+                row.principal,
+                balance,
+                row.balance
+            )),
+            None if crate::debt_logic::is_loan_finished(&schedule, ledger_date) => {
+                text.push_str(&format!("{}: paid off\n", bill_name));
+            }
+            None => text.push_str(&format!("{}: no payment due, balance ${}\n", bill_name, balance)),
+        }
+    }
+    text
+}
+
+/// Render budget rows as a small text table, flagging categories over budget
+fn format_budget_rows(rows: &[crate::budget_logic::CategoryBudgetRow]) -> StyledString {
+    let mut text = StyledString::plain(format!(
+        "{:<18} {:>10} {:>10} {:>10} {:>10}\n",
+        "CATEGORY", "PLANNED", "PAID", "BUDGET", "LEFT"
+    ));
+
+    for row in rows {
+        let name = format!("{}{}", "  ".repeat(row.depth), row.name);
+        let budget = row.budget.as_ref().map_or("-".to_string(), |b| format!("${}", b));
+        let remaining = row.remaining.as_ref().map_or("-".to_string(), |r| format!("${}", r));
+        let planned = format!("${}", row.planned);
+        let paid = format!("${}", row.paid);
+        let line = format!(
+            "{:<18} {:>10} {:>10} {:>10} {:>10}\n",
+            name, planned, paid, budget, remaining
+        );
+
+        if row.remaining.as_ref().is_some_and(|r| *r < BigDecimal::from(0)) {
+            text.append_styled(line, Color::Dark(BaseColor::Red));
+        } else {
+            text.append_plain(line);
+        }
+    }
+
+    text
+}
+
+fn add_income_to_ledger(siv: &mut Cursive, ledger_id: i32, repos: &Rc<LedgerRepos>) {
+    let pg_connector = repos.ledger.pg_connector();
+    let month_incomes = {
         let mut conn = pg_connector.get_connection();
 
         // Get ledger to find its month
@@ -285,7 +696,7 @@ fn add_income_to_ledger(siv: &mut Cursive, ledger_id: i32, ledger_repo: &Rc<Ledg
             .filter(|i| i.date.month() == month && i.date.year() == year)
             .collect();
 
-        (month, year, incomes)
+        incomes
     };
 
     if month_incomes.is_empty() {
@@ -295,11 +706,16 @@ fn add_income_to_ledger(siv: &mut Cursive, ledger_id: i32, ledger_repo: &Rc<Ledg
 
     let mut select = SelectView::new();
     for income in month_incomes {
-        let label = format!("{} - ${}", income.date.format("%d/%m/%Y"), income.amount);
+        let label = format!(
+            "{} - ${}{}",
+            income.date.format("%d/%m/%Y"),
+            income.amount,
+            if income.is_expected { " (expected)" } else { "" }
+        );
         select.add_item(label, income.id);
     }
 
-    let repo_add = Rc::clone(ledger_repo);
+    let repo_add = Rc::clone(repos);
     siv.add_layer(
         Dialog::around(select.with_name("income_select"))
             .title("Select Income to Add")
@@ -309,15 +725,11 @@ fn add_income_to_ledger(siv: &mut Cursive, ledger_id: i32, ledger_repo: &Rc<Ledg
                 }).unwrap();
 
                 if let Some(selected_id) = income_id {
-                    {
-                        let pg_connector = repo_add.pg_connector();
-                        let mut conn = pg_connector.get_connection();
-
-                        // Assign income to ledger
-                        diesel::update(schema::incomes::table.find(*selected_id))
-                            .set(schema::incomes::ledger_id.eq(ledger_id))
-                            .execute(&mut *conn)
-                            .expect("Error assigning income");
+                    // Assign income to ledger
+                    if let Err(e) = repo_add.ledger.assign_income_to_ledger(*selected_id, ledger_id) {
+                        s.pop_layer();
+                        s.add_layer(Dialog::info(e.to_string()));
+                        return;
                     }
 
                     s.pop_layer(); // Close dialog
@@ -328,17 +740,21 @@ fn add_income_to_ledger(siv: &mut Cursive, ledger_id: i32, ledger_repo: &Rc<Ledg
     );
 }
 
-fn delete_income_from_ledger(siv: &mut Cursive, ledger_id: i32, ledger_repo: &Rc<LedgerRepo>) {
+fn delete_income_from_ledger(siv: &mut Cursive, ledger_id: i32, repos: &Rc<LedgerRepos>) {
     let selected = siv.call_on_name("income_table", |v: &mut TableView<IncomeDisplay, IncomeColumn>| {
         v.borrow_item(v.item().unwrap()).cloned()
     }).flatten();
 
     if let Some(income) = selected {
-        let repo_yes = Rc::clone(ledger_repo);
+        let repo_yes = Rc::clone(repos);
         siv.add_layer(
             Dialog::text("Remove this income from ledger?")
                 .button("Yes", move |s| {
-                    repo_yes.unassign_income_from_ledger(income.id);
+                    if let Err(e) = repo_yes.ledger.unassign_income_from_ledger(income.id) {
+                        s.pop_layer();
+                        s.add_layer(Dialog::info(e.to_string()));
+                        return;
+                    }
 
                     s.pop_layer(); // Close dialog
                     show_ledger_detail(s, ledger_id, &repo_yes); // Refresh view
@@ -348,9 +764,18 @@ fn delete_income_from_ledger(siv: &mut Cursive, ledger_id: i32, ledger_repo: &Rc
     }
 }
 
-fn add_bill_to_ledger(siv: &mut Cursive, ledger_id: i32, ledger_repo: &Rc<LedgerRepo>) {
-    let pg_connector = ledger_repo.pg_connector();
-    let (ledger_month, ledger_year, available_bills) = {
+/// A catalog bill picked to add to a ledger, due on its day in the ledger's month
+#[derive(Clone)]
+struct BillToAdd {
+    bill_id: i32,
+    catalog_amount: BigDecimal,
+    ledger_due_day: Option<NaiveDate>,
+    autopay: bool,
+}
+
+fn add_bill_to_ledger(siv: &mut Cursive, ledger_id: i32, repos: &Rc<LedgerRepos>) {
+    let pg_connector = repos.ledger.pg_connector();
+    let (ledger_date, ledger_month, ledger_year, available_bills) = {
         let mut conn = pg_connector.get_connection();
 
         // Get ledger to find its month
@@ -375,9 +800,36 @@ fn add_bill_to_ledger(siv: &mut Cursive, ledger_id: i32, ledger_repo: &Rc<Ledger
 
         bills.retain(|b| !existing_bill_ids.contains(&b.id));
 
-        (month, year, bills)
+        (ledger.date, month, year, bills)
     };
 
+    // Installment bills are added at their scheduled payment and drop off once the loan is paid
+    // Credit card bills are added at the statement balance due this month
+    let loans = repos.loan.find_all();
+    let card_repo = &repos.credit_card;
+    let cards = card_repo.find_all();
+    let mut scheduled_bill_ids: Vec<i32> = Vec::new();
+    let available_bills: Vec<models::Bill> = available_bills
+        .into_iter()
+        .filter_map(|mut bill| {
+            if let Some(loan) = loans.iter().find(|l| l.bill_id == bill.id) {
+                let schedule = crate::debt_logic::loan_schedule(loan);
+                if crate::debt_logic::is_loan_finished(&schedule, ledger_date) {
+                    return None;
+                }
+                if let Some(row) = crate::debt_logic::payment_in_month(&schedule, ledger_date) {
+                    bill.amount = row.payment.clone();
+                }
+                scheduled_bill_ids.push(bill.id);
+            }
+            if let Some(card) = cards.iter().find(|c| c.bill_id == Some(bill.id)) {
+                bill.amount = card_repo.statement_amount(card, ledger_date);
+                scheduled_bill_ids.push(bill.id);
+            }
+            Some(bill)
+        })
+        .collect();
+
     if available_bills.is_empty() {
         siv.add_layer(Dialog::info("No bills available to add"));
         return;
@@ -387,74 +839,271 @@ fn add_bill_to_ledger(siv: &mut Cursive, ledger_id: i32, ledger_repo: &Rc<Ledger
     for bill in available_bills {
         let due_day_str = bill.due_day.map_or("-".to_string(), |d| d.format("%-d").to_string());
         let label = format!("{} - ${} - {}", bill.name, bill.amount, due_day_str);
-        select.add_item(label, (bill.id, bill.amount, bill.due_day, bill.is_auto_pay));
+        // Create due_day for this ledger (use bill's day with ledger's month/year)
+        let ledger_due_day = bill.due_day.and_then(|d| NaiveDate::from_ymd_opt(ledger_year, ledger_month, d.day()));
+        select.add_item(label, BillToAdd {
+            bill_id: bill.id,
+            catalog_amount: bill.amount,
+            ledger_due_day,
+            autopay: bill.is_auto_pay,
+        });
     }
 
-    let repo_add = Rc::clone(ledger_repo);
+    let repo_add = Rc::clone(repos);
     siv.add_layer(
         Dialog::around(select.with_name("bill_select"))
             .title("Select Bill to Add")
             .button("Add", move |s| {
-                let bill_data = s.call_on_name("bill_select", |v: &mut SelectView<(i32, BigDecimal, Option<chrono::NaiveDate>, bool)>| {
+                let bill_data = s.call_on_name("bill_select", |v: &mut SelectView<BillToAdd>| {
                     v.selection()
                 }).unwrap();
 
                 if let Some(bill_data_rc) = bill_data {
-                    let (bill_id, amount, due_day, autopay) = (*bill_data_rc).clone();
-                    
-                    // Create due_day for this ledger (use bill's day with ledger's month/year)
-                    let ledger_due_day = due_day.and_then(|d| {
-                        chrono::NaiveDate::from_ymd_opt(
-                            ledger_year,
-                            ledger_month,
-                            d.day()
-                        )
-                    });
-
-                    repo_add.create_ledger_bill(ledger_id, bill_id, amount, ledger_due_day, autopay, None);
+                    let bill = (*bill_data_rc).clone();
+
+                    // Variable bills offer estimates from past ledgers; scheduled amounts are already exact
+                    if !scheduled_bill_ids.contains(&bill.bill_id) {
+                        let history = repo_add.ledger.find_bill_amount_history(bill.bill_id);
+                        let estimates = crate::bill_history_logic::estimates(&history, ledger_date);
+                        if !estimates.is_empty() {
+                            estimated_amount_form(s, ledger_id, bill, estimates, &repo_add);
+                            return;
+                        }
+                    }
 
-                    s.pop_layer(); // Close dialog
-                    show_ledger_detail(s, ledger_id, &repo_add); // Refresh view
+                    let amount = bill.catalog_amount.clone();
+                    create_added_bill(s, ledger_id, &bill, amount, &repo_add);
                 }
             })
             .button("Cancel", |s| { s.pop_layer(); })
     );
 }
 
-fn toggle_bill_paid(siv: &mut Cursive, ledger_id: i32, ledger_repo: &Rc<LedgerRepo>) {
+/// Add the bill, then close the bill picker and refresh the ledger
+fn create_added_bill(siv: &mut Cursive, ledger_id: i32, bill: &BillToAdd, amount: BigDecimal, repos: &Rc<LedgerRepos>) {
+    if let Err(e) = repos.ledger.create_ledger_bill(ledger_id, bill.bill_id, amount, bill.ledger_due_day, bill.autopay, None) {
+        siv.pop_layer();
+        siv.add_layer(Dialog::info(e.to_string()));
+        return;
+    }
+
+    siv.pop_layer(); // Close dialog
+    show_ledger_detail(siv, ledger_id, repos); // Refresh view
+}
+
+/// Pick the amount for a variable bill from its history, starting from the preferred estimate
+fn estimated_amount_form(siv: &mut Cursive, ledger_id: i32, bill: BillToAdd, estimates: crate::bill_history_logic::BillEstimates, repos: &Rc<LedgerRepos>) {
+    let mut estimate_select = SelectView::new().popup();
+    if let Some(average) = &estimates.three_month_average {
+        estimate_select.add_item(format!("3-month average: ${}", average), average.clone());
+    }
+    if let Some(last) = &estimates.last {
+        estimate_select.add_item(format!("Last amount: ${}", last), last.clone());
+    }
+    if let Some(last_year) = &estimates.same_month_last_year {
+        estimate_select.add_item(format!("Same month last year: ${}", last_year), last_year.clone());
+    }
+    estimate_select.add_item(format!("Catalog: ${}", bill.catalog_amount), bill.catalog_amount.clone());
+
+    let initial = estimates.preferred().cloned().unwrap_or_else(|| bill.catalog_amount.clone());
+    let estimate_select = estimate_select.on_submit(|s, amount: &BigDecimal| {
+        s.call_on_name("estimated_amount", |v: &mut EditView| v.set_content(amount.to_string()));
+    });
+
+    let repo_save = Rc::clone(repos);
+    siv.add_layer(
+        Dialog::around(
+            ListView::new()
+                .child("Estimate", estimate_select)
+                .child("Amount", EditView::new().content(initial.to_string()).with_name("estimated_amount").fixed_width(14))
+        )
+        .title("Bill Amount")
+        .button("Add", move |s| {
+            let amount_str = s.call_on_name("estimated_amount", |v: &mut EditView| v.get_content()).unwrap();
+            let amount = match amount_str.trim().parse::<BigDecimal>() {
+                Ok(a) => a,
+                Err(_) => {
+                    s.add_layer(Dialog::info("Invalid amount format"));
+                    return;
+                }
+            };
+
+            s.pop_layer();
+            create_added_bill(s, ledger_id, &bill, amount, &repo_save);
+        })
+        .button("Cancel", |s| { s.pop_layer(); })
+    );
+}
+
+fn toggle_bill_paid(siv: &mut Cursive, ledger_id: i32, repos: &Rc<LedgerRepos>) {
     let selected = siv.call_on_name("bills_table", |v: &mut TableView<LedgerBillDisplay, BillColumn>| {
         v.borrow_item(v.item().unwrap()).cloned()
     }).flatten();
 
     if let Some(bill) = selected {
         let new_paid_status = !bill.is_payed;
-        let pg_connector = ledger_repo.pg_connector();
 
-        {
-            let mut conn = pg_connector.get_connection();
-            // Update is_payed status
-            diesel::update(schema::ledger_bills::table.find(bill.id))
-                .set(schema::ledger_bills::is_payed.eq(new_paid_status))
-                .execute(&mut *conn)
-                .expect("Error updating bill paid status");
+        // Update is_payed status
+        if let Err(e) = repos.ledger.set_ledger_bill_paid(bill.id, new_paid_status) {
+            siv.add_layer(Dialog::info(e.to_string()));
+            return;
         }
 
         // Refresh view
-        show_ledger_detail(siv, ledger_id, ledger_repo);
+        show_ledger_detail(siv, ledger_id, repos);
     }
 }
 
-fn delete_bill_from_ledger(siv: &mut Cursive, ledger_id: i32, ledger_repo: &Rc<LedgerRepo>) {
+fn show_bill_payments(siv: &mut Cursive, ledger_id: i32, ledger_bill_id: i32, bill_name: &str, repos: &Rc<LedgerRepos>) {
+    let ledger_bill = {
+        let pg_connector = repos.ledger.pg_connector();
+        let mut conn = pg_connector.get_connection();
+        schema::ledger_bills::table
+            .find(ledger_bill_id)
+            .first::<models::LedgerBill>(&mut *conn)
+            .expect("Error loading ledger bill")
+    };
+    let payments = repos.ledger.find_payments_by_ledger_bill(ledger_bill_id);
+
+    let payment_amounts: Vec<BigDecimal> = payments.iter().map(|p| p.amount.clone()).collect();
+    let paid = crate::ledger_logic::calculate_bill_paid_amount(&ledger_bill.amount, ledger_bill.is_payed, &payment_amounts);
+    let remaining = crate::ledger_logic::calculate_bill_remaining(&ledger_bill.amount, &paid);
+
+    let mut select = SelectView::new();
+    for payment in &payments {
+        let label = format!(
+            "{} - ${}{}",
+            payment.date.format("%d/%m/%Y"),
+            payment.amount,
+            payment.notes.as_ref().map_or(String::new(), |n| format!(" ({})", n))
+        );
+        select.add_item(label, payment.id);
+    }
+    let payment_count = payments.len();
+
+    let summary = TextView::new(format!(
+        "Amount: ${}\nPaid: ${}\nRemaining: ${}",
+        ledger_bill.amount, paid, remaining
+    ));
+
+    let bill_name_add = bill_name.to_string();
+    let bill_name_delete = bill_name.to_string();
+    let repo_add = Rc::clone(repos);
+    let repo_delete = Rc::clone(repos);
+    let mut dialog = Dialog::around(
+        LinearLayout::vertical()
+            .child(summary)
+            .child(Panel::new(select.with_name("payment_select").min_height(3)).title("Payments"))
+    )
+        .title(format!("Payments: {}", bill_name))
+        .button("Add", move |s| add_bill_payment(s, ledger_id, ledger_bill_id, &bill_name_add, remaining.clone(), &repo_add));
+
+    if payment_count > 0 {
+        dialog = dialog.button("Delete", move |s| {
+            let payment_id = s.call_on_name("payment_select", |v: &mut SelectView<i32>| {
+                v.selection()
+            }).unwrap();
+
+            if let Some(selected_id) = payment_id {
+                if let Err(e) = repo_delete.ledger.delete_payment(*selected_id) {
+                    s.add_layer(Dialog::info(e.to_string()));
+                    return;
+                }
+
+                s.pop_layer(); // Close payments dialog
+                show_ledger_detail(s, ledger_id, &repo_delete); // Refresh view
+                show_bill_payments(s, ledger_id, ledger_bill_id, &bill_name_delete, &repo_delete);
+            }
+        });
+    }
+
+    siv.add_layer(dialog.button("Close", |s| { s.pop_layer(); }));
+}
+
+fn add_bill_payment(siv: &mut Cursive, ledger_id: i32, ledger_bill_id: i32, bill_name: &str, remaining: BigDecimal, repos: &Rc<LedgerRepos>) {
+    let today = chrono::Local::now().date_naive();
+    let bill_name_refresh = bill_name.to_string();
+
+    let repo_save = Rc::clone(repos);
+    siv.add_layer(
+        Dialog::around(
+            ListView::new()
+                .child("Date (DD/MM/YYYY)", EditView::new()
+                    .content(today.format("%d/%m/%Y").to_string())
+                    .with_name("payment_date_input")
+                    .fixed_width(20))
+                .child("Amount", EditView::new()
+                    .content(remaining.to_string())
+                    .with_name("payment_amount_input")
+                    .fixed_width(20))
+                .child("Note", EditView::new()
+                    .with_name("payment_notes_input")
+                    .fixed_width(30))
+        )
+        .title(format!("Add Payment: {}", bill_name))
+        .button("Save", move |s| {
+            let date_str = s.call_on_name("payment_date_input", |v: &mut EditView| {
+                v.get_content()
+            }).unwrap();
+
+            let amount_str = s.call_on_name("payment_amount_input", |v: &mut EditView| {
+                v.get_content()
+            }).unwrap();
+
+            let notes_str = s.call_on_name("payment_notes_input", |v: &mut EditView| {
+                v.get_content()
+            }).unwrap();
+
+            let parsed_date = match NaiveDate::parse_from_str(&date_str, "%d/%m/%Y") {
+                Ok(d) => d,
+                Err(_) => {
+                    s.add_layer(Dialog::info("Invalid date format. Use DD/MM/YYYY"));
+                    return;
+                }
+            };
+
+            let amount = match amount_str.to_string().parse::<BigDecimal>() {
+                Ok(a) if a > BigDecimal::from(0) => a,
+                _ => {
+                    s.add_layer(Dialog::info("Invalid amount format"));
+                    return;
+                }
+            };
+
+            if let Err(e) = repo_save.ledger.create_payment(
+                ledger_bill_id,
+                parsed_date,
+                amount,
+                if notes_str.is_empty() { None } else { Some(notes_str.to_string()) }
+            ) {
+                s.add_layer(Dialog::info(e.to_string()));
+                return;
+            }
+
+            s.pop_layer(); // Close form
+            s.pop_layer(); // Close payments dialog
+            show_ledger_detail(s, ledger_id, &repo_save); // Refresh view
+            show_bill_payments(s, ledger_id, ledger_bill_id, &bill_name_refresh, &repo_save);
+        })
+        .button("Cancel", |s| { s.pop_layer(); })
+    );
+}
+
+fn delete_bill_from_ledger(siv: &mut Cursive, ledger_id: i32, repos: &Rc<LedgerRepos>) {
     let selected = siv.call_on_name("bills_table", |v: &mut TableView<LedgerBillDisplay, BillColumn>| {
         v.borrow_item(v.item().unwrap()).cloned()
     }).flatten();
 
     if let Some(bill) = selected {
-        let repo_yes = Rc::clone(ledger_repo);
+        let repo_yes = Rc::clone(repos);
         siv.add_layer(
             Dialog::text(format!("Remove '{}' from ledger?", bill.bill_name))
                 .button("Yes", move |s| {
-                    repo_yes.delete_ledger_bill(bill.id);
+                    if let Err(e) = repo_yes.ledger.delete_ledger_bill(bill.id) {
+                        s.pop_layer();
+                        s.add_layer(Dialog::info(e.to_string()));
+                        return;
+                    }
 
                     s.pop_layer(); // Close dialog
                     show_ledger_detail(s, ledger_id, &repo_yes); // Refresh view
@@ -464,7 +1113,80 @@ fn delete_bill_from_ledger(siv: &mut Cursive, ledger_id: i32, ledger_repo: &Rc<L
     }
 }
 
-fn edit_ledger_bill(siv: &mut Cursive, ledger_id: i32, ledger_repo: &Rc<LedgerRepo>) {
+fn allocate_bill_to_paycheck(siv: &mut Cursive, ledger_id: i32, repos: &Rc<LedgerRepos>) {
+    let selected = siv.call_on_name("bills_table", |v: &mut TableView<LedgerBillDisplay, BillColumn>| {
+        v.borrow_item(v.item().unwrap()).cloned()
+    }).flatten();
+
+    if let Some(bill) = selected {
+        let ledger_incomes = repos.ledger.find_incomes_by_ledger(ledger_id);
+
+        if ledger_incomes.is_empty() {
+            siv.add_layer(Dialog::info("No incomes assigned to this ledger"));
+            return;
+        }
+
+        let mut select = SelectView::new();
+        select.add_item("(Unallocated)", None);
+        for income in ledger_incomes {
+            let label = format!("{} - ${}", income.date.format("%d/%m/%Y"), income.amount);
+            select.add_item(label, Some(income.id));
+        }
+
+        let selected_index = bill.income_id
+            .and_then(|iid| select.iter().position(|(_, v)| *v == Some(iid)))
+            .unwrap_or(0);
+        let select = select.selected(selected_index);
+
+        let repo_allocate = Rc::clone(repos);
+        siv.add_layer(
+            Dialog::around(select.with_name("paycheck_select"))
+                .title(format!("Paycheck for: {}", bill.bill_name))
+                .button("Allocate", move |s| {
+                    let income_id = s.call_on_name("paycheck_select", |v: &mut SelectView<Option<i32>>| {
+                        v.selection()
+                    }).unwrap();
+
+                    if let Some(selected_income) = income_id {
+                        if let Err(e) = repo_allocate.ledger.allocate_bill_to_income(bill.id, *selected_income) {
+                            s.pop_layer();
+                            s.add_layer(Dialog::info(e.to_string()));
+                            return;
+                        }
+
+                        s.pop_layer(); // Close dialog
+                        show_ledger_detail(s, ledger_id, &repo_allocate); // Refresh view
+                    }
+                })
+                .button("Cancel", |s| { s.pop_layer(); })
+        );
+    }
+}
+
+fn auto_allocate_bills(siv: &mut Cursive, ledger_id: i32, repos: &Rc<LedgerRepos>) {
+    let repo_yes = Rc::clone(repos);
+    siv.add_layer(
+        Dialog::text("Assign each bill to the latest paycheck received before it is due?\nExisting allocations will be replaced.")
+            .title("Auto-Allocate")
+            .button("Yes", move |s| {
+                let count = match repo_yes.ledger.auto_allocate_bills(ledger_id) {
+                    Ok(c) => c,
+                    Err(e) => {
+                        s.pop_layer();
+                        s.add_layer(Dialog::info(e.to_string()));
+                        return;
+                    }
+                };
+
+                s.pop_layer(); // Close dialog
+                show_ledger_detail(s, ledger_id, &repo_yes); // Refresh view
+                s.add_layer(Dialog::info(format!("Allocated {} bills", count)));
+            })
+            .button("No", |s| { s.pop_layer(); })
+    );
+}
+
+fn edit_ledger_bill(siv: &mut Cursive, ledger_id: i32, repos: &Rc<LedgerRepos>) {
     let selected = siv.call_on_name("bills_table", |v: &mut TableView<LedgerBillDisplay, BillColumn>| {
         v.borrow_item(v.item().unwrap()).cloned()
     }).flatten();
@@ -473,7 +1195,7 @@ fn edit_ledger_bill(siv: &mut Cursive, ledger_id: i32, ledger_repo: &Rc<LedgerRe
         let bill_id = bill.id;
 
         // Get the current bill data from database to get the actual due_day
-        let pg_connector = ledger_repo.pg_connector();
+        let pg_connector = repos.ledger.pg_connector();
         let ledger_bill = {
             let mut conn = pg_connector.get_connection();
             schema::ledger_bills::table
@@ -481,6 +1203,7 @@ fn edit_ledger_bill(siv: &mut Cursive, ledger_id: i32, ledger_repo: &Rc<LedgerRe
                 .first::<models::LedgerBill>(&mut *conn)
                 .expect("Error loading ledger bill")
         };
+        let ledger_date = repos.ledger.find_by_id(ledger_bill.ledger_id).expect("Error loading ledger").date;
 
         let form = ListView::new()
             .child("Amount", EditView::new()
@@ -494,12 +1217,20 @@ fn edit_ledger_bill(siv: &mut Cursive, ledger_id: i32, ledger_repo: &Rc<LedgerRe
             .child("Paid", Checkbox::new()
                 .with_checked(bill.is_payed)
                 .with_name("edit_bill_paid"))
+            .child("Account", crate::account_table::build_account_select(
+                &repos.account.find_all(),
+                ledger_bill.account_id
+            ).with_name("edit_bill_account"))
+            .child("Tax", crate::tax_report::build_tax_category_select(
+                "(From bill)",
+                ledger_bill.tax_category.as_deref()
+            ).with_name("edit_bill_tax"))
             .child("Notes", TextArea::new()
                 .content(bill.notes.clone().unwrap_or_default())
                 .with_name("edit_bill_notes")
                 .min_size((40, 3)));
 
-        let repo_save = Rc::clone(ledger_repo);
+        let repo_save = Rc::clone(repos);
         siv.add_layer(
             Dialog::around(form)
                 .title(format!("Edit: {}", bill.bill_name))
@@ -520,6 +1251,14 @@ fn edit_ledger_bill(siv: &mut Cursive, ledger_id: i32, ledger_repo: &Rc<LedgerRe
                         v.get_content().to_string()
                     }).unwrap();
 
+                    let account_id = s.call_on_name("edit_bill_account", |v: &mut SelectView<Option<i32>>| {
+                        v.selection().and_then(|a| *a)
+                    }).flatten();
+
+                    let tax_category = s.call_on_name("edit_bill_tax", |v: &mut SelectView<Option<String>>| {
+                        v.selection().and_then(|t| (*t).clone())
+                    }).flatten();
+
                     // Parse amount
                     let amount = match amount_str.to_string().parse::<BigDecimal>() {
                         Ok(a) => a,
@@ -529,26 +1268,31 @@ fn edit_ledger_bill(siv: &mut Cursive, ledger_id: i32, ledger_repo: &Rc<LedgerRe
                         }
                     };
 
-                    // Parse due day (optional)
+                    // Parse due day (optional), in the ledger's year
                     let due_day = if due_day_str.is_empty() {
                         None
                     } else {
-                        match chrono::NaiveDate::parse_from_str(&format!("{}/2024", due_day_str.to_string()), "%d/%m/%Y") {
-                            Ok(d) => Some(d),
-                            Err(_) => {
+                        match crate::ledger_logic::ledger_due_date(ledger_date, &due_day_str) {
+                            Some(d) => Some(d),
+                            None => {
                                 s.add_layer(Dialog::info("Invalid date format (use DD/MM)"));
                                 return;
                             }
                         }
                     };
 
-                    repo_save.update_ledger_bill(
+                    if let Err(e) = repo_save.ledger.update_ledger_bill(
                         bill_id,
                         amount,
                         due_day,
                         is_paid,
                         if notes_str.is_empty() { None } else { Some(notes_str.to_string()) }
-                    );
+                    ) {
+                        s.add_layer(Dialog::info(e.to_string()));
+                        return;
+                    }
+                    let _ = repo_save.ledger.set_ledger_bill_account(bill_id, account_id);
+                    let _ = repo_save.ledger.set_ledger_bill_tax_category(bill_id, tax_category);
 
                     s.pop_layer(); // Close dialog
                     show_ledger_detail(s, ledger_id, &repo_save); // Refresh view
@@ -557,15 +1301,659 @@ fn edit_ledger_bill(siv: &mut Cursive, ledger_id: i32, ledger_repo: &Rc<LedgerRe
         );
     }
 }
-fn update_ledger(siv: &mut Cursive, ledger_id: i32, ledger_repo: &Rc<LedgerRepo>) {
-    let ledger = ledger_repo.find_by_id(ledger_id).expect("Error loading ledger");
+fn show_cash_flow_timeline(siv: &mut Cursive, ledger_id: i32, repos: &Rc<LedgerRepos>) {
+    let ledger = repos.ledger.find_by_id(ledger_id).expect("Error loading ledger");
+    let buffer = repos.config.get_cash_flow_buffer();
+
+    let ledger_incomes = repos.ledger.find_incomes_by_ledger(ledger_id);
+    let incomes: Vec<(NaiveDate, BigDecimal)> = ledger_incomes
+        .iter()
+        .map(|i| (i.date, i.amount.clone()))
+        .collect();
+    // Expected paychecks are still projected, but flagged until confirmed
+    let expected_dates: Vec<NaiveDate> = ledger_incomes
+        .iter()
+        .filter(|i| i.is_expected)
+        .map(|i| i.date)
+        .collect();
+
+    // Bills without a due date are assumed to go out on the opening day
+    let bills: Vec<(NaiveDate, BigDecimal)> = repos.ledger.find_ledger_bills_with_bill_names(ledger_id)
+        .into_iter()
+        .map(|(lb, _)| (lb.due_day.unwrap_or(ledger.date), lb.amount))
+        .collect();
+
+    let timeline = crate::ledger_logic::build_cash_flow_timeline(ledger.date, &ledger.bank_balance, &incomes, &bills);
+    let lowest_date = crate::ledger_logic::find_lowest_day(&timeline).map(|d| d.date);
+
+    let mut content = StyledString::new();
+    content.append_plain(format!("{:<8}{:>14}{:>14}{:>14}\n", "DATE", "IN", "OUT", "BALANCE"));
+    content.append_plain("──────────────────────────────────────────────────\n");
+
+    for day in &timeline {
+        let date_str = day.date.format("%d/%m").to_string();
+        let incoming_str = format!("${}", day.incoming);
+        let outgoing_str = format!("${}", day.outgoing);
+        let balance_str = format!("${}", day.balance);
+        let expected_marker = if expected_dates.contains(&day.date) { "  (expected pay)" } else { "" };
+        let marker = if Some(day.date) == lowest_date { "  ◀ lowest" } else { "" };
+        let line = format!("{:<8}{:>14}{:>14}{:>14}{}{}\n", date_str, incoming_str, outgoing_str, balance_str, expected_marker, marker);
+
+        if day.balance < BigDecimal::from(0) {
+            content.append_styled(line, Color::Light(BaseColor::Red));
+        } else if day.balance < buffer {
+            content.append_styled(line, Color::Light(BaseColor::Yellow));
+        } else {
+            content.append_plain(line);
+        }
+    }
+
+    content.append_plain(format!("\nBuffer: ${} (red: below zero, yellow: below buffer)", buffer));
+
+    siv.add_layer(
+        Dialog::around(TextView::new(content).scrollable())
+            .title("Cash-Flow Timeline")
+            .button("Close", |s| { s.pop_layer(); })
+    );
+}
+
+fn ripple_opening_balances(siv: &mut Cursive, ledger_id: i32, repos: &Rc<LedgerRepos>) {
+    let successor_count = repos.ledger.find_successors(ledger_id).len();
+
+    let repo_yes = Rc::clone(repos);
+    siv.add_layer(
+        Dialog::text(format!(
+            "Carry this ledger's closing balance forward (its latest reconciliation, or its net)?\nOpening balances of {} later ledger(s) will be recalculated.",
+            successor_count
+        ))
+            .title("Ripple Forward")
+            .button("Yes", move |s| {
+                s.pop_layer(); // Close dialog
+
+                match repo_yes.ledger.ripple_opening_balances(ledger_id) {
+                    Ok(count) => {
+                        show_ledger_detail(s, ledger_id, &repo_yes); // Refresh view
+                        s.add_layer(Dialog::info(format!("Updated {} ledger(s)", count)));
+                    }
+                    Err(_) => {
+                        s.add_layer(Dialog::info("A later ledger is closed. Reopen it to ripple balances forward."));
+                    }
+                }
+            })
+            .button("No", |s| { s.pop_layer(); })
+    );
+}
+
+fn toggle_ledger_closed(siv: &mut Cursive, ledger_id: i32, is_closed: bool, repos: &Rc<LedgerRepos>) {
+    let prompt = if is_closed {
+        "Reopen this ledger?\nChanges to its bills and incomes will alter its historical net."
+    } else {
+        "Close this ledger?\nIts bills and incomes will be locked until it is reopened."
+    };
+
+    let repo_yes = Rc::clone(repos);
+    siv.add_layer(
+        Dialog::text(prompt)
+            .title(if is_closed { "Reopen Ledger" } else { "Close Ledger" })
+            .button("Yes", move |s| {
+                if is_closed {
+                    repo_yes.ledger.reopen(ledger_id);
+                } else {
+                    repo_yes.ledger.close(ledger_id);
+                }
+
+                s.pop_layer(); // Close dialog
+                show_ledger_detail(s, ledger_id, &repo_yes); // Refresh view
+            })
+            .button("No", |s| { s.pop_layer(); })
+    );
+}
+
+fn reconcile_ledger(siv: &mut Cursive, ledger_id: i32, repos: &Rc<LedgerRepos>) {
+    let today = chrono::Local::now().date_naive();
+
+    let repo_save = Rc::clone(repos);
+    siv.add_layer(
+        Dialog::around(
+            ListView::new()
+                .child("Date (DD/MM/YYYY)", EditView::new()
+                    .content(today.format("%d/%m/%Y").to_string())
+                    .with_name("reconcile_date_input")
+                    .fixed_width(20))
+                .child("Actual Balance", EditView::new()
+                    .with_name("reconcile_actual_input")
+                    .fixed_width(20))
+                .child("Notes", TextArea::new()
+                    .with_name("reconcile_notes_input")
+                    .min_size((40, 3)))
+        )
+        .title("Reconcile Ledger")
+        .button("Reconcile", move |s| {
+            let date_str = s.call_on_name("reconcile_date_input", |v: &mut EditView| {
+                v.get_content()
+            }).unwrap();
+
+            let actual_str = s.call_on_name("reconcile_actual_input", |v: &mut EditView| {
+                v.get_content()
+            }).unwrap();
+
+            let notes_str = s.call_on_name("reconcile_notes_input", |v: &mut TextArea| {
+                v.get_content().to_string()
+            }).unwrap();
+
+            let parsed_date = match NaiveDate::parse_from_str(&date_str, "%d/%m/%Y") {
+                Ok(d) => d,
+                Err(_) => {
+                    s.add_layer(Dialog::info("Invalid date format. Use DD/MM/YYYY"));
+                    return;
+                }
+            };
+
+            let actual = match actual_str.to_string().parse::<BigDecimal>() {
+                Ok(a) => a,
+                Err(_) => {
+                    s.add_layer(Dialog::info("Invalid balance format"));
+                    return;
+                }
+            };
+
+            let expected = match repo_save.ledger.calculate_expected_balance(ledger_id, parsed_date) {
+                Some(e) => e,
+                None => {
+                    s.add_layer(Dialog::info("Error loading ledger"));
+                    return;
+                }
+            };
+
+            let reconciliation = repo_save.ledger.create_reconciliation(
+                ledger_id,
+                parsed_date,
+                actual.clone(),
+                expected,
+                if notes_str.is_empty() { None } else { Some(notes_str) }
+            );
+
+            // The reconciled balance is also a snapshot of the account the ledger draws from
+            if let Some(account_id) = repo_save.ledger.find_by_id(ledger_id).and_then(|l| l.account_id) {
+                repo_save.account.record_balance(account_id, parsed_date, actual);
+            }
+
+            s.pop_layer(); // Close dialog
+            show_ledger_detail(s, ledger_id, &repo_save); // Refresh view
+            s.add_layer(Dialog::info(format!(
+                "Actual:     ${}\nExpected:   ${}\nDifference: ${}",
+                reconciliation.actual_balance,
+                reconciliation.expected_balance,
+                reconciliation.delta.unwrap_or(BigDecimal::from(0))
+            )).title("Reconciliation Saved"));
+        })
+        .button("Cancel", |s| { s.pop_layer(); })
+    );
+}
+
+fn show_reconciliation_history(siv: &mut Cursive, ledger_id: i32, repos: &Rc<LedgerRepos>) {
+    let reconciliations = repos.ledger.find_reconciliations_by_ledger(ledger_id);
+
+    if reconciliations.is_empty() {
+        siv.add_layer(Dialog::info("No reconciliations recorded for this ledger"));
+        return;
+    }
+
+    let mut content = format!("{:<12}{:>14}{:>14}{:>14}\n", "DATE", "ACTUAL", "EXPECTED", "DIFFERENCE");
+    content.push_str("──────────────────────────────────────────────────────\n");
+    for r in reconciliations {
+        let date_str = r.date.format("%d/%m/%Y").to_string();
+        let actual_str = format!("${}", r.actual_balance);
+        let expected_str = format!("${}", r.expected_balance);
+        let delta_str = format!("${}", r.delta.unwrap_or(BigDecimal::from(0)));
+        content.push_str(&format!("{:<12}{:>14}{:>14}{:>14}\n", date_str, actual_str, expected_str, delta_str));
+        if let Some(notes_text) = r.notes {
+            content.push_str(&format!("  {}\n", notes_text));
+        }
+    }
+
+    siv.add_layer(
+        Dialog::around(TextView::new(content).scrollable())
+            .title("Reconciliation History")
+            .button("Close", |s| { s.pop_layer(); })
+    );
+}
+
+/// This ledger and its linked predecessors, oldest first
+fn ledger_chain_ids(ledger_id: i32, repos: &Rc<LedgerRepos>) -> Vec<i32> {
+    let mut chain: Vec<i32> = repos.ledger.find_predecessors(ledger_id)
+        .into_iter()
+        .rev()
+        .map(|l| l.id)
+        .collect();
+    chain.push(ledger_id);
+    chain
+}
+
+fn show_envelopes(siv: &mut Cursive, ledger_id: i32, repos: &Rc<LedgerRepos>) {
+    let envelope_repo = &repos.envelope;
+    let envelopes = envelope_repo.find_all();
+    let balances = envelope_repo.find_balances(&ledger_chain_ids(ledger_id, repos));
+
+    let header = format!(
+        "{:<18}{:>12}{:>12}{:>12}{:>12}",
+        "ENVELOPE", "CARRIED", "ALLOCATED", "SPENT", "BALANCE"
+    );
+
+    let mut select = SelectView::<i32>::new();
+    for envelope in &envelopes {
+        let balance = balances.iter().find(|b| b.envelope_id == envelope.id);
+        let carried = format!("${}", balance.map_or(BigDecimal::from(0), |b| b.carried_in.clone()));
+        let allocated = format!("${}", balance.map_or(BigDecimal::from(0), |b| b.allocated.clone()));
+        let spent = format!("${}", balance.map_or(BigDecimal::from(0), |b| b.spent.clone()));
+        let remaining = format!("${}", balance.map_or(BigDecimal::from(0), |b| b.balance.clone()));
+        select.add_item(
+            format!("{:<18}{:>12}{:>12}{:>12}{:>12}", envelope.name, carried, allocated, spent, remaining),
+            envelope.id
+        );
+    }
+
+    let repo_allocate = Rc::clone(repos);
+    let repo_add = Rc::clone(repos);
+    let repo_delete = Rc::clone(repos);
+    siv.add_layer(
+        Dialog::around(
+            LinearLayout::vertical()
+                .child(TextView::new(header))
+                .child(select.with_name("envelope_select").scrollable().min_height(5))
+        )
+        .title("Envelopes")
+        .button("Allocate", move |s| {
+            let selected = s.call_on_name("envelope_select", |v: &mut SelectView<i32>| {
+                v.selection().map(|e| *e)
+            }).flatten();
+
+            if let Some(envelope_id) = selected {
+                allocate_to_envelope(s, ledger_id, envelope_id, &repo_allocate);
+            }
+        })
+        .button("New", move |s| add_envelope(s, ledger_id, &repo_add))
+        .button("Delete", move |s| {
+            let selected = s.call_on_name("envelope_select", |v: &mut SelectView<i32>| {
+                v.selection().map(|e| *e)
+            }).flatten();
+
+            if let Some(envelope_id) = selected {
+                let repo = Rc::clone(&repo_delete);
+                s.add_layer(
+                    Dialog::text("Delete this envelope from every ledger?\nBills using it will no longer draw it down.")
+                        .button("Yes", move |s| {
+                            repo.envelope.delete(envelope_id);
+                            s.pop_layer(); // Close confirm
+                            s.pop_layer(); // Close envelopes
+                            show_ledger_detail(s, ledger_id, &repo);
+                            show_envelopes(s, ledger_id, &repo);
+                        })
+                        .button("No", |s| { s.pop_layer(); })
+                );
+            }
+        })
+        .button("Close", |s| { s.pop_layer(); })
+    );
+}
+
+fn allocate_to_envelope(siv: &mut Cursive, ledger_id: i32, envelope_id: i32, repos: &Rc<LedgerRepos>) {
+    let current = repos.envelope
+        .find_allocations_by_ledger(ledger_id)
+        .into_iter()
+        .find(|a| a.envelope_id == envelope_id)
+        .map_or(BigDecimal::from(0), |a| a.allocated);
+
+    let repo_save = Rc::clone(repos);
+    siv.add_layer(
+        Dialog::around(
+            ListView::new()
+                .child("Allocated", EditView::new()
+                    .content(current.to_string())
+                    .with_name("envelope_allocated_input")
+                    .fixed_width(20))
+        )
+        .title("Allocate to Envelope")
+        .button("Save", move |s| {
+            let amount_str = s.call_on_name("envelope_allocated_input", |v: &mut EditView| {
+                v.get_content()
+            }).unwrap();
+
+            let amount = match amount_str.to_string().parse::<BigDecimal>() {
+                Ok(a) => a,
+                Err(_) => {
+                    s.add_layer(Dialog::info("Invalid amount format"));
+                    return;
+                }
+            };
+
+            if let Err(e) = repo_save.envelope.set_allocation(ledger_id, envelope_id, amount) {
+                s.add_layer(Dialog::info(e.to_string()));
+                return;
+            }
+
+            s.pop_layer(); // Close dialog
+            s.pop_layer(); // Close envelopes
+            show_ledger_detail(s, ledger_id, &repo_save); // Refresh view
+            show_envelopes(s, ledger_id, &repo_save);
+        })
+        .button("Cancel", |s| { s.pop_layer(); })
+    );
+}
+
+fn add_envelope(siv: &mut Cursive, ledger_id: i32, repos: &Rc<LedgerRepos>) {
+    let repo_save = Rc::clone(repos);
+    siv.add_layer(
+        Dialog::around(
+            ListView::new()
+                .child("Name", EditView::new()
+                    .with_name("envelope_name_input")
+                    .fixed_width(20))
+                .child("Notes", TextArea::new()
+                    .with_name("envelope_notes_input")
+                    .min_size((40, 3)))
+        )
+        .title("New Envelope")
+        .button("Ok", move |s| {
+            let name_str = s.call_on_name("envelope_name_input", |v: &mut EditView| {
+                v.get_content()
+            }).unwrap();
+
+            let notes_str = s.call_on_name("envelope_notes_input", |v: &mut TextArea| {
+                v.get_content().to_string()
+            }).unwrap();
+
+            if name_str.trim().is_empty() {
+                s.add_layer(Dialog::info("Name cannot be empty"));
+                return;
+            }
+
+            repo_save.envelope.create(
+                name_str.trim().to_string(),
+                if notes_str.is_empty() { None } else { Some(notes_str) }
+            );
+
+            s.pop_layer(); // Close dialog
+            s.pop_layer(); // Close envelopes
+            show_ledger_detail(s, ledger_id, &repo_save); // Refresh view
+            show_envelopes(s, ledger_id, &repo_save);
+        })
+        .button("Cancel", |s| { s.pop_layer(); })
+    );
+}
+
+fn account_name(accounts: &[models::Account], account_id: Option<i32>) -> String {
+    account_id
+        .and_then(|aid| accounts.iter().find(|a| a.id == aid))
+        .map_or("Unassigned".to_string(), |a| a.name.clone())
+}
+
+/// Per-account openings, movements and nets, with transfers between accounts
+fn show_ledger_accounts(siv: &mut Cursive, ledger_id: i32, repos: &Rc<LedgerRepos>) {
+    let accounts = repos.account.find_all();
+    if accounts.is_empty() {
+        siv.add_layer(Dialog::info("Add accounts from the Net Worth screen (n) first"));
+        return;
+    }
+
+    let account_nets = repos.ledger.find_account_nets(ledger_id);
+    let mut account_text = format!(
+        "{:<16}{:>12}{:>12}{:>12}{:>12}{:>12}\n",
+        "ACCOUNT", "OPENING", "IN", "OUT", "TRANSFERS", "NET"
+    );
+    for account_net in &account_nets {
+        let opening = format!("${}", account_net.opening);
+        let income = format!("${}", account_net.income);
+        let expenses = format!("${}", account_net.expenses);
+        let transfers = format!("${}", account_net.transfers);
+        let net = format!("${}", account_net.net);
+        account_text.push_str(&format!(
+            "{:<16}{:>12}{:>12}{:>12}{:>12}{:>12}\n",
+            account_name(&accounts, account_net.account_id), opening, income, expenses, transfers, net
+        ));
+    }
+
+    let transfers = repos.ledger.find_transfers_by_ledger(ledger_id);
+    let mut transfer_select = SelectView::<i32>::new();
+    for transfer in &transfers {
+        transfer_select.add_item(
+            format!(
+                "{}  {} → {}  ${}{}",
+                transfer.date.format("%d/%m"),
+                account_name(&accounts, Some(transfer.from_account_id)),
+                account_name(&accounts, Some(transfer.to_account_id)),
+                transfer.amount,
+                transfer.notes.as_ref().map_or(String::new(), |n| format!("  ({})", n))
+            ),
+            transfer.id
+        );
+    }
+    let transfer_count = transfers.len();
+
+    let repo_opening = Rc::clone(repos);
+    let repo_transfer = Rc::clone(repos);
+    let repo_delete = Rc::clone(repos);
+    let mut dialog = Dialog::around(
+        LinearLayout::vertical()
+            .child(TextView::new(account_text))
+            .child(TextView::new(format!("TRANSFERS ({})", transfer_count)))
+            .child(transfer_select.with_name("transfer_select").scrollable().min_height(3))
+    )
+    .title("Accounts")
+    .button("Set Opening", move |s| set_account_opening(s, ledger_id, &repo_opening))
+    .button("Transfer", move |s| add_transfer(s, ledger_id, &repo_transfer));
+
+    if transfer_count > 0 {
+        dialog.add_button("Delete Transfer", move |s| {
+            let selected = s.call_on_name("transfer_select", |v: &mut SelectView<i32>| {
+                v.selection().map(|t| *t)
+            }).flatten();
+
+            if let Some(transfer_id) = selected {
+                if let Err(e) = repo_delete.ledger.delete_transfer(ledger_id, transfer_id) {
+                    s.add_layer(Dialog::info(e.to_string()));
+                    return;
+                }
+                s.pop_layer(); // Close accounts
+                show_ledger_detail(s, ledger_id, &repo_delete);
+                show_ledger_accounts(s, ledger_id, &repo_delete);
+            }
+        });
+    }
+
+    siv.add_layer(dialog.button("Close", |s| { s.pop_layer(); }));
+}
+
+fn set_account_opening(siv: &mut Cursive, ledger_id: i32, repos: &Rc<LedgerRepos>) {
+    let accounts = repos.account.find_all();
+    let openings = repos.ledger.find_ledger_accounts(ledger_id);
+
+    let mut account_select = SelectView::<i32>::new().popup();
+    for account in &accounts {
+        account_select.add_item(account.name.clone(), account.id);
+    }
+
+    // Picking an account that already has an opening shows its current amount
+    let first_opening = accounts.first()
+        .and_then(|a| openings.iter().find(|o| o.account_id == a.id))
+        .map_or(String::new(), |o| o.opening_balance.to_string());
+    let account_select = account_select.on_submit(move |s, account_id: &i32| {
+        let opening = openings.iter()
+            .find(|o| o.account_id == *account_id)
+            .map_or(String::new(), |o| o.opening_balance.to_string());
+        s.call_on_name("account_opening_input", |v: &mut EditView| {
+            let _ = v.set_content(opening);
+        });
+    });
+
+    let repo_save = Rc::clone(repos);
+    let repo_remove = Rc::clone(repos);
+    siv.add_layer(
+        Dialog::around(
+            ListView::new()
+                .child("Account", account_select.with_name("account_opening_select"))
+                .child("Opening Balance", EditView::new()
+                    .content(first_opening)
+                    .with_name("account_opening_input")
+                    .fixed_width(20))
+        )
+        .title("Account Opening Balance")
+        .button("Save", move |s| {
+            let account_id = s.call_on_name("account_opening_select", |v: &mut SelectView<i32>| {
+                v.selection().map(|a| *a)
+            }).flatten();
+            let amount_str = s.call_on_name("account_opening_input", |v: &mut EditView| {
+                v.get_content()
+            }).unwrap();
+
+            let amount = match amount_str.to_string().parse::<BigDecimal>() {
+                Ok(a) => a,
+                Err(_) => {
+                    s.add_layer(Dialog::info("Invalid amount format"));
+                    return;
+                }
+            };
+
+            if let Some(aid) = account_id
+                && let Err(e) = repo_save.ledger.set_account_opening(ledger_id, aid, amount) {
+                s.add_layer(Dialog::info(e.to_string()));
+                return;
+            }
+
+            s.pop_layer(); // Close dialog
+            s.pop_layer(); // Close accounts
+            show_ledger_detail(s, ledger_id, &repo_save); // Refresh view
+            show_ledger_accounts(s, ledger_id, &repo_save);
+        })
+        .button("Remove", move |s| {
+            let account_id = s.call_on_name("account_opening_select", |v: &mut SelectView<i32>| {
+                v.selection().map(|a| *a)
+            }).flatten();
+
+            if let Some(aid) = account_id
+                && let Err(e) = repo_remove.ledger.remove_ledger_account(ledger_id, aid) {
+                s.add_layer(Dialog::info(e.to_string()));
+                return;
+            }
+
+            s.pop_layer(); // Close dialog
+            s.pop_layer(); // Close accounts
+            show_ledger_detail(s, ledger_id, &repo_remove);
+            show_ledger_accounts(s, ledger_id, &repo_remove);
+        })
+        .button("Cancel", |s| { s.pop_layer(); })
+    );
+}
+
+fn add_transfer(siv: &mut Cursive, ledger_id: i32, repos: &Rc<LedgerRepos>) {
+    let accounts = repos.account.find_all();
+    if accounts.len() < 2 {
+        siv.add_layer(Dialog::info("Transfers need at least two accounts"));
+        return;
+    }
+
+    let ledger_date = repos.ledger.find_by_id(ledger_id)
+        .map(|l| l.date)
+        .unwrap_or_else(|| chrono::Local::now().date_naive());
+
+    let mut from_select = SelectView::<i32>::new().popup();
+    let mut to_select = SelectView::<i32>::new().popup();
+    for account in &accounts {
+        from_select.add_item(account.name.clone(), account.id);
+        to_select.add_item(account.name.clone(), account.id);
+    }
+    let to_select = to_select.selected(1);
+
+    let repo_save = Rc::clone(repos);
+    siv.add_layer(
+        Dialog::around(
+            ListView::new()
+                .child("From", from_select.with_name("transfer_from_select"))
+                .child("To", to_select.with_name("transfer_to_select"))
+                .child("Amount", EditView::new().with_name("transfer_amount_input").fixed_width(20))
+                .child("Date (DD/MM/YYYY)", EditView::new()
+                    .content(ledger_date.format("%d/%m/%Y").to_string())
+                    .with_name("transfer_date_input")
+                    .fixed_width(20))
+                .child("Notes", TextArea::new()
+                    .with_name("transfer_notes_input")
+                    .min_size((40, 3)))
+        )
+        .title("Transfer Between Accounts")
+        .button("Save", move |s| {
+            let from_id = s.call_on_name("transfer_from_select", |v: &mut SelectView<i32>| {
+                v.selection().map(|a| *a)
+            }).flatten();
+            let to_id = s.call_on_name("transfer_to_select", |v: &mut SelectView<i32>| {
+                v.selection().map(|a| *a)
+            }).flatten();
+            let amount_str = s.call_on_name("transfer_amount_input", |v: &mut EditView| {
+                v.get_content()
+            }).unwrap();
+            let date_str = s.call_on_name("transfer_date_input", |v: &mut EditView| {
+                v.get_content()
+            }).unwrap();
+            let notes_str = s.call_on_name("transfer_notes_input", |v: &mut TextArea| {
+                v.get_content().to_string()
+            }).unwrap();
+
+            let (from_id, to_id) = match (from_id, to_id) {
+                (Some(f), Some(t)) if f != t => (f, t),
+                _ => {
+                    s.add_layer(Dialog::info("Pick two different accounts"));
+                    return;
+                }
+            };
+
+            let amount = match amount_str.to_string().parse::<BigDecimal>() {
+                Ok(a) if a > BigDecimal::from(0) => a,
+                _ => {
+                    s.add_layer(Dialog::info("Amount must be a positive number"));
+                    return;
+                }
+            };
+
+            let date = match NaiveDate::parse_from_str(&date_str, "%d/%m/%Y") {
+                Ok(d) => d,
+                Err(_) => {
+                    s.add_layer(Dialog::info("Invalid date format. Use DD/MM/YYYY"));
+                    return;
+                }
+            };
+
+            if let Err(e) = repo_save.ledger.create_transfer(
+                ledger_id,
+                from_id,
+                to_id,
+                amount,
+                date,
+                if notes_str.is_empty() { None } else { Some(notes_str) }
+            ) {
+                s.add_layer(Dialog::info(e.to_string()));
+                return;
+            }
+
+            s.pop_layer(); // Close dialog
+            s.pop_layer(); // Close accounts
+            show_ledger_detail(s, ledger_id, &repo_save); // Refresh view
+            show_ledger_accounts(s, ledger_id, &repo_save);
+        })
+        .button("Cancel", |s| { s.pop_layer(); })
+    );
+}
+
+fn update_ledger(siv: &mut Cursive, ledger_id: i32, repos: &Rc<LedgerRepos>) {
+    let ledger = repos.ledger.find_by_id(ledger_id).expect("Error loading ledger");
 
     let current_balance = ledger.bank_balance.to_string();
     let name = ledger.name.unwrap_or_default();
     let date = ledger.date;
     let notes = ledger.notes.unwrap_or_default();
+    let (previous_select, _) = crate::ledger_table::build_previous_ledger_select(repos, Some(ledger_id), ledger.previous_ledger_id);
+    let accounts = repos.account.find_all();
+    let account_select = crate::account_table::build_account_select(&accounts, ledger.account_id);
 
-    let repo_update = Rc::clone(ledger_repo);
+    let repo_update = Rc::clone(repos);
     siv.add_layer(
         Dialog::around(
 
@@ -578,6 +1966,8 @@ fn update_ledger(siv: &mut Cursive, ledger_id: i32, ledger_repo: &Rc<LedgerRepo>
                     .content(name)
                     .with_name("ledger_name_input")
                     .fixed_width(20))
+                .child("Previous Ledger", previous_select.with_name("previous_ledger_select"))
+                .child("Account", account_select.with_name("ledger_account_select"))
                 .child("Bank Balance", EditView::new()
                     .content(current_balance)
                     .with_name("bank_balance_input")
@@ -605,6 +1995,14 @@ fn update_ledger(siv: &mut Cursive, ledger_id: i32, ledger_repo: &Rc<LedgerRepo>
                 v.get_content().to_string()
             }).unwrap();
 
+            let previous_id = s.call_on_name("previous_ledger_select", |v: &mut SelectView<Option<i32>>| {
+                v.selection()
+            }).unwrap().and_then(|sel| *sel);
+
+            let account_id = s.call_on_name("ledger_account_select", |v: &mut SelectView<Option<i32>>| {
+                v.selection()
+            }).unwrap().and_then(|sel| *sel);
+
             let parsed_date = NaiveDate::parse_from_str(&date, "%d/%m/%Y");
 
             if parsed_date.is_err() {
@@ -619,16 +2017,26 @@ fn update_ledger(siv: &mut Cursive, ledger_id: i32, ledger_repo: &Rc<LedgerRepo>
                 return;
             }
 
-            repo_update.update(
+            if let Err(e) = repo_update.ledger.update(
                 ledger_id,
                 parsed_date.unwrap(),
                 name,
                 balance.unwrap(),
                 if notes_str.is_empty() { None } else { Some(notes_str.to_string()) }
-            );
+            ) {
+                s.add_layer(Dialog::info(e.to_string()));
+                return;
+            }
+            let _ = repo_update.ledger.set_previous_ledger(ledger_id, previous_id);
+            let _ = repo_update.ledger.set_account(ledger_id, account_id);
 
             s.pop_layer(); // Close dialog
             show_ledger_detail(s, ledger_id, &repo_update); // Refresh view
+
+            // A new net here changes the opening balance of every later ledger
+            if !repo_update.ledger.find_successors(ledger_id).is_empty() {
+                ripple_opening_balances(s, ledger_id, &repo_update);
+            }
         })
         .button("Cancel", |s| { s.pop_layer(); })
     );
@@ -0,0 +1,499 @@
+use chrono::{Datelike, NaiveDate};
+use bigdecimal::BigDecimal;
+
+/// Assign each bill to the latest paycheck received on or before its due date
+/// Bills with no due date, or due before any paycheck arrives, stay unassigned
+pub fn auto_allocate_bills(
+    bills: &[(i32, Option<NaiveDate>)],
+    incomes: &[(i32, NaiveDate)],
+) -> Vec<(i32, Option<i32>)> {
+    let mut sorted_incomes = incomes.to_vec();
+    sorted_incomes.sort_by_key(|(income_id, date)| (*date, *income_id));
+
+    bills
+        .iter()
+        .map(|(bill_id, due_day)| {
+            let income_id = due_day.and_then(|due| {
+                sorted_incomes
+                    .iter()
+                    .rev()
+                    .find(|(_, date)| *date <= due)
+                    .map(|(income_id, _)| *income_id)
+            });
+            (*bill_id, income_id)
+        })
+        .collect()
+}
+
+/// Calculate what is left of a paycheck after the bills allocated to it
+pub fn calculate_income_remaining(
+    income_id: i32,
+    income_amount: &BigDecimal,
+    allocations: &[(Option<i32>, BigDecimal)],
+) -> BigDecimal {
+    let allocated: BigDecimal = allocations
+        .iter()
+        .filter(|(allocated_to, _)| *allocated_to == Some(income_id))
+        .map(|(_, amount)| amount.clone())
+        .sum();
+
+    income_amount.clone() - allocated
+}
+
+/// One day of a ledger's cash-flow timeline
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimelineDay {
+    pub date: NaiveDate,
+    pub incoming: BigDecimal,
+    pub outgoing: BigDecimal,
+    pub balance: BigDecimal,
+}
+
+/// Build a running balance per day, starting from the opening balance
+/// Incomes and bills are applied in date order, all movements on a day at once
+pub fn build_cash_flow_timeline(
+    opening_date: NaiveDate,
+    opening_balance: &BigDecimal,
+    incomes: &[(NaiveDate, BigDecimal)],
+    bills: &[(NaiveDate, BigDecimal)],
+) -> Vec<TimelineDay> {
+    let mut dates: Vec<NaiveDate> = incomes
+        .iter()
+        .chain(bills.iter())
+        .map(|(date, _)| *date)
+        .collect();
+    dates.push(opening_date);
+    dates.sort();
+    dates.dedup();
+
+    let mut balance = opening_balance.clone();
+    dates
+        .into_iter()
+        .map(|date| {
+            let incoming: BigDecimal = incomes
+                .iter()
+                .filter(|(d, _)| *d == date)
+                .map(|(_, amount)| amount.clone())
+                .sum();
+            let outgoing: BigDecimal = bills
+                .iter()
+                .filter(|(d, _)| *d == date)
+                .map(|(_, amount)| amount.clone())
+                .sum();
+
+            balance = balance.clone() + incoming.clone() - outgoing.clone();
+
+            TimelineDay {
+                date,
+                incoming,
+                outgoing,
+                balance: balance.clone(),
+            }
+        })
+        .collect()
+}
+
+/// Find the day with the lowest running balance (earliest wins on ties)
+pub fn find_lowest_day(timeline: &[TimelineDay]) -> Option<&TimelineDay> {
+    timeline
+        .iter()
+        .fold(None, |lowest: Option<&TimelineDay>, day| match lowest {
+            Some(l) if l.balance <= day.balance => Some(l),
+            _ => Some(day),
+        })
+}
+
+/// Calculate how much of a ledger bill has been paid
+/// Recorded payments win; without any, a bill marked paid counts in full
+pub fn calculate_bill_paid_amount(
+    bill_amount: &BigDecimal,
+    is_payed: bool,
+    payments: &[BigDecimal],
+) -> BigDecimal {
+    if !payments.is_empty() {
+        payments.iter().sum()
+    } else if is_payed {
+        bill_amount.clone()
+    } else {
+        BigDecimal::from(0)
+    }
+}
+
+/// When a ledger bill's money went out, as (date, amount)
+/// Recorded payments win; without any, a bill marked paid goes out in full on `paid_on`
+pub fn dated_bill_payments(
+    bill_amount: &BigDecimal,
+    is_payed: bool,
+    payments: &[(NaiveDate, BigDecimal)],
+    paid_on: NaiveDate,
+) -> Vec<(NaiveDate, BigDecimal)> {
+    if !payments.is_empty() {
+        payments.to_vec()
+    } else if is_payed {
+        vec![(paid_on, bill_amount.clone())]
+    } else {
+        Vec::new()
+    }
+}
+
+/// A DD/MM due date placed in the ledger's year
+/// Months before the ledger's month roll into the next year, so a December ledger can hold a January due date
+pub fn ledger_due_date(ledger_date: NaiveDate, day_month: &str) -> Option<NaiveDate> {
+    let (day, month) = day_month.trim().split_once('/')?;
+    let day: u32 = day.trim().parse().ok()?;
+    let month: u32 = month.trim().parse().ok()?;
+    let year = if month < ledger_date.month() { ledger_date.year() + 1 } else { ledger_date.year() };
+    NaiveDate::from_ymd_opt(year, month, day)
+}
+
+/// Calculate what is still owed on a ledger bill (never below zero)
+pub fn calculate_bill_remaining(bill_amount: &BigDecimal, paid_amount: &BigDecimal) -> BigDecimal {
+    let remaining = bill_amount - paid_amount;
+    if remaining < BigDecimal::from(0) {
+        BigDecimal::from(0)
+    } else {
+        remaining
+    }
+}
+
+/// Calculate the balance the bank should show on a given date
+/// Opening balance plus incomes received by then, minus bill payments made by then
+pub fn calculate_expected_balance(
+    opening_balance: &BigDecimal,
+    incomes: &[(NaiveDate, BigDecimal)],
+    paid_bills: &[(NaiveDate, BigDecimal)],
+    as_of: NaiveDate,
+) -> BigDecimal {
+    let received: BigDecimal = incomes
+        .iter()
+        .filter(|(date, _)| *date <= as_of)
+        .map(|(_, amount)| amount.clone())
+        .sum();
+    let paid: BigDecimal = paid_bills
+        .iter()
+        .filter(|(date, _)| *date <= as_of)
+        .map(|(_, amount)| amount.clone())
+        .sum();
+
+    opening_balance.clone() + received - paid
+}
+
+/// What a ledger hands on to the next one: its latest reconciled balance, else its net
+pub fn closing_balance(net: Option<&BigDecimal>, reconciled: Option<&BigDecimal>) -> BigDecimal {
+    reconciled.or(net).cloned().unwrap_or(BigDecimal::from(0))
+}
+
+/// Carry a closing balance forward through a chain of later ledgers
+/// Each successor is (id, income, expenses, latest reconciled balance); returns each one's new opening balance
+/// A reconciled successor hands on its reconciled balance rather than its recomputed net
+pub fn plan_opening_balance_ripple(
+    closing: &BigDecimal,
+    successors: &[(i32, BigDecimal, BigDecimal, Option<BigDecimal>)],
+) -> Vec<(i32, BigDecimal)> {
+    let mut carried = closing.clone();
+    successors
+        .iter()
+        .map(|(ledger_id, income, expenses, reconciled)| {
+            let opening = carried.clone();
+            let net = opening.clone() + income - expenses;
+            carried = closing_balance(Some(&net), reconciled.as_ref());
+            (*ledger_id, opening)
+        })
+        .collect()
+}
+
+/// One account's share of a ledger
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccountNet {
+    pub account_id: Option<i32>,
+    pub opening: BigDecimal,
+    pub income: BigDecimal,
+    pub expenses: BigDecimal,
+    pub transfers: BigDecimal,
+    pub net: BigDecimal,
+}
+
+/// Split a ledger's net across the accounts it draws from
+/// Incomes and expenses without an account land on the default account;
+/// transfers are (from, to, amount) and net to zero across the ledger
+pub fn calculate_account_nets(
+    openings: &[(Option<i32>, BigDecimal)],
+    incomes: &[(Option<i32>, BigDecimal)],
+    expenses: &[(Option<i32>, BigDecimal)],
+    transfers: &[(i32, i32, BigDecimal)],
+    default_account: Option<i32>,
+) -> Vec<AccountNet> {
+    let resolve = |account_id: Option<i32>| account_id.or(default_account);
+
+    let mut account_ids: Vec<Option<i32>> = Vec::new();
+    let all_ids = openings.iter().map(|(aid, _)| *aid)
+        .chain(incomes.iter().map(|(aid, _)| resolve(*aid)))
+        .chain(expenses.iter().map(|(aid, _)| resolve(*aid)))
+        .chain(transfers.iter().flat_map(|(from, to, _)| [Some(*from), Some(*to)]));
+    for account_id in all_ids {
+        if !account_ids.contains(&account_id) {
+            account_ids.push(account_id);
+        }
+    }
+
+    account_ids
+        .into_iter()
+        .map(|account_id| {
+            let opening: BigDecimal = openings.iter()
+                .filter(|(aid, _)| *aid == account_id)
+                .map(|(_, amount)| amount.clone())
+                .sum();
+            let income: BigDecimal = incomes.iter()
+                .filter(|(aid, _)| resolve(*aid) == account_id)
+                .map(|(_, amount)| amount.clone())
+                .sum();
+            let spent: BigDecimal = expenses.iter()
+                .filter(|(aid, _)| resolve(*aid) == account_id)
+                .map(|(_, amount)| amount.clone())
+                .sum();
+            let transferred: BigDecimal = transfers.iter()
+                .map(|(from, to, amount)| {
+                    if Some(*to) == account_id {
+                        amount.clone()
+                    } else if Some(*from) == account_id {
+                        -amount.clone()
+                    } else {
+                        BigDecimal::from(0)
+                    }
+                })
+                .sum();
+
+            AccountNet {
+                account_id,
+                net: &opening + &income - &spent + &transferred,
+                opening,
+                income,
+                expenses: spent,
+                transfers: transferred,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bigdecimal::BigDecimal;
+
+    #[test]
+    fn test_auto_allocate_picks_latest_paycheck_before_due() {
+        // Paid on the 1st and 15th; bills due on the 10th and 20th
+        let incomes = vec![
+            (1, NaiveDate::from_ymd_opt(2024, 1, 1).unwrap()),
+            (2, NaiveDate::from_ymd_opt(2024, 1, 15).unwrap()),
+        ];
+        let bills = vec![
+            (10, NaiveDate::from_ymd_opt(2024, 1, 10)),
+            (11, NaiveDate::from_ymd_opt(2024, 1, 20)),
+        ];
+
+        let result = auto_allocate_bills(&bills, &incomes);
+        assert_eq!(result, vec![(10, Some(1)), (11, Some(2))]);
+    }
+
+    #[test]
+    fn test_auto_allocate_includes_paycheck_on_due_date() {
+        let incomes = vec![
+            (1, NaiveDate::from_ymd_opt(2024, 1, 1).unwrap()),
+            (2, NaiveDate::from_ymd_opt(2024, 1, 15).unwrap()),
+        ];
+        let bills = vec![(10, NaiveDate::from_ymd_opt(2024, 1, 15))];
+
+        let result = auto_allocate_bills(&bills, &incomes);
+        assert_eq!(result, vec![(10, Some(2))]);
+    }
+
+    #[test]
+    fn test_auto_allocate_leaves_unmatched_bills_unassigned() {
+        // Bill due before the first paycheck, and a bill without a due date
+        let incomes = vec![(1, NaiveDate::from_ymd_opt(2024, 1, 15).unwrap())];
+        let bills = vec![
+            (10, NaiveDate::from_ymd_opt(2024, 1, 5)),
+            (11, None),
+        ];
+
+        let result = auto_allocate_bills(&bills, &incomes);
+        assert_eq!(result, vec![(10, None), (11, None)]);
+    }
+
+    #[test]
+    fn test_income_remaining() {
+        let allocations = vec![
+            (Some(1), BigDecimal::from(400)),
+            (Some(1), BigDecimal::from(100)),
+            (Some(2), BigDecimal::from(300)),
+            (None, BigDecimal::from(50)),
+        ];
+
+        let result = calculate_income_remaining(1, &BigDecimal::from(2000), &allocations);
+        assert_eq!(result, BigDecimal::from(1500));
+    }
+
+    #[test]
+    fn test_timeline_running_balance() {
+        // Start with 500, rent 1200 due on the 5th, paid 2000 on the 15th
+        let opening = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let incomes = vec![(NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(), BigDecimal::from(2000))];
+        let bills = vec![(NaiveDate::from_ymd_opt(2024, 1, 5).unwrap(), BigDecimal::from(1200))];
+
+        let timeline = build_cash_flow_timeline(opening, &BigDecimal::from(500), &incomes, &bills);
+        let balances: Vec<BigDecimal> = timeline.iter().map(|d| d.balance.clone()).collect();
+        assert_eq!(balances, vec![BigDecimal::from(500), BigDecimal::from(-700), BigDecimal::from(1300)]);
+    }
+
+    #[test]
+    fn test_timeline_groups_same_day_movements() {
+        let opening = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let day = NaiveDate::from_ymd_opt(2024, 1, 10).unwrap();
+        let incomes = vec![(day, BigDecimal::from(1000))];
+        let bills = vec![(day, BigDecimal::from(300)), (day, BigDecimal::from(200))];
+
+        let timeline = build_cash_flow_timeline(opening, &BigDecimal::from(0), &incomes, &bills);
+        assert_eq!(timeline.len(), 2);
+        assert_eq!(timeline[1].incoming, BigDecimal::from(1000));
+        assert_eq!(timeline[1].outgoing, BigDecimal::from(500));
+        assert_eq!(timeline[1].balance, BigDecimal::from(500));
+    }
+
+    #[test]
+    fn test_find_lowest_day() {
+        let opening = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let low_day = NaiveDate::from_ymd_opt(2024, 1, 5).unwrap();
+        let incomes = vec![(NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(), BigDecimal::from(2000))];
+        let bills = vec![(low_day, BigDecimal::from(1200))];
+
+        let timeline = build_cash_flow_timeline(opening, &BigDecimal::from(500), &incomes, &bills);
+        let lowest = find_lowest_day(&timeline).unwrap();
+        assert_eq!(lowest.date, low_day);
+        assert_eq!(lowest.balance, BigDecimal::from(-700));
+    }
+
+    #[test]
+    fn test_expected_balance_counts_only_received_incomes() {
+        let incomes = vec![
+            (NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(), BigDecimal::from(2000)),
+            (NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(), BigDecimal::from(2000)),
+        ];
+        let paid_bills = vec![
+            (NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(), BigDecimal::from(1200)),
+            (NaiveDate::from_ymd_opt(2024, 1, 5).unwrap(), BigDecimal::from(100)),
+        ];
+        let as_of = NaiveDate::from_ymd_opt(2024, 1, 10).unwrap();
+
+        let result = calculate_expected_balance(&BigDecimal::from(500), &incomes, &paid_bills, as_of);
+        assert_eq!(result, BigDecimal::from(1200)); // 500 + 2000 - 1300
+    }
+
+    #[test]
+    fn test_ledger_due_date_uses_ledger_year() {
+        let december = NaiveDate::from_ymd_opt(2026, 12, 1).unwrap();
+
+        assert_eq!(ledger_due_date(december, "15/12"), NaiveDate::from_ymd_opt(2026, 12, 15));
+        assert_eq!(ledger_due_date(december, "05/01"), NaiveDate::from_ymd_opt(2027, 1, 5));
+        assert_eq!(ledger_due_date(december, "31/02"), None);
+        assert_eq!(ledger_due_date(december, "15"), None);
+    }
+
+    #[test]
+    fn test_expected_balance_ignores_payments_after_as_of() {
+        let opened = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let as_of = NaiveDate::from_ymd_opt(2024, 1, 10).unwrap();
+        // One installment before the reconciliation date, one after; a hand-flagged bill falls on its due date
+        let installments = vec![
+            (NaiveDate::from_ymd_opt(2024, 1, 5).unwrap(), BigDecimal::from(300)),
+            (NaiveDate::from_ymd_opt(2024, 1, 20).unwrap(), BigDecimal::from(300)),
+        ];
+        let mut paid_bills = dated_bill_payments(&BigDecimal::from(600), false, &installments, opened);
+        paid_bills.extend(dated_bill_payments(&BigDecimal::from(50), true, &[], NaiveDate::from_ymd_opt(2024, 1, 25).unwrap()));
+
+        let result = calculate_expected_balance(&BigDecimal::from(1000), &[], &paid_bills, as_of);
+        assert_eq!(result, BigDecimal::from(700));
+        assert_eq!(paid_bills.len(), 3);
+    }
+
+    #[test]
+    fn test_bill_paid_amount_sums_partial_payments() {
+        let payments = vec![BigDecimal::from(100), BigDecimal::from(150)];
+
+        let result = calculate_bill_paid_amount(&BigDecimal::from(500), false, &payments);
+        assert_eq!(result, BigDecimal::from(250));
+    }
+
+    #[test]
+    fn test_bill_paid_amount_without_payments() {
+        let amount = BigDecimal::from(500);
+
+        assert_eq!(calculate_bill_paid_amount(&amount, true, &[]), BigDecimal::from(500));
+        assert_eq!(calculate_bill_paid_amount(&amount, false, &[]), BigDecimal::from(0));
+    }
+
+    #[test]
+    fn test_bill_remaining_never_negative() {
+        let amount = BigDecimal::from(500);
+
+        assert_eq!(calculate_bill_remaining(&amount, &BigDecimal::from(200)), BigDecimal::from(300));
+        assert_eq!(calculate_bill_remaining(&amount, &BigDecimal::from(600)), BigDecimal::from(0));
+    }
+
+    #[test]
+    fn test_opening_balance_ripple_chains_nets() {
+        // Closing 1000; next month +3000/-2500, then +3000/-3200
+        let successors = vec![
+            (2, BigDecimal::from(3000), BigDecimal::from(2500), None),
+            (3, BigDecimal::from(3000), BigDecimal::from(3200), None),
+        ];
+
+        let result = plan_opening_balance_ripple(&BigDecimal::from(1000), &successors);
+        assert_eq!(result, vec![(2, BigDecimal::from(1000)), (3, BigDecimal::from(1500))]);
+    }
+
+    #[test]
+    fn test_opening_balance_ripple_carries_reconciled_balance() {
+        // Predecessor nets 1000 but reconciled at 950; the middle ledger reconciled at 1480
+        let closing = closing_balance(Some(&BigDecimal::from(1000)), Some(&BigDecimal::from(950)));
+        let successors = vec![
+            (2, BigDecimal::from(3000), BigDecimal::from(2500), Some(BigDecimal::from(1480))),
+            (3, BigDecimal::from(3000), BigDecimal::from(3200), None),
+        ];
+
+        let result = plan_opening_balance_ripple(&closing, &successors);
+        assert_eq!(result, vec![(2, BigDecimal::from(950)), (3, BigDecimal::from(1480))]);
+        assert_eq!(closing_balance(Some(&BigDecimal::from(1000)), None), BigDecimal::from(1000));
+        assert_eq!(closing_balance(None, None), BigDecimal::from(0));
+    }
+
+    #[test]
+    fn test_account_nets_split_by_account() {
+        // Checking (1) opens at 1000, joint (2) at 500; rent from joint, pay into checking
+        let openings = vec![(Some(1), BigDecimal::from(1000)), (Some(2), BigDecimal::from(500))];
+        let incomes = vec![(None, BigDecimal::from(3000))];
+        let expenses = vec![(Some(2), BigDecimal::from(1500)), (None, BigDecimal::from(200))];
+        let transfers = vec![(1, 2, BigDecimal::from(1200))];
+
+        let nets = calculate_account_nets(&openings, &incomes, &expenses, &transfers, Some(1));
+
+        assert_eq!(nets.len(), 2);
+        assert_eq!(nets[0].net, BigDecimal::from(2600));
+        assert_eq!(nets[0].transfers, BigDecimal::from(-1200));
+        assert_eq!(nets[1].net, BigDecimal::from(200));
+    }
+
+    #[test]
+    fn test_account_nets_total_matches_ledger_net() {
+        let openings = vec![(Some(1), BigDecimal::from(1000)), (Some(2), BigDecimal::from(500))];
+        let incomes = vec![(Some(2), BigDecimal::from(800))];
+        let expenses = vec![(None, BigDecimal::from(300))];
+        let transfers = vec![(2, 1, BigDecimal::from(400))];
+
+        let nets = calculate_account_nets(&openings, &incomes, &expenses, &transfers, None);
+        let total: BigDecimal = nets.iter().map(|n| n.net.clone()).sum();
+
+        assert_eq!(total, BigDecimal::from(2000));
+        assert_eq!(nets.last().unwrap().account_id, None);
+    }
+}
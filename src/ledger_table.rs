@@ -1,15 +1,17 @@
 use std::cmp::Ordering;
+use std::collections::HashMap;
 use std::rc::Rc;
+use std::str::FromStr;
 use bigdecimal::BigDecimal;
 use chrono::{Datelike, Local, NaiveDate, ParseResult};
 use cursive::Cursive;
 use cursive::traits::*;
-use cursive::views::{Button, Dialog, EditView, HideableView, LinearLayout, ListView, Panel, TextArea};
+use cursive::views::{Button, Dialog, EditView, HideableView, LinearLayout, ListView, Panel, SelectView, TextArea};
 use cursive_table_view::{TableView, TableViewItem};
 use diesel::prelude::*;
 
 use crate::models;
-use crate::repositories::ledger_repo::LedgerRepo;
+use crate::dependency_container::LedgerRepos;
 use crate::db::PgConnector;
 use crate::ui_helpers::toggle_buttons_visible;
 
@@ -48,7 +50,11 @@ impl From<models::Ledger> for LedgerDisplay {
         LedgerDisplay {
             id: ledger.id,
             date: ledger.date,
-            name: ledger.name.unwrap_or_default(),
+            name: if ledger.is_closed {
+                format!("{} (closed)", ledger.name.unwrap_or_default())
+            } else {
+                ledger.name.unwrap_or_default()
+            },
             bank_balance: ledger.bank_balance,
             income: ledger.income,
             total: ledger.total.unwrap_or(BigDecimal::from(0)),
@@ -90,12 +96,12 @@ impl TableViewItem<BasicColumn> for LedgerDisplay {
 
 pub struct LedgerTableView {
     table: TableView<LedgerDisplay, BasicColumn>,
-    ledger_repo: Rc<LedgerRepo>,
+    repos: Rc<LedgerRepos>,
 }
 
 impl LedgerTableView {
-    pub fn new(ledger_repo: Rc<LedgerRepo>) -> Self {
-        let results = ledger_repo.find_all();
+    pub fn new(repos: Rc<LedgerRepos>) -> Self {
+        let results = repos.ledger.find_all();
 
         let ledger_displays: Vec<LedgerDisplay> = results
             .into_iter()
@@ -110,17 +116,17 @@ impl LedgerTableView {
                 .column(BasicColumn::Expenses, "Expenses", |c| c.width_percent(20))
                 .column(BasicColumn::Net, "Net", |c| c.width_percent(20))
                 .items(ledger_displays),
-            ledger_repo,
+            repos,
         }
     }
 
     pub fn add_table(self, siv: &mut Cursive) {
         siv.pop_layer();
 
-        let repo_add = Rc::clone(&self.ledger_repo);
-        let repo_duplicate = Rc::clone(&self.ledger_repo);
-        let repo_delete = Rc::clone(&self.ledger_repo);
-        let repo_view = Rc::clone(&self.ledger_repo);
+        let repo_add = Rc::clone(&self.repos);
+        let repo_duplicate = Rc::clone(&self.repos);
+        let repo_delete = Rc::clone(&self.repos);
+        let repo_view = Rc::clone(&self.repos);
 
         let buttons = LinearLayout::horizontal()
             .child(Button::new("Add", move |s| add_ledger_dialog(s, None, &repo_add)))
@@ -157,7 +163,7 @@ impl LedgerTableView {
     }
 }
 
-fn add_ledger_dialog(siv: &mut Cursive, existing: Option<LedgerDisplay>, ledger_repo: &Rc<LedgerRepo>) {
+fn add_ledger_dialog(siv: &mut Cursive, existing: Option<LedgerDisplay>, repos: &Rc<LedgerRepos>) {
     let is_duplicating = existing.is_some();
 
     let title = if is_duplicating { "Duplicate Ledger" } else { "Add Ledger" };
@@ -174,8 +180,22 @@ fn add_ledger_dialog(siv: &mut Cursive, existing: Option<LedgerDisplay>, ledger_
         .unwrap_or_default();
 
     let button_label = if is_duplicating { "Duplicate" } else { "Ok" };
-    
-    let repo = Rc::clone(ledger_repo);
+
+    // Duplicates carry forward from their source, new ledgers from the latest one
+    let default_previous_id = match existing.as_ref() {
+        Some(l) => Some(l.id),
+        None => repos.ledger.find_all().into_iter().max_by_key(|l| (l.date, l.id)).map(|l| l.id),
+    };
+    let (previous_select, opening_balance) = build_previous_ledger_select(repos, None, default_previous_id);
+
+    // Keep drawing from the same account as the ledger carried forward from
+    let default_account_id = default_previous_id
+        .and_then(|pid| repos.ledger.find_by_id(pid))
+        .and_then(|l| l.account_id);
+    let accounts = repos.account.find_all();
+    let account_select = crate::account_table::build_account_select(&accounts, default_account_id);
+
+    let repo = Rc::clone(repos);
 
     siv.add_layer(
         Dialog::new()
@@ -194,23 +214,78 @@ fn add_ledger_dialog(siv: &mut Cursive, existing: Option<LedgerDisplay>, ledger_
                 ListView::new()
                     .child("Date (DD/MM/YYYY)", EditView::new().content(ledger_date).with_name("date_input").fixed_width(20))
                     .child("Name", EditView::new().with_name("ledger_name").fixed_width(20))
+                    .child("Previous Ledger", previous_select.with_name("previous_ledger_select"))
+                    .child("Account", account_select.with_name("ledger_account_select"))
+                    .child("Opening Balance", EditView::new().content(opening_balance.to_string()).with_name("bank_balance_input").fixed_width(20))
                     .child("Notes", TextArea::new().content(notes_value).with_name("notes_input").min_size((40, 3)))
             )
     );
 }
 
-fn view_ledger_detail(siv: &mut Cursive, ledger_repo: &Rc<LedgerRepo>) {
+/// Popup of ledgers to carry an opening balance forward from
+/// Picking one fills `bank_balance_input` with its latest reconciled balance, or its net if never reconciled
+pub fn build_previous_ledger_select(repos: &Rc<LedgerRepos>, exclude_id: Option<i32>, selected_id: Option<i32>) -> (SelectView<Option<i32>>, BigDecimal) {
+    let mut candidates: Vec<models::Ledger> = repos.ledger.find_all()
+        .into_iter()
+        .filter(|l| Some(l.id) != exclude_id)
+        .collect();
+    candidates.sort_by(|a, b| b.date.cmp(&a.date).then(b.id.cmp(&a.id)));
+
+    let reconciled_balances: HashMap<i32, BigDecimal> = repos.ledger.find_latest_reconciliations()
+        .into_iter()
+        .map(|r| (r.ledger_id, r.actual_balance))
+        .collect();
+    let closings: Vec<(i32, BigDecimal)> = candidates
+        .iter()
+        .map(|l| (l.id, crate::ledger_logic::closing_balance(l.net.as_ref(), reconciled_balances.get(&l.id))))
+        .collect();
+
+    let mut select = SelectView::new().popup();
+    select.add_item("(None)", None);
+    for ledger in &candidates {
+        let net = ledger.net.clone().unwrap_or(BigDecimal::from(0));
+        let reconciled = reconciled_balances.get(&ledger.id)
+            .map_or(String::new(), |balance| format!(", reconciled ${}", balance));
+        let label = format!(
+            "{} - {} (net ${}{})",
+            ledger.name.clone().unwrap_or_default(),
+            ledger.date.format("%d/%m/%Y"),
+            net,
+            reconciled
+        );
+        select.add_item(label, Some(ledger.id));
+    }
+
+    let selected_index = selected_id
+        .and_then(|sid| candidates.iter().position(|l| l.id == sid))
+        .map_or(0, |i| i + 1);
+    let opening_balance = selected_id.map_or(BigDecimal::from(0), |sid| repos.ledger.closing_balance(sid));
+
+    let select = select
+        .selected(selected_index)
+        .on_submit(move |s, selected: &Option<i32>| {
+            let balance = selected
+                .and_then(|sid| closings.iter().find(|(lid, _)| *lid == sid))
+                .map_or(BigDecimal::from(0), |(_, closing)| closing.clone());
+            s.call_on_name("bank_balance_input", |v: &mut EditView| {
+                let _ = v.set_content(balance.to_string());
+            });
+        });
+
+    (select, opening_balance)
+}
+
+fn view_ledger_detail(siv: &mut Cursive, repos: &Rc<LedgerRepos>) {
     let selected = siv.call_on_name("ledger_table", |v: &mut TableView<LedgerDisplay, BasicColumn>| {
         v.borrow_item(v.item().unwrap()).cloned()
     }).flatten();
 
     if let Some(ledger) = selected {
-        // ledger_detail still uses PgConnector - will be migrated in p3.4
-        crate::ledger_detail::show_ledger_detail(siv, ledger.id, ledger_repo);
+        crate::ledger_detail::show_ledger_detail(siv, ledger.id, repos);
     }
 }
 
-fn get_form_values(s: &mut Cursive) -> (ParseResult<NaiveDate>, String, String) {
+fn get_form_values(s: &mut Cursive) -> (ParseResult<NaiveDate>, String, String, String, Option<i32>, Option<i32>) {
     let date_str = s.call_on_name("date_input", |v: &mut EditView| {
         v.get_content()
     }).unwrap();
@@ -225,25 +300,57 @@ fn get_form_values(s: &mut Cursive) -> (ParseResult<NaiveDate>, String, String)
         v.get_content().to_string()
     }).unwrap();
 
-    (parsed_date, ledger_name.to_string(), notes_str.to_string())
+    let balance_str = s.call_on_name("bank_balance_input", |v: &mut EditView| {
+        v.get_content()
+    }).unwrap();
+
+    let previous_id = s.call_on_name("previous_ledger_select", |v: &mut SelectView<Option<i32>>| {
+        v.selection()
+    }).unwrap().and_then(|sel| *sel);
+
+    let account_id = s.call_on_name("ledger_account_select", |v: &mut SelectView<Option<i32>>| {
+        v.selection()
+    }).unwrap().and_then(|sel| *sel);
+
+    (parsed_date, ledger_name.to_string(), notes_str.to_string(), balance_str.to_string(), previous_id, account_id)
 }
-fn add_ledger(s: &mut Cursive, ledger_repo: &Rc<LedgerRepo>) {
-    let (parsed_date, ledger_name, notes_str) = get_form_values(s);
+fn add_ledger(s: &mut Cursive, repos: &Rc<LedgerRepos>) {
+    let (parsed_date, ledger_name, notes_str, balance_str, previous_id, account_id) = get_form_values(s);
 
     if parsed_date.is_err() {
         s.add_layer(Dialog::info("Invalid date format. Use DD/MM/YYYY"));
         return;
     }
 
-    ledger_repo.create(
+    let balance = match BigDecimal::from_str(balance_str.trim()) {
+        Ok(b) => b,
+        Err(_) => {
+            s.add_layer(Dialog::info("Invalid balance format"));
+            return;
+        }
+    };
+
+    let new_ledger_record = repos.ledger.create(
         parsed_date.unwrap(),
         ledger_name.to_string(),
-        BigDecimal::from(0),
-        if notes_str.is_empty() { None } else { Some(notes_str) }
+        balance,
+        if notes_str.is_empty() { None } else { Some(notes_str) },
+        previous_id,
+        account_id
     );
 
+    // New ledgers start with this month's savings set-asides
+    repos.savings_goal
+        .generate_contributions(new_ledger_record.id, new_ledger_record.date)
+        .ok();
+
+    // Accounts open where they closed in the previous ledger
+    if let Some(pid) = previous_id {
+        repos.ledger.carry_account_openings(new_ledger_record.id, pid).ok();
+    }
+
     // Reload table
-    let ledger_displays = ledger_repo.find_all()
+    let ledger_displays = repos.ledger.find_all()
         .into_iter()
         .map(|l| l.into())
         .collect::<Vec<LedgerDisplay>>();
@@ -257,10 +364,10 @@ fn add_ledger(s: &mut Cursive, ledger_repo: &Rc<LedgerRepo>) {
     toggle_buttons_visible(s, ledger_count, TOGGLE_BUTTONS);
 }
 
-fn duplicate_ledger(s: &mut Cursive, selected: Option<LedgerDisplay>, ledger_repo: &Rc<LedgerRepo>) {
+fn duplicate_ledger(s: &mut Cursive, selected: Option<LedgerDisplay>, repos: &Rc<LedgerRepos>) {
 
     if let Some(ledger) = selected {
-        let (parsed_date, ledger_name, notes_str) = get_form_values(s);
+        let (parsed_date, ledger_name, notes_str, balance_str, previous_id, account_id) = get_form_values(s);
 
         if parsed_date.is_err() {
             s.add_layer(Dialog::info("Invalid date format. Use DD/MM/YYYY"));
@@ -272,15 +379,25 @@ fn duplicate_ledger(s: &mut Cursive, selected: Option<LedgerDisplay>, ledger_rep
             return;
         }
 
-        let new_ledger_record = ledger_repo.create(
+        let balance = match BigDecimal::from_str(balance_str.trim()) {
+            Ok(b) => b,
+            Err(_) => {
+                s.add_layer(Dialog::info("Invalid balance format"));
+                return;
+            }
+        };
+
+        let new_ledger_record = repos.ledger.create(
             parsed_date.unwrap(),
             ledger_name,
-            BigDecimal::from(0),
-            if notes_str.is_empty() { None } else { Some(notes_str) }
+            balance,
+            if notes_str.is_empty() { None } else { Some(notes_str) },
+            previous_id,
+            account_id
         );
 
         // Duplicate ledger bills using raw queries
-        let pg_connector = ledger_repo.pg_connector();
+        let pg_connector = repos.ledger.pg_connector();
         let old_ledger_bills = {
             use crate::schema::ledger_bills;
             let mut conn = pg_connector.get_connection();
@@ -290,12 +407,36 @@ fn duplicate_ledger(s: &mut Cursive, selected: Option<LedgerDisplay>, ledger_rep
                 .expect("Error loading ledger bills")
         };
 
+        // Installment loans follow their schedule and stop after the final payment
+        // Credit cards take the statement balance due in the new ledger's month
+        let loans = repos.loan.find_all();
+        let card_repo = &repos.credit_card;
+        let card_statements: Vec<(i32, BigDecimal)> = card_repo
+            .find_all()
+            .iter()
+            .filter_map(|c| c.bill_id.map(|bid| (bid, card_repo.statement_amount(c, new_ledger_record.date))))
+            .collect();
+
         {
             use crate::schema::ledger_bills;
             use crate::schema::bills;
             let mut conn = pg_connector.get_connection();
 
             for old_bill in old_ledger_bills {
+                let mut amount = old_bill.amount;
+                if let Some(loan) = loans.iter().find(|l| l.bill_id == old_bill.bill_id) {
+                    let schedule = crate::debt_logic::loan_schedule(loan);
+                    if crate::debt_logic::is_loan_finished(&schedule, new_ledger_record.date) {
+                        continue;
+                    }
+                    if let Some(row) = crate::debt_logic::payment_in_month(&schedule, new_ledger_record.date) {
+                        amount = row.payment.clone();
+                    }
+                }
+                if let Some((_, statement)) = card_statements.iter().find(|(bid, _)| *bid == old_bill.bill_id) {
+                    amount = statement.clone();
+                }
+
                 // Get the bill to check is_auto_pay
                 let bill = bills::table
                     .find(old_bill.bill_id)
@@ -310,10 +451,13 @@ fn duplicate_ledger(s: &mut Cursive, selected: Option<LedgerDisplay>, ledger_rep
                 let new_ledger_bill = models::NewLedgerBill {
                     ledger_id: new_ledger_record.id,
                     bill_id: old_bill.bill_id,
-                    amount: old_bill.amount,
+                    amount,
                     due_day: updated_due_day,
                     is_payed: bill.is_auto_pay,
                     notes: None,
+                    income_id: None,
+                    account_id: old_bill.account_id,
+                    tax_category: old_bill.tax_category.clone(),
                 };
 
                 diesel::insert_into(ledger_bills::table)
@@ -323,8 +467,18 @@ fn duplicate_ledger(s: &mut Cursive, selected: Option<LedgerDisplay>, ledger_rep
             }
         }
 
+        // New ledgers start with this month's savings set-asides
+        repos.savings_goal
+            .generate_contributions(new_ledger_record.id, new_ledger_record.date)
+            .ok();
+
+        // Accounts open where they closed in the previous ledger
+        if let Some(pid) = previous_id {
+            repos.ledger.carry_account_openings(new_ledger_record.id, pid).ok();
+        }
+
         // Reload table
-        let ledger_displays = ledger_repo.find_all()
+        let ledger_displays = repos.ledger.find_all()
             .into_iter()
             .map(|l| l.into())
             .collect::<Vec<LedgerDisplay>>();
@@ -340,20 +494,24 @@ fn duplicate_ledger(s: &mut Cursive, selected: Option<LedgerDisplay>, ledger_rep
     }
 }
 
-fn delete_ledger(siv: &mut Cursive, ledger_repo: &Rc<LedgerRepo>) {
+fn delete_ledger(siv: &mut Cursive, repos: &Rc<LedgerRepos>) {
     let selected = siv.call_on_name("ledger_table", |v: &mut TableView<LedgerDisplay, BasicColumn>| {
         v.borrow_item(v.item().unwrap()).cloned()
     }).flatten();
 
     if let Some(ledger) = selected {
-        let repo = Rc::clone(ledger_repo);
+        let repo = Rc::clone(repos);
         siv.add_layer(
             Dialog::text("Delete this ledger?")
                 .button("Yes", move |s| {
-                    repo.delete(ledger.id);
+                    if let Err(e) = repo.ledger.delete(ledger.id) {
+                        s.pop_layer();
+                        s.add_layer(Dialog::info(e.to_string()));
+                        return;
+                    }
 
                     // Reload table
-                    let ledger_displays = repo.find_all()
+                    let ledger_displays = repo.ledger.find_all()
                         .into_iter()
                         .map(|l| l.into())
                         .collect::<Vec<LedgerDisplay>>();
@@ -6,20 +6,43 @@ mod models;
 mod db;
 mod repositories;
 mod bill_table;
+mod category_table;
+mod savings_goal_table;
+mod debt_table;
+mod account_table;
 mod ledger_table;
 mod ledger_detail;
 mod common_layout;
 mod ui_helpers;
 mod pto_logic;
+mod ledger_logic;
+mod budget_logic;
+mod debt_logic;
+mod net_worth_logic;
+mod card_logic;
+mod income_logic;
+mod tax_logic;
+mod tax_report;
+mod trend_logic;
+mod trend_report;
+mod forecast_logic;
+mod forecast_report;
+mod alert_logic;
+mod alerts;
+mod ics_logic;
+mod ics_export;
+mod subscription_logic;
+mod subscription_report;
+mod bill_history_logic;
 mod pto_table;
 mod pto_detail;
+mod dashboard;
 mod configuration_manager;
 mod dependency_container;
 
 use cursive::Cursive;
 use cursive::theme::{BorderStyle, Palette};
 use cursive::traits::With;
-use cursive::views::TextView;
 use simplelog::*;
 use std::fs::File;
 use std::rc::Rc;
@@ -96,8 +119,21 @@ fn main() {
                 println!("money-bae {} - Personal Finance Tracker", VERSION);
                 println!("\nUsage: money-bae [OPTIONS]\n");
                 println!("Options:");
-                println!("  -v, --version    Show version information");
-                println!("  -h, --help       Show this help message");
+                println!("  -v, --version             Show version information");
+                println!("  -h, --help                Show this help message");
+                println!("      --export-ics [FILE]   Write unpaid bill due dates as calendar events");
+                println!("                            (default {})", ics_export::default_export_path());
+                return;
+            }
+            "--export-ics" => {
+                let path = args.get(2).cloned().unwrap_or_else(ics_export::default_export_path);
+                match ics_export::export_bill_reminders(&dc.ledger_repo(), &path) {
+                    Ok(count) => println!("Wrote {} bill reminders to {}", count, path),
+                    Err(e) => {
+                        eprintln!("Could not write {}: {}", path, e);
+                        std::process::exit(1);
+                    }
+                }
                 return;
             }
             _ => {
@@ -142,7 +178,8 @@ fn main() {
     });
 
     siv.add_global_callback('q', |s| s.quit());
-    siv.add_global_callback('h', |s| clear(s));
+    let dc_home = Rc::clone(&dc);
+    siv.add_global_callback('h', move |s| show_home(s, &dc_home));
     
     let dc_income = Rc::clone(&dc);
     siv.add_global_callback('i', move |s| show_income_table(s, &dc_income));
@@ -156,32 +193,50 @@ fn main() {
     let dc_pto = Rc::clone(&dc);
     siv.add_global_callback('p', move |s| show_pto_view(s, &dc_pto));
 
+    let dc_savings = Rc::clone(&dc);
+    siv.add_global_callback('s', move |s| show_savings_goals(s, &dc_savings));
 
-    let main_menu = common_layout::create_screen(
-        &format!("money-bae v{}", VERSION),
-        TextView::new("Personal Finance Tracker\n\nPress a key to begin:").center(),
-        &common_layout::standard_footer()
-    );
+    let dc_debt = Rc::clone(&dc);
+    siv.add_global_callback('d', move |s| show_debts(s, &dc_debt));
+
+    let dc_net_worth = Rc::clone(&dc);
+    siv.add_global_callback('n', move |s| show_net_worth(s, &dc_net_worth));
+
+    let dc_tax = Rc::clone(&dc);
+    siv.add_global_callback('t', move |s| show_tax_summary(s, &dc_tax));
+
+    let dc_trends = Rc::clone(&dc);
+    siv.add_global_callback('r', move |s| show_trends(s, &dc_trends));
 
-    siv.add_layer(main_menu);
+    let dc_forecast = Rc::clone(&dc);
+    siv.add_global_callback('f', move |s| show_forecast(s, &dc_forecast));
+
+    let dc_subscriptions = Rc::clone(&dc);
+    siv.add_global_callback('u', move |s| show_subscriptions(s, &dc_subscriptions));
+
+    let dc_alerts = Rc::clone(&dc);
+    siv.add_global_callback('a', move |s| show_alerts(s, &dc_alerts));
+
+    show_home(&mut siv, &dc);
+    alerts::show_startup_alerts(&mut siv, &dc.ledger_repos());
 
     siv.run();
 }
 
 fn show_income_table(siv: &mut Cursive, dc: &DependencyContainer) {
-    let income_table = income_table::IncomeTableView::new(dc.income_repo());
+    let income_table = income_table::IncomeTableView::new(dc.income_repos());
 
     income_table.add_table(siv);
 }
 
 fn show_bill_table(siv: &mut Cursive, dc: &DependencyContainer) {
-    let bill_table = bill_table::BillTableView::new(dc.bill_repo());
+    let bill_table = bill_table::BillTableView::new(dc.bill_repo(), dc.category_repo(), dc.envelope_repo(), dc.loan_repo(), dc.ledger_repo());
 
     bill_table.add_table(siv);
 }
 
 fn show_ledger_table(siv: &mut Cursive, dc: &DependencyContainer) {
-    let ledger_table = ledger_table::LedgerTableView::new(dc.ledger_repo());
+    let ledger_table = ledger_table::LedgerTableView::new(dc.ledger_repos());
 
     ledger_table.add_table(siv);
 }
@@ -191,14 +246,46 @@ fn show_pto_view(siv: &mut Cursive, dc: &DependencyContainer) {
     pto_table::show_pto_table_view(siv, &dc.pto_repo(), &dc.pto_plan_repo(), &dc.holiday_hours_repo());
 }
 
-fn clear(siv: &mut Cursive){
+fn show_savings_goals(siv: &mut Cursive, dc: &DependencyContainer) {
+    savings_goal_table::show_savings_goal_table(siv, &dc.savings_goal_repo(), &dc.bill_repo());
+}
+
+fn show_debts(siv: &mut Cursive, dc: &DependencyContainer) {
+    debt_table::show_debt_table(siv, &dc.debt_repo(), &dc.bill_repo());
+}
+
+fn show_net_worth(siv: &mut Cursive, dc: &DependencyContainer) {
+    account_table::show_account_table(siv, &dc.account_repos());
+}
+
+fn show_tax_summary(siv: &mut Cursive, dc: &DependencyContainer) {
+    tax_report::show_tax_report(siv, &dc.tax_repo(), &dc.income_repo());
+}
+
+fn show_trends(siv: &mut Cursive, dc: &DependencyContainer) {
+    trend_report::show_trend_report(siv, &dc.ledger_repo());
+}
+
+fn show_forecast(siv: &mut Cursive, dc: &DependencyContainer) {
+    forecast_report::show_forecast(siv, &dc.ledger_repo(), &dc.bill_repo(), &dc.loan_repo(), &dc.income_source_repo(), &dc.configuration_manager());
+}
+
+fn show_subscriptions(siv: &mut Cursive, dc: &DependencyContainer) {
+    subscription_report::show_subscriptions(siv, &dc.bill_repo(), &dc.ledger_repo());
+}
+
+fn show_alerts(siv: &mut Cursive, dc: &DependencyContainer) {
+    alerts::show_alerts(siv, &dc.ledger_repos());
+}
+
+fn show_home(siv: &mut Cursive, dc: &Rc<DependencyContainer>) {
     siv.pop_layer();
 
-    let main_menu = common_layout::create_screen(
+    let home = common_layout::create_screen(
         &format!("money-bae v{}", VERSION),
-        TextView::new("Personal Finance Tracker\n\nPress a key to begin:").center(),
-        &common_layout::standard_footer()
+        dashboard::build_dashboard(dc),
+        &common_layout::badged_footer(alerts::footer_badge(&dc.ledger_repos()))
     );
 
-    siv.add_layer(main_menu);
+    siv.add_layer(home);
 }
@@ -50,6 +50,227 @@ impl From<PtoStatus> for String {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AccountType {
+    Checking,
+    Savings,
+    Brokerage,
+    Retirement,
+    Property,
+    CreditCard,
+    Loan,
+}
+
+impl AccountType {
+    pub fn all() -> Vec<AccountType> {
+        vec![
+            AccountType::Checking,
+            AccountType::Savings,
+            AccountType::Brokerage,
+            AccountType::Retirement,
+            AccountType::Property,
+            AccountType::CreditCard,
+            AccountType::Loan,
+        ]
+    }
+
+    /// Credit cards and loans count against net worth
+    pub fn is_liability(&self) -> bool {
+        matches!(self, AccountType::CreditCard | AccountType::Loan)
+    }
+}
+
+impl fmt::Display for AccountType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AccountType::Checking => write!(f, "Checking"),
+            AccountType::Savings => write!(f, "Savings"),
+            AccountType::Brokerage => write!(f, "Brokerage"),
+            AccountType::Retirement => write!(f, "Retirement"),
+            AccountType::Property => write!(f, "Property"),
+            AccountType::CreditCard => write!(f, "Credit Card"),
+            AccountType::Loan => write!(f, "Loan"),
+        }
+    }
+}
+
+impl From<String> for AccountType {
+    fn from(s: String) -> Self {
+        match s.as_str() {
+            "Savings" => AccountType::Savings,
+            "Brokerage" => AccountType::Brokerage,
+            "Retirement" => AccountType::Retirement,
+            "Property" => AccountType::Property,
+            "Credit Card" => AccountType::CreditCard,
+            "Loan" => AccountType::Loan,
+            _ => AccountType::Checking,
+        }
+    }
+}
+
+impl From<AccountType> for String {
+    fn from(account_type: AccountType) -> Self {
+        account_type.to_string()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PaySchedule {
+    Weekly,
+    BiWeekly,
+    SemiMonthly,
+    Monthly,
+}
+
+impl PaySchedule {
+    pub fn all() -> Vec<PaySchedule> {
+        vec![
+            PaySchedule::Weekly,
+            PaySchedule::BiWeekly,
+            PaySchedule::SemiMonthly,
+            PaySchedule::Monthly,
+        ]
+    }
+}
+
+impl fmt::Display for PaySchedule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PaySchedule::Weekly => write!(f, "Weekly"),
+            PaySchedule::BiWeekly => write!(f, "Bi-Weekly"),
+            PaySchedule::SemiMonthly => write!(f, "Semi-Monthly"),
+            PaySchedule::Monthly => write!(f, "Monthly"),
+        }
+    }
+}
+
+impl From<String> for PaySchedule {
+    fn from(s: String) -> Self {
+        match s.as_str() {
+            "Weekly" => PaySchedule::Weekly,
+            "Bi-Weekly" => PaySchedule::BiWeekly,
+            "Semi-Monthly" => PaySchedule::SemiMonthly,
+            _ => PaySchedule::Monthly,
+        }
+    }
+}
+
+impl From<PaySchedule> for String {
+    fn from(schedule: PaySchedule) -> Self {
+        schedule.to_string()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IncomeCategory {
+    Salary,
+    SideGig,
+    Reimbursement,
+    Interest,
+    Dividends,
+    Gift,
+    Other,
+}
+
+impl IncomeCategory {
+    pub fn all() -> Vec<IncomeCategory> {
+        vec![
+            IncomeCategory::Salary,
+            IncomeCategory::SideGig,
+            IncomeCategory::Reimbursement,
+            IncomeCategory::Interest,
+            IncomeCategory::Dividends,
+            IncomeCategory::Gift,
+            IncomeCategory::Other,
+        ]
+    }
+}
+
+impl fmt::Display for IncomeCategory {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            IncomeCategory::Salary => write!(f, "Salary"),
+            IncomeCategory::SideGig => write!(f, "Side Gig"),
+            IncomeCategory::Reimbursement => write!(f, "Reimbursement"),
+            IncomeCategory::Interest => write!(f, "Interest"),
+            IncomeCategory::Dividends => write!(f, "Dividends"),
+            IncomeCategory::Gift => write!(f, "Gift"),
+            IncomeCategory::Other => write!(f, "Other"),
+        }
+    }
+}
+
+impl From<String> for IncomeCategory {
+    fn from(s: String) -> Self {
+        match s.as_str() {
+            "Salary" => IncomeCategory::Salary,
+            "Side Gig" => IncomeCategory::SideGig,
+            "Reimbursement" => IncomeCategory::Reimbursement,
+            "Interest" => IncomeCategory::Interest,
+            "Dividends" => IncomeCategory::Dividends,
+            "Gift" => IncomeCategory::Gift,
+            _ => IncomeCategory::Other,
+        }
+    }
+}
+
+impl From<IncomeCategory> for String {
+    fn from(category: IncomeCategory) -> Self {
+        category.to_string()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TaxCategory {
+    Deductible,
+    Charitable,
+    Medical,
+    HsaEligible,
+    Business,
+}
+
+impl TaxCategory {
+    pub fn all() -> Vec<TaxCategory> {
+        vec![
+            TaxCategory::Deductible,
+            TaxCategory::Charitable,
+            TaxCategory::Medical,
+            TaxCategory::HsaEligible,
+            TaxCategory::Business,
+        ]
+    }
+}
+
+impl fmt::Display for TaxCategory {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TaxCategory::Deductible => write!(f, "Deductible"),
+            TaxCategory::Charitable => write!(f, "Charitable"),
+            TaxCategory::Medical => write!(f, "Medical"),
+            TaxCategory::HsaEligible => write!(f, "HSA-Eligible"),
+            TaxCategory::Business => write!(f, "Business"),
+        }
+    }
+}
+
+impl From<String> for TaxCategory {
+    fn from(s: String) -> Self {
+        match s.as_str() {
+            "Charitable" => TaxCategory::Charitable,
+            "Medical" => TaxCategory::Medical,
+            "HSA-Eligible" => TaxCategory::HsaEligible,
+            "Business" => TaxCategory::Business,
+            _ => TaxCategory::Deductible,
+        }
+    }
+}
+
+impl From<TaxCategory> for String {
+    fn from(category: TaxCategory) -> Self {
+        category.to_string()
+    }
+}
+
 
 #[derive(Queryable, Selectable, Clone, Debug)]
 #[diesel(table_name = crate::schema::incomes)]
@@ -61,6 +282,12 @@ pub struct Income {
     pub created_at: chrono::NaiveDateTime,
     pub ledger_id: Option<i32>,
     pub notes: Option<String>,
+    pub account_id: Option<i32>,
+    pub income_source_id: Option<i32>,
+    pub is_expected: bool,
+    pub tax_category: Option<String>,
+    pub scheduled_date: Option<NaiveDate>,
+    pub category: Option<String>,
 }
 
 #[derive(Insertable)]
@@ -69,6 +296,12 @@ pub struct NewIncome {
     pub date: NaiveDate,
     pub amount: BigDecimal,
     pub notes: Option<String>,
+    pub account_id: Option<i32>,
+    pub income_source_id: Option<i32>,
+    pub is_expected: bool,
+    pub tax_category: Option<String>,
+    pub scheduled_date: Option<NaiveDate>,
+    pub category: Option<String>,
 }
 
 #[derive(Queryable, Selectable, Clone, Debug)]
@@ -82,6 +315,9 @@ pub struct Bill {
     pub is_auto_pay: bool,
     pub created_at: chrono::NaiveDateTime,
     pub notes: Option<String>,
+    pub category_id: Option<i32>,
+    pub envelope_id: Option<i32>,
+    pub tax_category: Option<String>,
 }
 
 #[derive(Insertable)]
@@ -92,6 +328,85 @@ pub struct NewBill {
     pub due_day: Option<NaiveDate>,
     pub is_auto_pay: bool,
     pub notes: Option<String>,
+    pub category_id: Option<i32>,
+    pub envelope_id: Option<i32>,
+    pub tax_category: Option<String>,
+}
+
+#[derive(Queryable, Selectable, Clone, Debug)]
+#[diesel(table_name = crate::schema::bill_price_changes)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct BillPriceChange {
+    pub id: i32,
+    pub bill_id: i32,
+    pub changed_on: NaiveDate,
+    pub old_amount: BigDecimal,
+    pub new_amount: BigDecimal,
+    pub created_at: chrono::NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = crate::schema::bill_price_changes)]
+pub struct NewBillPriceChange {
+    pub bill_id: i32,
+    pub changed_on: NaiveDate,
+    pub old_amount: BigDecimal,
+    pub new_amount: BigDecimal,
+}
+
+#[derive(Queryable, Selectable, Clone, Debug)]
+#[diesel(table_name = crate::schema::envelopes)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Envelope {
+    pub id: i32,
+    pub name: String,
+    pub notes: Option<String>,
+    pub created_at: chrono::NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = crate::schema::envelopes)]
+pub struct NewEnvelope {
+    pub name: String,
+    pub notes: Option<String>,
+}
+
+#[derive(Queryable, Selectable, Clone, Debug)]
+#[diesel(table_name = crate::schema::ledger_envelopes)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct LedgerEnvelope {
+    pub id: i32,
+    pub ledger_id: i32,
+    pub envelope_id: i32,
+    pub allocated: BigDecimal,
+    pub created_at: chrono::NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = crate::schema::ledger_envelopes)]
+pub struct NewLedgerEnvelope {
+    pub ledger_id: i32,
+    pub envelope_id: i32,
+    pub allocated: BigDecimal,
+}
+
+#[derive(Queryable, Selectable, Clone, Debug)]
+#[diesel(table_name = crate::schema::categories)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Category {
+    pub id: i32,
+    pub name: String,
+    pub parent_id: Option<i32>,
+    pub monthly_budget: Option<BigDecimal>,
+    pub created_at: chrono::NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = crate::schema::categories)]
+pub struct NewCategory {
+    pub name: String,
+    pub parent_id: Option<i32>,
+    pub monthly_budget: Option<BigDecimal>,
 }
 
 #[derive(Queryable, Selectable, Clone, Debug)]
@@ -108,6 +423,10 @@ pub struct Ledger {
     pub name: Option<String>,
     pub total: Option<BigDecimal>,
     pub notes: Option<String>,
+    pub is_closed: bool,
+    pub closed_at: Option<chrono::NaiveDateTime>,
+    pub previous_ledger_id: Option<i32>,
+    pub account_id: Option<i32>,
 }
 
 #[derive(Insertable)]
@@ -117,6 +436,8 @@ pub struct NewLedger {
     pub name: String,
     pub bank_balance: BigDecimal,
     pub notes: Option<String>,
+    pub previous_ledger_id: Option<i32>,
+    pub account_id: Option<i32>,
 }
 
 #[derive(Queryable, Selectable, Clone, Debug)]
@@ -131,6 +452,9 @@ pub struct LedgerBill {
     pub is_payed: bool,
     pub created_at: chrono::NaiveDateTime,
     pub notes: Option<String>,
+    pub income_id: Option<i32>,
+    pub account_id: Option<i32>,
+    pub tax_category: Option<String>,
 }
 
 #[derive(Insertable)]
@@ -142,6 +466,54 @@ pub struct NewLedgerBill {
     pub due_day: Option<NaiveDate>,
     pub is_payed: bool,
     pub notes: Option<String>,
+    pub income_id: Option<i32>,
+    pub account_id: Option<i32>,
+    pub tax_category: Option<String>,
+}
+
+#[derive(Queryable, Selectable, Clone, Debug)]
+#[diesel(table_name = crate::schema::ledger_bill_payments)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct LedgerBillPayment {
+    pub id: i32,
+    pub ledger_bill_id: i32,
+    pub date: NaiveDate,
+    pub amount: BigDecimal,
+    pub notes: Option<String>,
+    pub created_at: chrono::NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = crate::schema::ledger_bill_payments)]
+pub struct NewLedgerBillPayment {
+    pub ledger_bill_id: i32,
+    pub date: NaiveDate,
+    pub amount: BigDecimal,
+    pub notes: Option<String>,
+}
+
+#[derive(Queryable, Selectable, Clone, Debug)]
+#[diesel(table_name = crate::schema::ledger_reconciliations)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct LedgerReconciliation {
+    pub id: i32,
+    pub ledger_id: i32,
+    pub date: NaiveDate,
+    pub actual_balance: BigDecimal,
+    pub expected_balance: BigDecimal,
+    pub delta: Option<BigDecimal>,
+    pub notes: Option<String>,
+    pub created_at: chrono::NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = crate::schema::ledger_reconciliations)]
+pub struct NewLedgerReconciliation {
+    pub ledger_id: i32,
+    pub date: NaiveDate,
+    pub actual_balance: BigDecimal,
+    pub expected_balance: BigDecimal,
+    pub notes: Option<String>,
 }
 
 #[derive(Queryable, Selectable, Clone, Debug)]
@@ -215,3 +587,297 @@ pub struct NewHolidayHours {
     pub name: String,
     pub hours: BigDecimal,
 }
+
+#[derive(Queryable, Selectable, Clone, Debug)]
+#[diesel(table_name = crate::schema::savings_goals)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct SavingsGoal {
+    pub id: i32,
+    pub name: String,
+    pub target_amount: BigDecimal,
+    pub target_date: NaiveDate,
+    pub bill_id: Option<i32>,
+    pub notes: Option<String>,
+    pub created_at: chrono::NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = crate::schema::savings_goals)]
+pub struct NewSavingsGoal {
+    pub name: String,
+    pub target_amount: BigDecimal,
+    pub target_date: NaiveDate,
+    pub bill_id: Option<i32>,
+    pub notes: Option<String>,
+}
+
+#[derive(Queryable, Selectable, Clone, Debug)]
+#[diesel(table_name = crate::schema::ledger_savings_contributions)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct LedgerSavingsContribution {
+    pub id: i32,
+    pub ledger_id: i32,
+    pub savings_goal_id: i32,
+    pub amount: BigDecimal,
+    pub is_transferred: bool,
+    pub created_at: chrono::NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = crate::schema::ledger_savings_contributions)]
+pub struct NewLedgerSavingsContribution {
+    pub ledger_id: i32,
+    pub savings_goal_id: i32,
+    pub amount: BigDecimal,
+}
+
+#[derive(Queryable, Selectable, Clone, Debug)]
+#[diesel(table_name = crate::schema::debts)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Debt {
+    pub id: i32,
+    pub name: String,
+    pub balance: BigDecimal,
+    pub apr: BigDecimal,
+    pub minimum_payment: BigDecimal,
+    pub bill_id: Option<i32>,
+    pub notes: Option<String>,
+    pub created_at: chrono::NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = crate::schema::debts)]
+pub struct NewDebt {
+    pub name: String,
+    pub balance: BigDecimal,
+    pub apr: BigDecimal,
+    pub minimum_payment: BigDecimal,
+    pub bill_id: Option<i32>,
+    pub notes: Option<String>,
+}
+
+#[derive(Queryable, Selectable, Clone, Debug)]
+#[diesel(table_name = crate::schema::debt_payments)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct DebtPayment {
+    pub id: i32,
+    pub debt_id: i32,
+    pub ledger_bill_id: i32,
+    pub amount: BigDecimal,
+    pub interest: BigDecimal,
+    pub principal: BigDecimal,
+    pub created_at: chrono::NaiveDateTime,
+}
+
+#[derive(Queryable, Selectable, Clone, Debug)]
+#[diesel(table_name = crate::schema::loans)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Loan {
+    pub id: i32,
+    pub bill_id: i32,
+    pub principal: BigDecimal,
+    pub annual_rate: BigDecimal,
+    pub term_months: i32,
+    pub start_date: NaiveDate,
+    pub notes: Option<String>,
+    pub created_at: chrono::NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = crate::schema::loans)]
+pub struct NewLoan {
+    pub bill_id: i32,
+    pub principal: BigDecimal,
+    pub annual_rate: BigDecimal,
+    pub term_months: i32,
+    pub start_date: NaiveDate,
+    pub notes: Option<String>,
+}
+
+#[derive(Queryable, Selectable, Clone, Debug)]
+#[diesel(table_name = crate::schema::accounts)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Account {
+    pub id: i32,
+    pub name: String,
+    pub account_type: String,
+    pub notes: Option<String>,
+    pub created_at: chrono::NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = crate::schema::accounts)]
+pub struct NewAccount {
+    pub name: String,
+    pub account_type: String,
+    pub notes: Option<String>,
+}
+
+#[derive(Queryable, Selectable, Clone, Debug)]
+#[diesel(table_name = crate::schema::account_balances)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct AccountBalance {
+    pub id: i32,
+    pub account_id: i32,
+    pub date: NaiveDate,
+    pub balance: BigDecimal,
+    pub created_at: chrono::NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = crate::schema::account_balances)]
+pub struct NewAccountBalance {
+    pub account_id: i32,
+    pub date: NaiveDate,
+    pub balance: BigDecimal,
+}
+
+#[derive(Queryable, Selectable, Clone, Debug)]
+#[diesel(table_name = crate::schema::ledger_accounts)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct LedgerAccount {
+    pub id: i32,
+    pub ledger_id: i32,
+    pub account_id: i32,
+    pub opening_balance: BigDecimal,
+    pub created_at: chrono::NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = crate::schema::ledger_accounts)]
+pub struct NewLedgerAccount {
+    pub ledger_id: i32,
+    pub account_id: i32,
+    pub opening_balance: BigDecimal,
+}
+
+#[derive(Queryable, Selectable, Clone, Debug)]
+#[diesel(table_name = crate::schema::ledger_transfers)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct LedgerTransfer {
+    pub id: i32,
+    pub ledger_id: i32,
+    pub from_account_id: i32,
+    pub to_account_id: i32,
+    pub amount: BigDecimal,
+    pub date: NaiveDate,
+    pub notes: Option<String>,
+    pub created_at: chrono::NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = crate::schema::ledger_transfers)]
+pub struct NewLedgerTransfer {
+    pub ledger_id: i32,
+    pub from_account_id: i32,
+    pub to_account_id: i32,
+    pub amount: BigDecimal,
+    pub date: NaiveDate,
+    pub notes: Option<String>,
+}
+
+#[derive(Queryable, Selectable, Clone, Debug)]
+#[diesel(table_name = crate::schema::credit_cards)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct CreditCard {
+    pub id: i32,
+    pub bill_id: Option<i32>,
+    pub closing_day: i32,
+    pub due_day: i32,
+    pub notes: Option<String>,
+    pub created_at: chrono::NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = crate::schema::credit_cards)]
+pub struct NewCreditCard {
+    pub account_id: i32,
+    pub bill_id: Option<i32>,
+    pub closing_day: i32,
+    pub due_day: i32,
+    pub notes: Option<String>,
+}
+
+#[derive(Queryable, Selectable, Clone, Debug)]
+#[diesel(table_name = crate::schema::card_charges)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct CardCharge {
+    pub id: i32,
+    pub date: NaiveDate,
+    pub amount: BigDecimal,
+    pub description: String,
+    pub created_at: chrono::NaiveDateTime,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = crate::schema::card_charges)]
+pub struct NewCardCharge {
+    pub credit_card_id: i32,
+    pub date: NaiveDate,
+    pub amount: BigDecimal,
+    pub description: String,
+}
+
+#[derive(Queryable, Selectable, Clone, Debug)]
+#[diesel(table_name = crate::schema::income_sources)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct IncomeSource {
+    pub id: i32,
+    pub name: String,
+    pub schedule: String,
+    pub anchor_date: NaiveDate,
+    pub second_day: Option<i32>,
+    pub expected_amount: BigDecimal,
+    pub account_id: Option<i32>,
+    pub is_active: bool,
+    pub notes: Option<String>,
+    pub created_at: chrono::NaiveDateTime,
+    pub category: String,
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = crate::schema::income_sources)]
+pub struct NewIncomeSource {
+    pub name: String,
+    pub schedule: String,
+    pub anchor_date: NaiveDate,
+    pub second_day: Option<i32>,
+    pub expected_amount: BigDecimal,
+    pub account_id: Option<i32>,
+    pub is_active: bool,
+    pub notes: Option<String>,
+    pub category: String,
+}
+
+#[derive(Queryable, Selectable, Clone, Debug)]
+#[diesel(table_name = crate::schema::paystubs)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct Paystub {
+    pub id: i32,
+    pub income_id: i32,
+    pub gross: BigDecimal,
+    pub federal_tax: BigDecimal,
+    pub state_tax: BigDecimal,
+    pub social_security: BigDecimal,
+    pub medicare: BigDecimal,
+    pub retirement_401k: BigDecimal,
+    pub hsa: BigDecimal,
+    pub insurance: BigDecimal,
+    pub other_deductions: BigDecimal,
+    pub created_at: chrono::NaiveDateTime,
+}
+
+#[derive(Insertable, Clone)]
+#[diesel(table_name = crate::schema::paystubs)]
+pub struct NewPaystub {
+    pub income_id: i32,
+    pub gross: BigDecimal,
+    pub federal_tax: BigDecimal,
+    pub state_tax: BigDecimal,
+    pub social_security: BigDecimal,
+    pub medicare: BigDecimal,
+    pub retirement_401k: BigDecimal,
+    pub hsa: BigDecimal,
+    pub insurance: BigDecimal,
+    pub other_deductions: BigDecimal,
+}
@@ -0,0 +1,121 @@
+use std::collections::BTreeSet;
+use bigdecimal::BigDecimal;
+use chrono::NaiveDate;
+
+/// One account's balance on a date; liabilities hold the positive amount owed
+#[derive(Debug, Clone)]
+pub struct BalanceSnapshot {
+    pub account_id: i32,
+    pub is_liability: bool,
+    pub date: NaiveDate,
+    pub balance: BigDecimal,
+}
+
+/// Assets, liabilities and net worth on a date
+#[derive(Debug, Clone, PartialEq)]
+pub struct NetWorthPoint {
+    pub date: NaiveDate,
+    pub assets: BigDecimal,
+    pub liabilities: BigDecimal,
+    pub net_worth: BigDecimal,
+}
+
+/// Each account's most recent snapshot on or before a date
+/// Accounts with no snapshot yet are left out
+pub fn balances_as_of(snapshots: &[BalanceSnapshot], as_of: NaiveDate) -> Vec<&BalanceSnapshot> {
+    let account_ids: BTreeSet<i32> = snapshots.iter().map(|s| s.account_id).collect();
+
+    account_ids
+        .into_iter()
+        .filter_map(|account_id| {
+            snapshots
+                .iter()
+                .filter(|s| s.account_id == account_id && s.date <= as_of)
+                .max_by_key(|s| s.date)
+        })
+        .collect()
+}
+
+/// Assets minus liabilities using each account's latest balance as of a date
+pub fn calculate_net_worth(snapshots: &[BalanceSnapshot], as_of: NaiveDate) -> NetWorthPoint {
+    let latest = balances_as_of(snapshots, as_of);
+
+    let assets: BigDecimal = latest
+        .iter()
+        .filter(|s| !s.is_liability)
+        .map(|s| s.balance.clone())
+        .sum();
+    let liabilities: BigDecimal = latest
+        .iter()
+        .filter(|s| s.is_liability)
+        .map(|s| s.balance.clone())
+        .sum();
+
+    NetWorthPoint {
+        date: as_of,
+        net_worth: &assets - &liabilities,
+        assets,
+        liabilities,
+    }
+}
+
+/// Net worth on every date a snapshot was taken, oldest first
+pub fn net_worth_history(snapshots: &[BalanceSnapshot]) -> Vec<NetWorthPoint> {
+    let dates: BTreeSet<NaiveDate> = snapshots.iter().map(|s| s.date).collect();
+
+    dates
+        .into_iter()
+        .map(|date| calculate_net_worth(snapshots, date))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(account_id: i32, is_liability: bool, day: u32, balance: i32) -> BalanceSnapshot {
+        BalanceSnapshot {
+            account_id,
+            is_liability,
+            date: NaiveDate::from_ymd_opt(2026, 1, day).unwrap(),
+            balance: BigDecimal::from(balance),
+        }
+    }
+
+    #[test]
+    fn test_liabilities_reduce_net_worth() {
+        let snapshots = vec![snapshot(1, false, 1, 5000), snapshot(2, true, 1, 1200)];
+
+        let point = calculate_net_worth(&snapshots, NaiveDate::from_ymd_opt(2026, 1, 31).unwrap());
+
+        assert_eq!(point.assets, BigDecimal::from(5000));
+        assert_eq!(point.liabilities, BigDecimal::from(1200));
+        assert_eq!(point.net_worth, BigDecimal::from(3800));
+    }
+
+    #[test]
+    fn test_latest_snapshot_wins() {
+        let snapshots = vec![snapshot(1, false, 1, 5000), snapshot(1, false, 15, 4200), snapshot(1, false, 20, 9999)];
+
+        let latest = balances_as_of(&snapshots, NaiveDate::from_ymd_opt(2026, 1, 16).unwrap());
+
+        assert_eq!(latest.len(), 1);
+        assert_eq!(latest[0].balance, BigDecimal::from(4200));
+    }
+
+    #[test]
+    fn test_history_carries_unchanged_accounts_forward() {
+        let snapshots = vec![snapshot(1, false, 1, 5000), snapshot(2, true, 10, 1000), snapshot(1, false, 20, 6000)];
+
+        let history = net_worth_history(&snapshots);
+
+        let net_worths: Vec<BigDecimal> = history.iter().map(|p| p.net_worth.clone()).collect();
+        assert_eq!(net_worths, vec![BigDecimal::from(5000), BigDecimal::from(4000), BigDecimal::from(5000)]);
+    }
+
+    #[test]
+    fn test_no_snapshots_is_zero() {
+        let point = calculate_net_worth(&[], NaiveDate::from_ymd_opt(2026, 1, 1).unwrap());
+        assert_eq!(point.net_worth, BigDecimal::from(0));
+    }
+}
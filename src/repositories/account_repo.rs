@@ -0,0 +1,106 @@
+use std::rc::Rc;
+use diesel::prelude::*;
+use bigdecimal::BigDecimal;
+use chrono::NaiveDate;
+
+use crate::db::PgConnector;
+use crate::models::{Account, AccountBalance, NewAccount, NewAccountBalance};
+use crate::schema::{account_balances, accounts};
+
+pub struct AccountRepo {
+    pg_connector: Rc<PgConnector>,
+}
+
+impl AccountRepo {
+    pub fn new(pg_connector: Rc<PgConnector>) -> Self {
+        Self { pg_connector }
+    }
+
+    pub fn find_all(&self) -> Vec<Account> {
+        let mut conn = self.pg_connector.get_connection();
+        accounts::table
+            .order(accounts::name.asc())
+            .load::<Account>(&mut *conn)
+            .expect("Error loading accounts")
+    }
+
+    pub fn create(&self, account_name: String, account_type: String, account_notes: Option<String>) -> Account {
+        let mut conn = self.pg_connector.get_connection();
+        let new_account = NewAccount {
+            name: account_name,
+            account_type,
+            notes: account_notes,
+        };
+
+        diesel::insert_into(accounts::table)
+            .values(&new_account)
+            .returning(Account::as_returning())
+            .get_result(&mut *conn)
+            .expect("Error saving new account")
+    }
+
+    pub fn update(&self, account_id: i32, account_name: String, account_type: String, account_notes: Option<String>) -> Account {
+        let mut conn = self.pg_connector.get_connection();
+        diesel::update(accounts::table.filter(accounts::id.eq(account_id)))
+            .set((
+                accounts::name.eq(account_name),
+                accounts::account_type.eq(account_type),
+                accounts::notes.eq(account_notes),
+            ))
+            .returning(Account::as_returning())
+            .get_result(&mut *conn)
+            .expect("Error updating account")
+    }
+
+    pub fn delete(&self, account_id: i32) -> bool {
+        let mut conn = self.pg_connector.get_connection();
+        diesel::delete(accounts::table.filter(accounts::id.eq(account_id)))
+            .execute(&mut *conn)
+            .is_ok()
+    }
+
+    // Balance snapshots
+    pub fn find_balances(&self) -> Vec<AccountBalance> {
+        let mut conn = self.pg_connector.get_connection();
+        account_balances::table
+            .order(account_balances::date.asc())
+            .load::<AccountBalance>(&mut *conn)
+            .unwrap_or_default()
+    }
+
+    /// One account's snapshots, newest first
+    pub fn find_balances_by_account(&self, account_id: i32) -> Vec<AccountBalance> {
+        let mut conn = self.pg_connector.get_connection();
+        account_balances::table
+            .filter(account_balances::account_id.eq(account_id))
+            .order(account_balances::date.desc())
+            .load::<AccountBalance>(&mut *conn)
+            .unwrap_or_default()
+    }
+
+    /// Record an account's balance on a date, replacing any snapshot already taken that day
+    pub fn record_balance(&self, account_id: i32, balance_date: NaiveDate, balance_amount: BigDecimal) -> AccountBalance {
+        let mut conn = self.pg_connector.get_connection();
+        let new_balance = NewAccountBalance {
+            account_id,
+            date: balance_date,
+            balance: balance_amount.clone(),
+        };
+
+        diesel::insert_into(account_balances::table)
+            .values(&new_balance)
+            .on_conflict((account_balances::account_id, account_balances::date))
+            .do_update()
+            .set(account_balances::balance.eq(balance_amount))
+            .returning(AccountBalance::as_returning())
+            .get_result(&mut *conn)
+            .expect("Error saving account balance")
+    }
+
+    pub fn delete_balance(&self, balance_id: i32) -> bool {
+        let mut conn = self.pg_connector.get_connection();
+        diesel::delete(account_balances::table.filter(account_balances::id.eq(balance_id)))
+            .execute(&mut *conn)
+            .is_ok()
+    }
+}
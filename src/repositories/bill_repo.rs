@@ -1,10 +1,11 @@
 use std::rc::Rc;
 use diesel::prelude::*;
 use bigdecimal::BigDecimal;
-use chrono::NaiveDate;
+use chrono::Local;
 
 use crate::db::PgConnector;
-use crate::models::{Bill, NewBill};
+use crate::models::{Bill, BillPriceChange, NewBill, NewBillPriceChange};
+use crate::schema::bill_price_changes;
 use crate::schema::bills::dsl::*;
 
 pub struct BillRepo {
@@ -31,16 +32,8 @@ impl BillRepo {
             .ok()
     }
 
-    pub fn create(&self, bill_name: String, bill_amount: BigDecimal, bill_due_day: Option<NaiveDate>, bill_is_auto_pay: bool, bill_notes: Option<String>) -> Bill {
+    pub fn create(&self, new_bill: NewBill) -> Bill {
         let mut conn = self.pg_connector.get_connection();
-        let new_bill = NewBill {
-            name: bill_name,
-            amount: bill_amount,
-            due_day: bill_due_day,
-            is_auto_pay: bill_is_auto_pay,
-            notes: bill_notes,
-        };
-
         diesel::insert_into(bills)
             .values(&new_bill)
             .returning(Bill::as_returning())
@@ -48,19 +41,56 @@ impl BillRepo {
             .expect("Error saving new bill")
     }
 
-    pub fn update(&self, bill_id: i32, bill_name: String, bill_amount: BigDecimal, bill_due_day: Option<NaiveDate>, bill_is_auto_pay: bool, bill_notes: Option<String>) -> Bill {
+    /// Update a bill, recording a dated price change when its amount differs from before
+    pub fn update(&self, bill_id: i32, bill: NewBill) -> Bill {
         let mut conn = self.pg_connector.get_connection();
-        diesel::update(bills.filter(id.eq(bill_id)))
-            .set((
-                name.eq(bill_name),
-                amount.eq(bill_amount),
-                due_day.eq(bill_due_day),
-                is_auto_pay.eq(bill_is_auto_pay),
-                notes.eq(bill_notes),
-            ))
-            .returning(Bill::as_returning())
-            .get_result(&mut *conn)
-            .expect("Error updating bill")
+        conn.transaction::<Bill, diesel::result::Error, _>(|conn| {
+            let previous_amount: BigDecimal = bills
+                .filter(id.eq(bill_id))
+                .select(amount)
+                .first(conn)?;
+            if previous_amount != bill.amount {
+                Self::record_price_change(conn, bill_id, previous_amount, bill.amount.clone())?;
+            }
+
+            diesel::update(bills.filter(id.eq(bill_id)))
+                .set((
+                    name.eq(bill.name),
+                    amount.eq(bill.amount),
+                    due_day.eq(bill.due_day),
+                    is_auto_pay.eq(bill.is_auto_pay),
+                    notes.eq(bill.notes),
+                    category_id.eq(bill.category_id),
+                    envelope_id.eq(bill.envelope_id),
+                    tax_category.eq(bill.tax_category),
+                ))
+                .returning(Bill::as_returning())
+                .get_result(conn)
+        })
+        .expect("Error updating bill")
+    }
+
+    fn record_price_change(conn: &mut PgConnection, bill_id: i32, change_old_amount: BigDecimal, change_new_amount: BigDecimal) -> QueryResult<BillPriceChange> {
+        let new_change = NewBillPriceChange {
+            bill_id,
+            changed_on: Local::now().date_naive(),
+            old_amount: change_old_amount,
+            new_amount: change_new_amount,
+        };
+
+        diesel::insert_into(bill_price_changes::table)
+            .values(&new_change)
+            .returning(BillPriceChange::as_returning())
+            .get_result(conn)
+    }
+
+    /// Every recorded price change, oldest first
+    pub fn find_price_changes(&self) -> Vec<BillPriceChange> {
+        let mut conn = self.pg_connector.get_connection();
+        bill_price_changes::table
+            .order((bill_price_changes::changed_on.asc(), bill_price_changes::id.asc()))
+            .load(&mut *conn)
+            .unwrap_or_default()
     }
 
     pub fn delete(&self, bill_id: i32) -> bool {
@@ -0,0 +1,60 @@
+use std::rc::Rc;
+use diesel::prelude::*;
+use bigdecimal::BigDecimal;
+
+use crate::db::PgConnector;
+use crate::models::{Category, NewCategory};
+use crate::schema::categories;
+
+pub struct CategoryRepo {
+    pg_connector: Rc<PgConnector>,
+}
+
+impl CategoryRepo {
+    pub fn new(pg_connector: Rc<PgConnector>) -> Self {
+        Self { pg_connector }
+    }
+
+    pub fn find_all(&self) -> Vec<Category> {
+        let mut conn = self.pg_connector.get_connection();
+        categories::table
+            .order(categories::name.asc())
+            .load::<Category>(&mut *conn)
+            .expect("Error loading categories")
+    }
+
+    pub fn create(&self, category_name: String, category_parent_id: Option<i32>, category_monthly_budget: Option<BigDecimal>) -> Category {
+        let mut conn = self.pg_connector.get_connection();
+        let new_category = NewCategory {
+            name: category_name,
+            parent_id: category_parent_id,
+            monthly_budget: category_monthly_budget,
+        };
+
+        diesel::insert_into(categories::table)
+            .values(&new_category)
+            .returning(Category::as_returning())
+            .get_result(&mut *conn)
+            .expect("Error saving new category")
+    }
+
+    pub fn update(&self, category_id: i32, category_name: String, category_parent_id: Option<i32>, category_monthly_budget: Option<BigDecimal>) -> Category {
+        let mut conn = self.pg_connector.get_connection();
+        diesel::update(categories::table.filter(categories::id.eq(category_id)))
+            .set((
+                categories::name.eq(category_name),
+                categories::parent_id.eq(category_parent_id),
+                categories::monthly_budget.eq(category_monthly_budget),
+            ))
+            .returning(Category::as_returning())
+            .get_result(&mut *conn)
+            .expect("Error updating category")
+    }
+
+    pub fn delete(&self, category_id: i32) -> bool {
+        let mut conn = self.pg_connector.get_connection();
+        diesel::delete(categories::table.filter(categories::id.eq(category_id)))
+            .execute(&mut *conn)
+            .is_ok()
+    }
+}
@@ -0,0 +1,166 @@
+use std::rc::Rc;
+use diesel::prelude::*;
+use bigdecimal::BigDecimal;
+use chrono::{Datelike, NaiveDate};
+
+use crate::card_logic;
+use crate::db::PgConnector;
+use crate::models::{CardCharge, CreditCard, NewCardCharge, NewCreditCard};
+use crate::schema::{card_charges, credit_cards, ledger_bills, ledgers};
+
+pub struct CreditCardRepo {
+    pg_connector: Rc<PgConnector>,
+}
+
+impl CreditCardRepo {
+    pub fn new(pg_connector: Rc<PgConnector>) -> Self {
+        Self { pg_connector }
+    }
+
+    pub fn find_all(&self) -> Vec<CreditCard> {
+        let mut conn = self.pg_connector.get_connection();
+        credit_cards::table
+            .select(CreditCard::as_select())
+            .load(&mut *conn)
+            .expect("Error loading credit cards")
+    }
+
+    pub fn find_by_account(&self, card_account_id: i32) -> Option<CreditCard> {
+        let mut conn = self.pg_connector.get_connection();
+        credit_cards::table
+            .filter(credit_cards::account_id.eq(card_account_id))
+            .select(CreditCard::as_select())
+            .first(&mut *conn)
+            .optional()
+            .unwrap_or_default()
+    }
+
+    /// Set an account's statement cycle, replacing any earlier one, and reprice the bill in open ledgers
+    pub fn save(&self, card_account_id: i32, card_bill_id: Option<i32>, card_closing_day: i32, card_due_day: i32, card_notes: Option<String>) -> CreditCard {
+        let card = {
+            let mut conn = self.pg_connector.get_connection();
+            let new_card = NewCreditCard {
+                account_id: card_account_id,
+                bill_id: card_bill_id,
+                closing_day: card_closing_day,
+                due_day: card_due_day,
+                notes: card_notes.clone(),
+            };
+
+            diesel::insert_into(credit_cards::table)
+                .values(&new_card)
+                .on_conflict(credit_cards::account_id)
+                .do_update()
+                .set((
+                    credit_cards::bill_id.eq(card_bill_id),
+                    credit_cards::closing_day.eq(card_closing_day),
+                    credit_cards::due_day.eq(card_due_day),
+                    credit_cards::notes.eq(card_notes),
+                ))
+                .returning(CreditCard::as_returning())
+                .get_result(&mut *conn)
+                .expect("Error saving credit card")
+        };
+
+        self.apply_statements(&card);
+        card
+    }
+
+    pub fn delete(&self, card_id: i32) -> bool {
+        let mut conn = self.pg_connector.get_connection();
+        diesel::delete(credit_cards::table.filter(credit_cards::id.eq(card_id)))
+            .execute(&mut *conn)
+            .is_ok()
+    }
+
+    pub fn find_charges(&self, card_id: i32) -> Vec<CardCharge> {
+        let mut conn = self.pg_connector.get_connection();
+        card_charges::table
+            .filter(card_charges::credit_card_id.eq(card_id))
+            .order(card_charges::date.desc())
+            .select(CardCharge::as_select())
+            .load(&mut *conn)
+            .unwrap_or_default()
+    }
+
+    pub fn create_charge(&self, card: &CreditCard, charge_date: NaiveDate, charge_amount: BigDecimal, charge_description: String) -> CardCharge {
+        let charge = {
+            let mut conn = self.pg_connector.get_connection();
+            let new_charge = NewCardCharge {
+                credit_card_id: card.id,
+                date: charge_date,
+                amount: charge_amount,
+                description: charge_description,
+            };
+
+            diesel::insert_into(card_charges::table)
+                .values(&new_charge)
+                .returning(CardCharge::as_returning())
+                .get_result(&mut *conn)
+                .expect("Error saving card charge")
+        };
+
+        self.apply_statements(card);
+        charge
+    }
+
+    pub fn delete_charge(&self, card: &CreditCard, charge_id: i32) -> bool {
+        let deleted = {
+            let mut conn = self.pg_connector.get_connection();
+            diesel::delete(card_charges::table.filter(card_charges::id.eq(charge_id)))
+                .execute(&mut *conn)
+                .is_ok()
+        };
+
+        self.apply_statements(card);
+        deleted
+    }
+
+    /// Charges as (date, amount) pairs for the statement calculations
+    pub fn charge_amounts(&self, card_id: i32) -> Vec<(NaiveDate, BigDecimal)> {
+        self.find_charges(card_id)
+            .into_iter()
+            .map(|c| (c.date, c.amount))
+            .collect()
+    }
+
+    /// Balance of the statement due in the month of a ledger date
+    pub fn statement_amount(&self, card: &CreditCard, ledger_date: NaiveDate) -> BigDecimal {
+        let cycle = card_logic::statement_due_in(card.closing_day as u32, card.due_day as u32, ledger_date.year(), ledger_date.month());
+        card_logic::statement_balance(&self.charge_amounts(card.id), &cycle)
+    }
+
+    /// Set the card's unpaid bill in every open ledger to the statement due that month
+    /// Returns how many ledger bills were updated
+    pub fn apply_statements(&self, card: &CreditCard) -> usize {
+        let Some(card_bill_id) = card.bill_id else {
+            return 0;
+        };
+
+        let targets: Vec<(i32, NaiveDate)> = {
+            let mut conn = self.pg_connector.get_connection();
+            ledger_bills::table
+                .inner_join(ledgers::table)
+                .filter(ledger_bills::bill_id.eq(card_bill_id))
+                .filter(ledger_bills::is_payed.eq(false))
+                .filter(ledgers::is_closed.eq(false))
+                .select((ledger_bills::id, ledgers::date))
+                .load(&mut *conn)
+                .unwrap_or_default()
+        };
+
+        let charges = self.charge_amounts(card.id);
+        let mut conn = self.pg_connector.get_connection();
+        let mut updated = 0;
+        for (ledger_bill_id, ledger_date) in targets {
+            let cycle = card_logic::statement_due_in(card.closing_day as u32, card.due_day as u32, ledger_date.year(), ledger_date.month());
+            let result = diesel::update(ledger_bills::table.filter(ledger_bills::id.eq(ledger_bill_id)))
+                .set(ledger_bills::amount.eq(card_logic::statement_balance(&charges, &cycle)))
+                .execute(&mut *conn);
+            if result.is_ok() {
+                updated += 1;
+            }
+        }
+        updated
+    }
+}
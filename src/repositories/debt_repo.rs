@@ -0,0 +1,69 @@
+use std::rc::Rc;
+use diesel::prelude::*;
+use chrono::NaiveDate;
+
+use crate::db::PgConnector;
+use crate::models::{Debt, DebtPayment, NewDebt};
+use crate::schema::{debt_payments, debts, ledger_bills, ledgers};
+
+pub struct DebtRepo {
+    pg_connector: Rc<PgConnector>,
+}
+
+impl DebtRepo {
+    pub fn new(pg_connector: Rc<PgConnector>) -> Self {
+        Self { pg_connector }
+    }
+
+    pub fn find_all(&self) -> Vec<Debt> {
+        let mut conn = self.pg_connector.get_connection();
+        debts::table
+            .order(debts::name.asc())
+            .load::<Debt>(&mut *conn)
+            .expect("Error loading debts")
+    }
+
+    pub fn create(&self, new_debt: NewDebt) -> Debt {
+        let mut conn = self.pg_connector.get_connection();
+        diesel::insert_into(debts::table)
+            .values(&new_debt)
+            .returning(Debt::as_returning())
+            .get_result(&mut *conn)
+            .expect("Error saving new debt")
+    }
+
+    pub fn update(&self, debt_id: i32, debt: NewDebt) -> Debt {
+        let mut conn = self.pg_connector.get_connection();
+        diesel::update(debts::table.filter(debts::id.eq(debt_id)))
+            .set((
+                debts::name.eq(debt.name),
+                debts::balance.eq(debt.balance),
+                debts::apr.eq(debt.apr),
+                debts::minimum_payment.eq(debt.minimum_payment),
+                debts::bill_id.eq(debt.bill_id),
+                debts::notes.eq(debt.notes),
+            ))
+            .returning(Debt::as_returning())
+            .get_result(&mut *conn)
+            .expect("Error updating debt")
+    }
+
+    pub fn delete(&self, debt_id: i32) -> bool {
+        let mut conn = self.pg_connector.get_connection();
+        diesel::delete(debts::table.filter(debts::id.eq(debt_id)))
+            .execute(&mut *conn)
+            .is_ok()
+    }
+
+    /// Payments applied from paid ledger bills, with the ledger's date and name, newest first
+    pub fn find_payments_by_debt(&self, debt_id: i32) -> Vec<(DebtPayment, NaiveDate, Option<String>)> {
+        let mut conn = self.pg_connector.get_connection();
+        debt_payments::table
+            .inner_join(ledger_bills::table.inner_join(ledgers::table))
+            .filter(debt_payments::debt_id.eq(debt_id))
+            .order((ledgers::date.desc(), debt_payments::id.desc()))
+            .select((DebtPayment::as_select(), ledgers::date, ledgers::name))
+            .load::<(DebtPayment, NaiveDate, Option<String>)>(&mut *conn)
+            .unwrap_or_default()
+    }
+}
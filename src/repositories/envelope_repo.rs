@@ -0,0 +1,127 @@
+use std::rc::Rc;
+use diesel::prelude::*;
+use bigdecimal::BigDecimal;
+
+use crate::budget_logic::{self, EnvelopeBalance, EnvelopePeriod};
+use crate::db::PgConnector;
+use crate::models::{Envelope, LedgerEnvelope, NewEnvelope, NewLedgerEnvelope};
+use crate::repositories::{self, LedgerClosedError};
+use crate::schema::{bills, envelopes, ledger_bill_payments, ledger_bills, ledger_envelopes};
+
+pub struct EnvelopeRepo {
+    pg_connector: Rc<PgConnector>,
+}
+
+impl EnvelopeRepo {
+    pub fn new(pg_connector: Rc<PgConnector>) -> Self {
+        Self { pg_connector }
+    }
+
+    pub fn find_all(&self) -> Vec<Envelope> {
+        let mut conn = self.pg_connector.get_connection();
+        envelopes::table
+            .order(envelopes::name.asc())
+            .load::<Envelope>(&mut *conn)
+            .expect("Error loading envelopes")
+    }
+
+    pub fn create(&self, envelope_name: String, envelope_notes: Option<String>) -> Envelope {
+        let mut conn = self.pg_connector.get_connection();
+        let new_envelope = NewEnvelope {
+            name: envelope_name,
+            notes: envelope_notes,
+        };
+
+        diesel::insert_into(envelopes::table)
+            .values(&new_envelope)
+            .returning(Envelope::as_returning())
+            .get_result(&mut *conn)
+            .expect("Error saving new envelope")
+    }
+
+    pub fn delete(&self, envelope_id: i32) -> bool {
+        let mut conn = self.pg_connector.get_connection();
+        diesel::delete(envelopes::table.filter(envelopes::id.eq(envelope_id)))
+            .execute(&mut *conn)
+            .is_ok()
+    }
+
+    pub fn find_allocations_by_ledger(&self, ledger_id: i32) -> Vec<LedgerEnvelope> {
+        let mut conn = self.pg_connector.get_connection();
+        ledger_envelopes::table
+            .filter(ledger_envelopes::ledger_id.eq(ledger_id))
+            .load::<LedgerEnvelope>(&mut *conn)
+            .unwrap_or_default()
+    }
+
+    /// Set how much a ledger gives an envelope, replacing any earlier amount
+    pub fn set_allocation(&self, ledger_id: i32, envelope_id: i32, amount: BigDecimal) -> Result<LedgerEnvelope, LedgerClosedError> {
+        repositories::ensure_ledger_open(&self.pg_connector, ledger_id)?;
+        let mut conn = self.pg_connector.get_connection();
+        let new_allocation = NewLedgerEnvelope {
+            ledger_id,
+            envelope_id,
+            allocated: amount.clone(),
+        };
+
+        Ok(diesel::insert_into(ledger_envelopes::table)
+            .values(&new_allocation)
+            .on_conflict((ledger_envelopes::ledger_id, ledger_envelopes::envelope_id))
+            .do_update()
+            .set(ledger_envelopes::allocated.eq(amount))
+            .returning(LedgerEnvelope::as_returning())
+            .get_result(&mut *conn)
+            .expect("Error saving envelope allocation"))
+    }
+
+    /// Amounts paid on a ledger's bills, grouped by the bill's envelope
+    pub fn find_spending_by_ledger(&self, ledger_id: i32) -> Vec<(i32, BigDecimal)> {
+        let (enveloped_bills, payments) = {
+            let mut conn = self.pg_connector.get_connection();
+            let enveloped_bills: Vec<(i32, BigDecimal, bool, Option<i32>)> = ledger_bills::table
+                .inner_join(bills::table)
+                .filter(ledger_bills::ledger_id.eq(ledger_id))
+                .filter(bills::envelope_id.is_not_null())
+                .select((ledger_bills::id, ledger_bills::amount, ledger_bills::is_payed, bills::envelope_id))
+                .load(&mut *conn)
+                .unwrap_or_default();
+            let payments: Vec<(i32, BigDecimal)> = ledger_bill_payments::table
+                .inner_join(ledger_bills::table)
+                .filter(ledger_bills::ledger_id.eq(ledger_id))
+                .select((ledger_bill_payments::ledger_bill_id, ledger_bill_payments::amount))
+                .load(&mut *conn)
+                .unwrap_or_default();
+            (enveloped_bills, payments)
+        };
+
+        enveloped_bills
+            .into_iter()
+            .filter_map(|(ledger_bill_id, amount, is_payed, envelope_id)| {
+                let bill_payments: Vec<BigDecimal> = payments
+                    .iter()
+                    .filter(|(pid, _)| *pid == ledger_bill_id)
+                    .map(|(_, a)| a.clone())
+                    .collect();
+                let paid = crate::ledger_logic::calculate_bill_paid_amount(&amount, is_payed, &bill_payments);
+                envelope_id.map(|eid| (eid, paid))
+            })
+            .collect()
+    }
+
+    /// Envelope balances for the last ledger in a chain (ledger ids oldest first)
+    pub fn find_balances(&self, ledger_chain: &[i32]) -> Vec<EnvelopeBalance> {
+        let envelope_ids: Vec<i32> = self.find_all().iter().map(|e| e.id).collect();
+        let periods: Vec<EnvelopePeriod> = ledger_chain
+            .iter()
+            .map(|&ledger_id| EnvelopePeriod {
+                allocations: self.find_allocations_by_ledger(ledger_id)
+                    .into_iter()
+                    .map(|a| (a.envelope_id, a.allocated))
+                    .collect(),
+                spending: self.find_spending_by_ledger(ledger_id),
+            })
+            .collect();
+
+        budget_logic::roll_envelope_balances(&envelope_ids, &periods)
+    }
+}
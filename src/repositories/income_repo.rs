@@ -1,11 +1,42 @@
+use std::fmt;
 use std::rc::Rc;
 use diesel::prelude::*;
 use bigdecimal::BigDecimal;
 use chrono::NaiveDate;
 
 use crate::db::PgConnector;
-use crate::models::{Income, NewIncome};
+use crate::income_logic;
+use crate::models::{Income, NewIncome, NewPaystub, Paystub};
+use crate::repositories::{self, LedgerClosedError};
 use crate::schema::incomes::dsl::*;
+use crate::schema::paystubs;
+
+/// Returned when an income change is refused
+#[derive(Debug, Clone, PartialEq)]
+pub enum IncomeChangeError {
+    LedgerClosed(LedgerClosedError),
+    /// The new amount no longer matches the net pay of the income's paystub
+    PaystubMismatch { paystub_net: BigDecimal },
+}
+
+impl fmt::Display for IncomeChangeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            IncomeChangeError::LedgerClosed(e) => write!(f, "{}", e),
+            IncomeChangeError::PaystubMismatch { paystub_net } => write!(
+                f,
+                "This income's paystub nets ${}. Update or remove the paystub before changing the amount.",
+                paystub_net
+            ),
+        }
+    }
+}
+
+impl From<LedgerClosedError> for IncomeChangeError {
+    fn from(e: LedgerClosedError) -> Self {
+        IncomeChangeError::LedgerClosed(e)
+    }
+}
 
 pub struct IncomeRepo {
     pg_connector: Rc<PgConnector>,
@@ -16,10 +47,6 @@ impl IncomeRepo {
         Self { pg_connector }
     }
 
-    pub fn pg_connector(&self) -> Rc<PgConnector> {
-        Rc::clone(&self.pg_connector)
-    }
-
     pub fn find_all(&self) -> Vec<Income> {
         let mut conn = self.pg_connector.get_connection();
         incomes
@@ -35,14 +62,8 @@ impl IncomeRepo {
             .ok()
     }
 
-    pub fn create(&self, income_date: NaiveDate, income_amount: BigDecimal, income_notes: Option<String>) -> Income {
+    pub fn create(&self, new_income: NewIncome) -> Income {
         let mut conn = self.pg_connector.get_connection();
-        let new_income = NewIncome {
-            date: income_date,
-            amount: income_amount,
-            notes: income_notes,
-        };
-
         diesel::insert_into(incomes)
             .values(&new_income)
             .returning(Income::as_returning())
@@ -50,23 +71,113 @@ impl IncomeRepo {
             .expect("Error saving new income")
     }
 
-    pub fn update(&self, income_id: i32, income_date: NaiveDate, income_amount: BigDecimal, income_notes: Option<String>) -> Income {
+    /// Whether the income is still expected is left as it was
+    pub fn update(&self, income_id: i32, income: NewIncome) -> Result<Income, IncomeChangeError> {
+        repositories::ensure_income_ledger_open(&self.pg_connector, income_id)?;
+        self.ensure_paystub_matches(income_id, &income.amount)?;
         let mut conn = self.pg_connector.get_connection();
-        diesel::update(incomes.filter(id.eq(income_id)))
+        Ok(diesel::update(incomes.filter(id.eq(income_id)))
+            .set((
+                date.eq(income.date),
+                amount.eq(income.amount),
+                notes.eq(income.notes),
+                account_id.eq(income.account_id),
+                income_source_id.eq(income.income_source_id),
+                tax_category.eq(income.tax_category),
+                category.eq(income.category),
+            ))
+            .returning(Income::as_returning())
+            .get_result(&mut *conn)
+            .expect("Error updating income"))
+    }
+
+    /// Record the real date and amount of an expected paycheck
+    pub fn confirm(&self, income_id: i32, income_date: NaiveDate, income_amount: BigDecimal) -> Result<Income, IncomeChangeError> {
+        repositories::ensure_income_ledger_open(&self.pg_connector, income_id)?;
+        self.ensure_paystub_matches(income_id, &income_amount)?;
+        let mut conn = self.pg_connector.get_connection();
+        Ok(diesel::update(incomes.filter(id.eq(income_id)))
             .set((
                 date.eq(income_date),
                 amount.eq(income_amount),
-                notes.eq(income_notes),
+                is_expected.eq(false),
             ))
             .returning(Income::as_returning())
             .get_result(&mut *conn)
-            .expect("Error updating income")
+            .expect("Error confirming income"))
     }
 
-    pub fn delete(&self, income_id: i32) -> bool {
+    pub fn delete(&self, income_id: i32) -> Result<bool, LedgerClosedError> {
+        repositories::ensure_income_ledger_open(&self.pg_connector, income_id)?;
         let mut conn = self.pg_connector.get_connection();
-        diesel::delete(incomes.filter(id.eq(income_id)))
+        Ok(diesel::delete(incomes.filter(id.eq(income_id)))
             .execute(&mut *conn)
-            .is_ok()
+            .is_ok())
+    }
+
+    pub fn find_paystub(&self, paystub_income_id: i32) -> Option<Paystub> {
+        let mut conn = self.pg_connector.get_connection();
+        paystubs::table
+            .filter(paystubs::income_id.eq(paystub_income_id))
+            .first::<Paystub>(&mut *conn)
+            .optional()
+            .unwrap_or_default()
+    }
+
+    /// Paystubs for incomes dated between two days, for year-to-date totals
+    pub fn find_paystubs_between(&self, from: NaiveDate, through: NaiveDate) -> Vec<Paystub> {
+        let mut conn = self.pg_connector.get_connection();
+        paystubs::table
+            .inner_join(incomes)
+            .filter(date.ge(from))
+            .filter(date.le(through))
+            .select(Paystub::as_select())
+            .load(&mut *conn)
+            .unwrap_or_default()
+    }
+
+    /// Set an income's paystub, replacing any earlier one
+    pub fn save_paystub(&self, new_paystub: NewPaystub) -> Result<Paystub, LedgerClosedError> {
+        repositories::ensure_income_ledger_open(&self.pg_connector, new_paystub.income_id)?;
+        let mut conn = self.pg_connector.get_connection();
+        Ok(diesel::insert_into(paystubs::table)
+            .values(&new_paystub)
+            .on_conflict(paystubs::income_id)
+            .do_update()
+            .set((
+                paystubs::gross.eq(&new_paystub.gross),
+                paystubs::federal_tax.eq(&new_paystub.federal_tax),
+                paystubs::state_tax.eq(&new_paystub.state_tax),
+                paystubs::social_security.eq(&new_paystub.social_security),
+                paystubs::medicare.eq(&new_paystub.medicare),
+                paystubs::retirement_401k.eq(&new_paystub.retirement_401k),
+                paystubs::hsa.eq(&new_paystub.hsa),
+                paystubs::insurance.eq(&new_paystub.insurance),
+                paystubs::other_deductions.eq(&new_paystub.other_deductions),
+            ))
+            .returning(Paystub::as_returning())
+            .get_result(&mut *conn)
+            .expect("Error saving paystub"))
+    }
+
+    pub fn delete_paystub(&self, paystub_income_id: i32) -> Result<bool, LedgerClosedError> {
+        repositories::ensure_income_ledger_open(&self.pg_connector, paystub_income_id)?;
+        let mut conn = self.pg_connector.get_connection();
+        Ok(diesel::delete(paystubs::table.filter(paystubs::income_id.eq(paystub_income_id)))
+            .execute(&mut *conn)
+            .is_ok())
+    }
+
+    /// A changed amount must still be the paystub's gross less deductions
+    fn ensure_paystub_matches(&self, income_id: i32, new_amount: &BigDecimal) -> Result<(), IncomeChangeError> {
+        let unchanged = self.find_by_id(income_id).is_some_and(|i| i.amount == *new_amount);
+        if unchanged {
+            return Ok(());
+        }
+
+        match self.find_paystub(income_id).map(|p| income_logic::to_paystub_lines(&p).net()) {
+            Some(paystub_net) if paystub_net != *new_amount => Err(IncomeChangeError::PaystubMismatch { paystub_net }),
+            _ => Ok(()),
+        }
     }
 }
@@ -0,0 +1,109 @@
+use std::rc::Rc;
+use diesel::prelude::*;
+use chrono::NaiveDate;
+
+use crate::db::PgConnector;
+use crate::income_logic;
+use crate::models::{IncomeSource, NewIncome, NewIncomeSource, PaySchedule};
+use crate::schema::{income_sources, incomes};
+
+pub struct IncomeSourceRepo {
+    pg_connector: Rc<PgConnector>,
+}
+
+impl IncomeSourceRepo {
+    pub fn new(pg_connector: Rc<PgConnector>) -> Self {
+        Self { pg_connector }
+    }
+
+    pub fn find_all(&self) -> Vec<IncomeSource> {
+        let mut conn = self.pg_connector.get_connection();
+        income_sources::table
+            .order(income_sources::name.asc())
+            .load::<IncomeSource>(&mut *conn)
+            .expect("Error loading income sources")
+    }
+
+    pub fn create(&self, new_source: NewIncomeSource) -> IncomeSource {
+        let mut conn = self.pg_connector.get_connection();
+        diesel::insert_into(income_sources::table)
+            .values(&new_source)
+            .returning(IncomeSource::as_returning())
+            .get_result(&mut *conn)
+            .expect("Error saving new income source")
+    }
+
+    /// Unassigned expected paychecks from today on are dropped so they can be regenerated on the new schedule
+    pub fn update(&self, source_id: i32, source: NewIncomeSource) -> IncomeSource {
+        let mut conn = self.pg_connector.get_connection();
+        diesel::delete(
+            incomes::table
+                .filter(incomes::income_source_id.eq(source_id))
+                .filter(incomes::is_expected.eq(true))
+                .filter(incomes::ledger_id.is_null())
+                .filter(incomes::date.ge(chrono::Local::now().date_naive()))
+        )
+        .execute(&mut *conn)
+        .expect("Error clearing expected incomes");
+
+        diesel::update(income_sources::table.filter(income_sources::id.eq(source_id)))
+            .set((
+                income_sources::name.eq(source.name),
+                income_sources::schedule.eq(source.schedule),
+                income_sources::anchor_date.eq(source.anchor_date),
+                income_sources::second_day.eq(source.second_day),
+                income_sources::expected_amount.eq(source.expected_amount),
+                income_sources::account_id.eq(source.account_id),
+                income_sources::is_active.eq(source.is_active),
+                income_sources::notes.eq(source.notes),
+                income_sources::category.eq(source.category),
+            ))
+            .returning(IncomeSource::as_returning())
+            .get_result(&mut *conn)
+            .expect("Error updating income source")
+    }
+
+    pub fn delete(&self, source_id: i32) -> bool {
+        let mut conn = self.pg_connector.get_connection();
+        diesel::delete(income_sources::table.filter(income_sources::id.eq(source_id)))
+            .execute(&mut *conn)
+            .is_ok()
+    }
+
+    /// Create expected incomes for every pay date between two dates
+    /// Pay dates already generated for this source are left alone, even if confirming moved them; returns how many were added
+    pub fn generate_expected(&self, source: &IncomeSource, from: NaiveDate, through: NaiveDate) -> usize {
+        let dates = income_logic::pay_dates(
+            PaySchedule::from(source.schedule.clone()),
+            source.anchor_date,
+            source.second_day.map(|d| d as u32),
+            from,
+            through
+        );
+
+        let mut conn = self.pg_connector.get_connection();
+        dates
+            .into_iter()
+            .map(|pay_date| {
+                let new_income = NewIncome {
+                    date: pay_date,
+                    amount: source.expected_amount.clone(),
+                    notes: None,
+                    account_id: source.account_id,
+                    income_source_id: Some(source.id),
+                    is_expected: true,
+                    tax_category: None,
+                    scheduled_date: Some(pay_date),
+                    category: None,
+                };
+
+                diesel::insert_into(incomes::table)
+                    .values(&new_income)
+                    .on_conflict((incomes::income_source_id, incomes::scheduled_date))
+                    .do_nothing()
+                    .execute(&mut *conn)
+                    .unwrap_or(0)
+            })
+            .sum()
+    }
+}
@@ -1,12 +1,25 @@
+use std::collections::HashMap;
+use std::fmt;
 use std::rc::Rc;
 use diesel::prelude::*;
 use bigdecimal::BigDecimal;
 use chrono::NaiveDate;
 
 use crate::db::PgConnector;
-use crate::models::{Ledger, NewLedger, LedgerBill, NewLedgerBill, Bill, Income};
+use crate::models::{Ledger, NewLedger, LedgerBill, NewLedgerBill, Bill, Income, LedgerReconciliation, NewLedgerReconciliation, LedgerBillPayment, NewLedgerBillPayment, LedgerAccount, NewLedgerAccount, LedgerTransfer, NewLedgerTransfer};
+use crate::ledger_logic::AccountNet;
 use crate::schema;
 
+/// Returned when a change targets a ledger that has been closed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LedgerClosedError;
+
+impl fmt::Display for LedgerClosedError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "This ledger is closed. Reopen it to make changes.")
+    }
+}
+
 pub struct LedgerRepo {
     pg_connector: Rc<PgConnector>,
 }
@@ -27,6 +40,17 @@ impl LedgerRepo {
             .expect("Error loading ledgers")
     }
 
+    /// Ledgers dated between two days (inclusive), oldest first
+    pub fn find_between(&self, from: NaiveDate, through: NaiveDate) -> Vec<Ledger> {
+        let mut conn = self.pg_connector.get_connection();
+        schema::ledgers::table
+            .filter(schema::ledgers::date.ge(from))
+            .filter(schema::ledgers::date.le(through))
+            .order(schema::ledgers::date.asc())
+            .load::<Ledger>(&mut *conn)
+            .unwrap_or_default()
+    }
+
     pub fn find_by_id(&self, ledger_id: i32) -> Option<Ledger> {
         let mut conn = self.pg_connector.get_connection();
         schema::ledgers::table
@@ -35,13 +59,15 @@ impl LedgerRepo {
             .ok()
     }
 
-    pub fn create(&self, ledger_date: NaiveDate, ledger_name: String, ledger_bank_balance: BigDecimal, ledger_notes: Option<String>) -> Ledger {
+    pub fn create(&self, ledger_date: NaiveDate, ledger_name: String, ledger_bank_balance: BigDecimal, ledger_notes: Option<String>, ledger_previous_id: Option<i32>, ledger_account_id: Option<i32>) -> Ledger {
         let mut conn = self.pg_connector.get_connection();
         let new_ledger = NewLedger {
             date: ledger_date,
             name: ledger_name,
             bank_balance: ledger_bank_balance,
             notes: ledger_notes,
+            previous_ledger_id: ledger_previous_id,
+            account_id: ledger_account_id,
         };
 
         diesel::insert_into(schema::ledgers::table)
@@ -51,9 +77,10 @@ impl LedgerRepo {
             .expect("Error saving new ledger")
     }
 
-    pub fn update(&self, ledger_id: i32, ledger_date: NaiveDate, ledger_name: String, ledger_bank_balance: BigDecimal, ledger_notes: Option<String>) -> Ledger {
+    pub fn update(&self, ledger_id: i32, ledger_date: NaiveDate, ledger_name: String, ledger_bank_balance: BigDecimal, ledger_notes: Option<String>) -> Result<Ledger, LedgerClosedError> {
+        self.ensure_open(ledger_id)?;
         let mut conn = self.pg_connector.get_connection();
-        diesel::update(schema::ledgers::table.filter(schema::ledgers::id.eq(ledger_id)))
+        Ok(diesel::update(schema::ledgers::table.filter(schema::ledgers::id.eq(ledger_id)))
             .set((
                 schema::ledgers::date.eq(ledger_date),
                 schema::ledgers::name.eq(ledger_name),
@@ -62,14 +89,196 @@ impl LedgerRepo {
             ))
             .returning(Ledger::as_returning())
             .get_result(&mut *conn)
-            .expect("Error updating ledger")
+            .expect("Error updating ledger"))
+    }
+
+    pub fn delete(&self, ledger_id: i32) -> Result<bool, LedgerClosedError> {
+        self.ensure_open(ledger_id)?;
+        let mut conn = self.pg_connector.get_connection();
+        Ok(diesel::delete(schema::ledgers::table.filter(schema::ledgers::id.eq(ledger_id)))
+            .execute(&mut *conn)
+            .is_ok())
+    }
+
+    /// Choose the account this ledger draws from
+    pub fn set_account(&self, ledger_id: i32, ledger_account_id: Option<i32>) -> Result<bool, LedgerClosedError> {
+        self.ensure_open(ledger_id)?;
+        let mut conn = self.pg_connector.get_connection();
+        Ok(diesel::update(schema::ledgers::table.filter(schema::ledgers::id.eq(ledger_id)))
+            .set(schema::ledgers::account_id.eq(ledger_account_id))
+            .execute(&mut *conn)
+            .is_ok())
+    }
+
+    // Opening balance chain
+    pub fn set_previous_ledger(&self, ledger_id: i32, ledger_previous_id: Option<i32>) -> Result<bool, LedgerClosedError> {
+        self.ensure_open(ledger_id)?;
+        let mut conn = self.pg_connector.get_connection();
+        Ok(diesel::update(schema::ledgers::table.filter(schema::ledgers::id.eq(ledger_id)))
+            .set(schema::ledgers::previous_ledger_id.eq(ledger_previous_id))
+            .execute(&mut *conn)
+            .is_ok())
+    }
+
+    /// Later ledgers linked to this one, following the chain in order
+    pub fn find_successors(&self, ledger_id: i32) -> Vec<Ledger> {
+        let mut successors: Vec<Ledger> = Vec::new();
+        let mut current_id = ledger_id;
+
+        loop {
+            let next = {
+                let mut conn = self.pg_connector.get_connection();
+                schema::ledgers::table
+                    .filter(schema::ledgers::previous_ledger_id.eq(current_id))
+                    .order((schema::ledgers::date.asc(), schema::ledgers::id.asc()))
+                    .first::<Ledger>(&mut *conn)
+                    .ok()
+            };
+
+            match next {
+                // Guard against a chain that loops back on itself
+                Some(l) if l.id != ledger_id && !successors.iter().any(|s| s.id == l.id) => {
+                    current_id = l.id;
+                    successors.push(l);
+                }
+                _ => break,
+            }
+        }
+
+        successors
+    }
+
+    /// Walk back through `previous_ledger_id` links, nearest predecessor first
+    pub fn find_predecessors(&self, ledger_id: i32) -> Vec<Ledger> {
+        let mut predecessors: Vec<Ledger> = Vec::new();
+        let mut previous_id = self.find_by_id(ledger_id).and_then(|l| l.previous_ledger_id);
+
+        while let Some(pid) = previous_id {
+            // Guard against a chain that loops back on itself
+            if pid == ledger_id || predecessors.iter().any(|p| p.id == pid) {
+                break;
+            }
+            match self.find_by_id(pid) {
+                Some(l) => {
+                    previous_id = l.previous_ledger_id;
+                    predecessors.push(l);
+                }
+                None => break,
+            }
+        }
+
+        predecessors
+    }
+
+    /// What a ledger carries forward: its latest reconciled balance, else its net
+    pub fn closing_balance(&self, ledger_id: i32) -> BigDecimal {
+        let net = self.find_by_id(ledger_id).and_then(|l| l.net);
+        let reconciled = self.find_reconciliations_by_ledger(ledger_id)
+            .into_iter()
+            .next()
+            .map(|r| r.actual_balance);
+        crate::ledger_logic::closing_balance(net.as_ref(), reconciled.as_ref())
+    }
+
+    /// Set each later ledger's opening balance to its predecessor's closing balance
+    pub fn ripple_opening_balances(&self, ledger_id: i32) -> Result<usize, LedgerClosedError> {
+        if self.find_by_id(ledger_id).is_none() {
+            return Ok(0);
+        }
+        let successors = self.find_successors(ledger_id);
+
+        if successors.iter().any(|l| l.is_closed) {
+            return Err(LedgerClosedError);
+        }
+
+        let reconciled: HashMap<i32, BigDecimal> = self.find_latest_reconciliations()
+            .into_iter()
+            .map(|r| (r.ledger_id, r.actual_balance))
+            .collect();
+        let chain: Vec<(i32, BigDecimal, BigDecimal, Option<BigDecimal>)> = successors
+            .into_iter()
+            .map(|l| {
+                let balance = reconciled.get(&l.id).cloned();
+                (l.id, l.income, l.expenses, balance)
+            })
+            .collect();
+        let openings = crate::ledger_logic::plan_opening_balance_ripple(&self.closing_balance(ledger_id), &chain);
+
+        let mut updated_count = 0;
+        {
+            let mut conn = self.pg_connector.get_connection();
+            for (successor_id, opening_balance) in &openings {
+                let updated = diesel::update(schema::ledgers::table.filter(schema::ledgers::id.eq(successor_id)))
+                    .set(schema::ledgers::bank_balance.eq(opening_balance))
+                    .execute(&mut *conn)
+                    .is_ok();
+                if updated {
+                    updated_count += 1;
+                }
+            }
+        }
+
+        // Ledgers split across accounts carry each account's net instead
+        let mut previous_id = ledger_id;
+        for (successor_id, _) in openings {
+            self.carry_account_openings(successor_id, previous_id)?;
+            previous_id = successor_id;
+        }
+
+        Ok(updated_count)
+    }
+
+    // Closing a ledger period
+    pub fn close(&self, ledger_id: i32) -> bool {
+        let mut conn = self.pg_connector.get_connection();
+        let closed = diesel::update(schema::ledgers::table.filter(schema::ledgers::id.eq(ledger_id)))
+            .set((
+                schema::ledgers::is_closed.eq(true),
+                schema::ledgers::closed_at.eq(Some(chrono::Local::now().naive_local())),
+            ))
+            .execute(&mut *conn)
+            .is_ok();
+
+        if closed {
+            log::info!("Ledger {} closed", ledger_id);
+        }
+        closed
     }
 
-    pub fn delete(&self, ledger_id: i32) -> bool {
+    pub fn reopen(&self, ledger_id: i32) -> bool {
         let mut conn = self.pg_connector.get_connection();
-        diesel::delete(schema::ledgers::table.filter(schema::ledgers::id.eq(ledger_id)))
+        let reopened = diesel::update(schema::ledgers::table.filter(schema::ledgers::id.eq(ledger_id)))
+            .set((
+                schema::ledgers::is_closed.eq(false),
+                schema::ledgers::closed_at.eq(None::<chrono::NaiveDateTime>),
+            ))
             .execute(&mut *conn)
-            .is_ok()
+            .is_ok();
+
+        if reopened {
+            log::warn!("Ledger {} reopened; historical totals may change", ledger_id);
+        }
+        reopened
+    }
+
+    pub fn ensure_open(&self, ledger_id: i32) -> Result<(), LedgerClosedError> {
+        super::ensure_ledger_open(&self.pg_connector, ledger_id)
+    }
+
+    fn ensure_ledger_bill_open(&self, ledger_bill_id: i32) -> Result<(), LedgerClosedError> {
+        let ledger_id = {
+            let mut conn = self.pg_connector.get_connection();
+            schema::ledger_bills::table
+                .find(ledger_bill_id)
+                .select(schema::ledger_bills::ledger_id)
+                .first::<i32>(&mut *conn)
+                .ok()
+        };
+
+        match ledger_id {
+            Some(lid) => self.ensure_open(lid),
+            None => Ok(()),
+        }
     }
 
     // Ledger detail queries
@@ -82,6 +291,31 @@ impl LedgerRepo {
             .unwrap_or_default()
     }
 
+    /// Unpaid bills across every open ledger, with their bill templates
+    pub fn find_unpaid_bills_in_open_ledgers(&self) -> Vec<(LedgerBill, Bill)> {
+        let mut conn = self.pg_connector.get_connection();
+        schema::ledger_bills::table
+            .inner_join(schema::ledgers::table)
+            .inner_join(schema::bills::table)
+            .filter(schema::ledger_bills::is_payed.eq(false))
+            .filter(schema::ledgers::is_closed.eq(false))
+            .select((LedgerBill::as_select(), Bill::as_select()))
+            .load(&mut *conn)
+            .unwrap_or_default()
+    }
+
+    /// A bill's amount in every ledger it was added to, as (ledger date, amount), oldest first
+    pub fn find_bill_amount_history(&self, bill_id: i32) -> Vec<(NaiveDate, BigDecimal)> {
+        let mut conn = self.pg_connector.get_connection();
+        schema::ledger_bills::table
+            .inner_join(schema::ledgers::table)
+            .filter(schema::ledger_bills::bill_id.eq(bill_id))
+            .order(schema::ledgers::date.asc())
+            .select((schema::ledgers::date, schema::ledger_bills::amount))
+            .load(&mut *conn)
+            .unwrap_or_default()
+    }
+
     pub fn find_incomes_by_ledger(&self, ledger_id: i32) -> Vec<Income> {
         let mut conn = self.pg_connector.get_connection();
         schema::incomes::table
@@ -90,7 +324,8 @@ impl LedgerRepo {
             .unwrap_or_default()
     }
 
-    pub fn create_ledger_bill(&self, ledger_id: i32, bill_id: i32, bill_amount: BigDecimal, bill_due_day: Option<NaiveDate>, bill_is_payed: bool, bill_notes: Option<String>) -> LedgerBill {
+    pub fn create_ledger_bill(&self, ledger_id: i32, bill_id: i32, bill_amount: BigDecimal, bill_due_day: Option<NaiveDate>, bill_is_payed: bool, bill_notes: Option<String>) -> Result<LedgerBill, LedgerClosedError> {
+        self.ensure_open(ledger_id)?;
         let mut conn = self.pg_connector.get_connection();
         let new_ledger_bill = NewLedgerBill {
             ledger_id,
@@ -99,18 +334,22 @@ impl LedgerRepo {
             due_day: bill_due_day,
             is_payed: bill_is_payed,
             notes: bill_notes,
+            income_id: None,
+            account_id: None,
+            tax_category: None,
         };
 
-        diesel::insert_into(schema::ledger_bills::table)
+        Ok(diesel::insert_into(schema::ledger_bills::table)
             .values(&new_ledger_bill)
             .returning(LedgerBill::as_returning())
             .get_result(&mut *conn)
-            .expect("Error saving new ledger bill")
+            .expect("Error saving new ledger bill"))
     }
 
-    pub fn update_ledger_bill(&self, ledger_bill_id: i32, bill_amount: BigDecimal, bill_due_day: Option<NaiveDate>, bill_is_payed: bool, bill_notes: Option<String>) -> LedgerBill {
+    pub fn update_ledger_bill(&self, ledger_bill_id: i32, bill_amount: BigDecimal, bill_due_day: Option<NaiveDate>, bill_is_payed: bool, bill_notes: Option<String>) -> Result<LedgerBill, LedgerClosedError> {
+        self.ensure_ledger_bill_open(ledger_bill_id)?;
         let mut conn = self.pg_connector.get_connection();
-        diesel::update(schema::ledger_bills::table.filter(schema::ledger_bills::id.eq(ledger_bill_id)))
+        Ok(diesel::update(schema::ledger_bills::table.filter(schema::ledger_bills::id.eq(ledger_bill_id)))
             .set((
                 schema::ledger_bills::amount.eq(bill_amount),
                 schema::ledger_bills::due_day.eq(bill_due_day),
@@ -119,21 +358,406 @@ impl LedgerRepo {
             ))
             .returning(LedgerBill::as_returning())
             .get_result(&mut *conn)
-            .expect("Error updating ledger bill")
+            .expect("Error updating ledger bill"))
+    }
+
+    pub fn set_ledger_bill_paid(&self, ledger_bill_id: i32, bill_is_payed: bool) -> Result<bool, LedgerClosedError> {
+        self.ensure_ledger_bill_open(ledger_bill_id)?;
+        let mut conn = self.pg_connector.get_connection();
+        Ok(diesel::update(schema::ledger_bills::table.filter(schema::ledger_bills::id.eq(ledger_bill_id)))
+            .set(schema::ledger_bills::is_payed.eq(bill_is_payed))
+            .execute(&mut *conn)
+            .is_ok())
+    }
+
+    /// Pay a ledger bill from a specific account (None uses the ledger's account)
+    pub fn set_ledger_bill_account(&self, ledger_bill_id: i32, bill_account_id: Option<i32>) -> Result<bool, LedgerClosedError> {
+        self.ensure_ledger_bill_open(ledger_bill_id)?;
+        let mut conn = self.pg_connector.get_connection();
+        Ok(diesel::update(schema::ledger_bills::table.filter(schema::ledger_bills::id.eq(ledger_bill_id)))
+            .set(schema::ledger_bills::account_id.eq(bill_account_id))
+            .execute(&mut *conn)
+            .is_ok())
+    }
+
+    /// Override the bill's tax category for one ledger bill (None inherits the bill's)
+    pub fn set_ledger_bill_tax_category(&self, ledger_bill_id: i32, bill_tax_category: Option<String>) -> Result<bool, LedgerClosedError> {
+        self.ensure_ledger_bill_open(ledger_bill_id)?;
+        let mut conn = self.pg_connector.get_connection();
+        Ok(diesel::update(schema::ledger_bills::table.filter(schema::ledger_bills::id.eq(ledger_bill_id)))
+            .set(schema::ledger_bills::tax_category.eq(bill_tax_category))
+            .execute(&mut *conn)
+            .is_ok())
+    }
+
+    pub fn delete_ledger_bill(&self, ledger_bill_id: i32) -> Result<bool, LedgerClosedError> {
+        self.ensure_ledger_bill_open(ledger_bill_id)?;
+        let mut conn = self.pg_connector.get_connection();
+        Ok(diesel::delete(schema::ledger_bills::table.filter(schema::ledger_bills::id.eq(ledger_bill_id)))
+            .execute(&mut *conn)
+            .is_ok())
     }
 
-    pub fn delete_ledger_bill(&self, ledger_bill_id: i32) -> bool {
+    pub fn assign_income_to_ledger(&self, income_id: i32, ledger_id: i32) -> Result<bool, LedgerClosedError> {
+        self.ensure_open(ledger_id)?;
+        super::ensure_income_ledger_open(&self.pg_connector, income_id)?;
         let mut conn = self.pg_connector.get_connection();
-        diesel::delete(schema::ledger_bills::table.filter(schema::ledger_bills::id.eq(ledger_bill_id)))
+        Ok(diesel::update(schema::incomes::table.filter(schema::incomes::id.eq(income_id)))
+            .set(schema::incomes::ledger_id.eq(ledger_id))
             .execute(&mut *conn)
-            .is_ok()
+            .is_ok())
     }
 
-    pub fn unassign_income_from_ledger(&self, income_id: i32) -> bool {
+    pub fn unassign_income_from_ledger(&self, income_id: i32) -> Result<bool, LedgerClosedError> {
+        super::ensure_income_ledger_open(&self.pg_connector, income_id)?;
         let mut conn = self.pg_connector.get_connection();
-        diesel::update(schema::incomes::table.filter(schema::incomes::id.eq(income_id)))
+
+        // Bills allocated to this paycheck no longer have it available
+        let _ = diesel::update(schema::ledger_bills::table.filter(schema::ledger_bills::income_id.eq(income_id)))
+            .set(schema::ledger_bills::income_id.eq(None::<i32>))
+            .execute(&mut *conn);
+
+        Ok(diesel::update(schema::incomes::table.filter(schema::incomes::id.eq(income_id)))
             .set(schema::incomes::ledger_id.eq(None::<i32>))
             .execute(&mut *conn)
-            .is_ok()
+            .is_ok())
+    }
+
+    pub fn allocate_bill_to_income(&self, ledger_bill_id: i32, income_id: Option<i32>) -> Result<bool, LedgerClosedError> {
+        self.ensure_ledger_bill_open(ledger_bill_id)?;
+        let mut conn = self.pg_connector.get_connection();
+        Ok(diesel::update(schema::ledger_bills::table.filter(schema::ledger_bills::id.eq(ledger_bill_id)))
+            .set(schema::ledger_bills::income_id.eq(income_id))
+            .execute(&mut *conn)
+            .is_ok())
+    }
+
+    pub fn auto_allocate_bills(&self, ledger_id: i32) -> Result<usize, LedgerClosedError> {
+        self.ensure_open(ledger_id)?;
+        let ledger_bills: Vec<LedgerBill> = {
+            let mut conn = self.pg_connector.get_connection();
+            schema::ledger_bills::table
+                .filter(schema::ledger_bills::ledger_id.eq(ledger_id))
+                .load(&mut *conn)
+                .unwrap_or_default()
+        };
+        let incomes = self.find_incomes_by_ledger(ledger_id);
+
+        let bill_dates: Vec<(i32, Option<NaiveDate>)> = ledger_bills
+            .iter()
+            .map(|lb| (lb.id, lb.due_day))
+            .collect();
+        let income_dates: Vec<(i32, NaiveDate)> = incomes
+            .iter()
+            .map(|i| (i.id, i.date))
+            .collect();
+
+        let allocations = crate::ledger_logic::auto_allocate_bills(&bill_dates, &income_dates);
+
+        // Bills without a fitting paycheck lose any earlier allocation
+        let mut allocated_count = 0;
+        for (ledger_bill_id, income_id) in allocations {
+            if self.allocate_bill_to_income(ledger_bill_id, income_id)? && income_id.is_some() {
+                allocated_count += 1;
+            }
+        }
+
+        Ok(allocated_count)
+    }
+
+    // Ledger bill payment queries
+    pub fn find_payments_by_ledger_bill(&self, ledger_bill_id: i32) -> Vec<LedgerBillPayment> {
+        let mut conn = self.pg_connector.get_connection();
+        schema::ledger_bill_payments::table
+            .filter(schema::ledger_bill_payments::ledger_bill_id.eq(ledger_bill_id))
+            .order(schema::ledger_bill_payments::date.asc())
+            .load(&mut *conn)
+            .unwrap_or_default()
+    }
+
+    pub fn find_payments_by_ledger(&self, ledger_id: i32) -> Vec<LedgerBillPayment> {
+        let mut conn = self.pg_connector.get_connection();
+        schema::ledger_bill_payments::table
+            .inner_join(schema::ledger_bills::table)
+            .filter(schema::ledger_bills::ledger_id.eq(ledger_id))
+            .select(LedgerBillPayment::as_select())
+            .load(&mut *conn)
+            .unwrap_or_default()
+    }
+
+    pub fn create_payment(&self, ledger_bill_id: i32, payment_date: NaiveDate, payment_amount: BigDecimal, payment_notes: Option<String>) -> Result<LedgerBillPayment, LedgerClosedError> {
+        self.ensure_ledger_bill_open(ledger_bill_id)?;
+        let mut conn = self.pg_connector.get_connection();
+        let new_payment = NewLedgerBillPayment {
+            ledger_bill_id,
+            date: payment_date,
+            amount: payment_amount,
+            notes: payment_notes,
+        };
+
+        Ok(diesel::insert_into(schema::ledger_bill_payments::table)
+            .values(&new_payment)
+            .returning(LedgerBillPayment::as_returning())
+            .get_result(&mut *conn)
+            .expect("Error saving ledger bill payment"))
+    }
+
+    pub fn delete_payment(&self, payment_id: i32) -> Result<bool, LedgerClosedError> {
+        let ledger_bill_id = {
+            let mut conn = self.pg_connector.get_connection();
+            schema::ledger_bill_payments::table
+                .find(payment_id)
+                .select(schema::ledger_bill_payments::ledger_bill_id)
+                .first::<i32>(&mut *conn)
+                .ok()
+        };
+        if let Some(lbid) = ledger_bill_id {
+            self.ensure_ledger_bill_open(lbid)?;
+        }
+
+        let mut conn = self.pg_connector.get_connection();
+        Ok(diesel::delete(schema::ledger_bill_payments::table.filter(schema::ledger_bill_payments::id.eq(payment_id)))
+            .execute(&mut *conn)
+            .is_ok())
+    }
+
+    // Reconciliation queries
+    pub fn find_reconciliations_by_ledger(&self, ledger_id: i32) -> Vec<LedgerReconciliation> {
+        let mut conn = self.pg_connector.get_connection();
+        schema::ledger_reconciliations::table
+            .filter(schema::ledger_reconciliations::ledger_id.eq(ledger_id))
+            .order((schema::ledger_reconciliations::date.desc(), schema::ledger_reconciliations::id.desc()))
+            .load(&mut *conn)
+            .unwrap_or_default()
+    }
+
+    /// The most recent reconciliation of every ledger that has one
+    pub fn find_latest_reconciliations(&self) -> Vec<LedgerReconciliation> {
+        let mut conn = self.pg_connector.get_connection();
+        schema::ledger_reconciliations::table
+            .distinct_on(schema::ledger_reconciliations::ledger_id)
+            .order((
+                schema::ledger_reconciliations::ledger_id,
+                schema::ledger_reconciliations::date.desc(),
+                schema::ledger_reconciliations::id.desc(),
+            ))
+            .load(&mut *conn)
+            .unwrap_or_default()
+    }
+
+    pub fn calculate_expected_balance(&self, ledger_id: i32, as_of: NaiveDate) -> Option<BigDecimal> {
+        let ledger = self.find_by_id(ledger_id)?;
+
+        // Expected paychecks haven't reached the bank yet
+        let incomes: Vec<(NaiveDate, BigDecimal)> = {
+            let mut conn = self.pg_connector.get_connection();
+            schema::incomes::table
+                .filter(schema::incomes::ledger_id.eq(ledger_id))
+                .filter(schema::incomes::is_expected.eq(false))
+                .select((schema::incomes::date, schema::incomes::amount))
+                .load(&mut *conn)
+                .unwrap_or_default()
+        };
+        let payments = self.find_payments_by_ledger(ledger_id);
+        // Bills flagged paid by hand count as paid on their due date, else the ledger date
+        let paid_bills: Vec<(NaiveDate, BigDecimal)> = self.find_ledger_bills_with_bill_names(ledger_id)
+            .into_iter()
+            .flat_map(|(lb, _)| {
+                let bill_payments: Vec<(NaiveDate, BigDecimal)> = payments
+                    .iter()
+                    .filter(|p| p.ledger_bill_id == lb.id)
+                    .map(|p| (p.date, p.amount.clone()))
+                    .collect();
+                crate::ledger_logic::dated_bill_payments(&lb.amount, lb.is_payed, &bill_payments, lb.due_day.unwrap_or(ledger.date))
+            })
+            .collect();
+
+        Some(crate::ledger_logic::calculate_expected_balance(&ledger.bank_balance, &incomes, &paid_bills, as_of))
+    }
+
+    pub fn create_reconciliation(&self, ledger_id: i32, reconciliation_date: NaiveDate, actual_balance: BigDecimal, expected_balance: BigDecimal, reconciliation_notes: Option<String>) -> LedgerReconciliation {
+        let mut conn = self.pg_connector.get_connection();
+        let new_reconciliation = NewLedgerReconciliation {
+            ledger_id,
+            date: reconciliation_date,
+            actual_balance,
+            expected_balance,
+            notes: reconciliation_notes,
+        };
+
+        diesel::insert_into(schema::ledger_reconciliations::table)
+            .values(&new_reconciliation)
+            .returning(LedgerReconciliation::as_returning())
+            .get_result(&mut *conn)
+            .expect("Error saving reconciliation")
+    }
+
+    // Per-account balances
+    pub fn find_ledger_accounts(&self, ledger_id: i32) -> Vec<LedgerAccount> {
+        let mut conn = self.pg_connector.get_connection();
+        schema::ledger_accounts::table
+            .filter(schema::ledger_accounts::ledger_id.eq(ledger_id))
+            .order(schema::ledger_accounts::id.asc())
+            .load(&mut *conn)
+            .unwrap_or_default()
+    }
+
+    /// Set an account's opening balance in a ledger; the ledger's bank balance becomes the sum
+    pub fn set_account_opening(&self, ledger_id: i32, account_id: i32, opening_balance: BigDecimal) -> Result<LedgerAccount, LedgerClosedError> {
+        self.ensure_open(ledger_id)?;
+        let ledger_account = {
+            let mut conn = self.pg_connector.get_connection();
+            let new_ledger_account = NewLedgerAccount {
+                ledger_id,
+                account_id,
+                opening_balance: opening_balance.clone(),
+            };
+
+            diesel::insert_into(schema::ledger_accounts::table)
+                .values(&new_ledger_account)
+                .on_conflict((schema::ledger_accounts::ledger_id, schema::ledger_accounts::account_id))
+                .do_update()
+                .set(schema::ledger_accounts::opening_balance.eq(opening_balance))
+                .returning(LedgerAccount::as_returning())
+                .get_result(&mut *conn)
+                .expect("Error saving ledger account")
+        };
+        self.sync_bank_balance(ledger_id);
+
+        Ok(ledger_account)
+    }
+
+    pub fn remove_ledger_account(&self, ledger_id: i32, account_id: i32) -> Result<bool, LedgerClosedError> {
+        self.ensure_open(ledger_id)?;
+        let removed = {
+            let mut conn = self.pg_connector.get_connection();
+            diesel::delete(schema::ledger_accounts::table
+                .filter(schema::ledger_accounts::ledger_id.eq(ledger_id))
+                .filter(schema::ledger_accounts::account_id.eq(account_id)))
+                .execute(&mut *conn)
+                .is_ok()
+        };
+        self.sync_bank_balance(ledger_id);
+
+        Ok(removed)
+    }
+
+    /// Keep bank_balance equal to the per-account openings when a ledger has any
+    fn sync_bank_balance(&self, ledger_id: i32) {
+        let openings: Vec<BigDecimal> = self.find_ledger_accounts(ledger_id)
+            .into_iter()
+            .map(|la| la.opening_balance)
+            .collect();
+        if openings.is_empty() {
+            return;
+        }
+
+        let mut conn = self.pg_connector.get_connection();
+        let _ = diesel::update(schema::ledgers::table.filter(schema::ledgers::id.eq(ledger_id)))
+            .set(schema::ledgers::bank_balance.eq(openings.iter().sum::<BigDecimal>()))
+            .execute(&mut *conn);
+    }
+
+    /// Open each account where it closed in the previous ledger
+    /// Does nothing unless the previous ledger tracks per-account balances
+    pub fn carry_account_openings(&self, ledger_id: i32, previous_ledger_id: i32) -> Result<usize, LedgerClosedError> {
+        if self.find_ledger_accounts(previous_ledger_id).is_empty() {
+            return Ok(0);
+        }
+
+        let mut carried_count = 0;
+        for account_net in self.find_account_nets(previous_ledger_id) {
+            if let Some(account_id) = account_net.account_id {
+                self.set_account_opening(ledger_id, account_id, account_net.net)?;
+                carried_count += 1;
+            }
+        }
+
+        Ok(carried_count)
+    }
+
+    /// Opening, income, expenses, transfers and net for each account a ledger touches
+    pub fn find_account_nets(&self, ledger_id: i32) -> Vec<AccountNet> {
+        let ledger = match self.find_by_id(ledger_id) {
+            Some(l) => l,
+            None => return Vec::new(),
+        };
+
+        let ledger_accounts = self.find_ledger_accounts(ledger_id);
+        let openings: Vec<(Option<i32>, BigDecimal)> = if ledger_accounts.is_empty() {
+            vec![(ledger.account_id, ledger.bank_balance.clone())]
+        } else {
+            ledger_accounts
+                .into_iter()
+                .map(|la| (Some(la.account_id), la.opening_balance))
+                .collect()
+        };
+
+        let incomes: Vec<(Option<i32>, BigDecimal)> = self.find_incomes_by_ledger(ledger_id)
+            .into_iter()
+            .map(|i| (i.account_id, i.amount))
+            .collect();
+
+        let contributions: Vec<BigDecimal> = {
+            let mut conn = self.pg_connector.get_connection();
+            schema::ledger_savings_contributions::table
+                .filter(schema::ledger_savings_contributions::ledger_id.eq(ledger_id))
+                .select(schema::ledger_savings_contributions::amount)
+                .load(&mut *conn)
+                .unwrap_or_default()
+        };
+        let expenses: Vec<(Option<i32>, BigDecimal)> = self.find_ledger_bills_with_bill_names(ledger_id)
+            .into_iter()
+            .map(|(lb, _)| (lb.account_id, lb.amount))
+            .chain(contributions.into_iter().map(|amount| (None, amount)))
+            .collect();
+
+        let transfers: Vec<(i32, i32, BigDecimal)> = self.find_transfers_by_ledger(ledger_id)
+            .into_iter()
+            .map(|t| (t.from_account_id, t.to_account_id, t.amount))
+            .collect();
+
+        crate::ledger_logic::calculate_account_nets(&openings, &incomes, &expenses, &transfers, ledger.account_id)
+    }
+
+    // Transfers between accounts
+    pub fn find_transfers_by_ledger(&self, ledger_id: i32) -> Vec<LedgerTransfer> {
+        let mut conn = self.pg_connector.get_connection();
+        schema::ledger_transfers::table
+            .filter(schema::ledger_transfers::ledger_id.eq(ledger_id))
+            .order((schema::ledger_transfers::date.asc(), schema::ledger_transfers::id.asc()))
+            .load(&mut *conn)
+            .unwrap_or_default()
+    }
+
+    pub fn create_transfer(&self, ledger_id: i32, from_account_id: i32, to_account_id: i32, transfer_amount: BigDecimal, transfer_date: NaiveDate, transfer_notes: Option<String>) -> Result<LedgerTransfer, LedgerClosedError> {
+        self.ensure_open(ledger_id)?;
+        let mut conn = self.pg_connector.get_connection();
+        let new_transfer = NewLedgerTransfer {
+            ledger_id,
+            from_account_id,
+            to_account_id,
+            amount: transfer_amount,
+            date: transfer_date,
+            notes: transfer_notes,
+        };
+
+        Ok(diesel::insert_into(schema::ledger_transfers::table)
+            .values(&new_transfer)
+            .returning(LedgerTransfer::as_returning())
+            .get_result(&mut *conn)
+            .expect("Error saving transfer"))
+    }
+
+    pub fn delete_transfer(&self, ledger_id: i32, transfer_id: i32) -> Result<bool, LedgerClosedError> {
+        self.ensure_open(ledger_id)?;
+        let mut conn = self.pg_connector.get_connection();
+        Ok(diesel::delete(
+            schema::ledger_transfers::table
+                .filter(schema::ledger_transfers::id.eq(transfer_id))
+                .filter(schema::ledger_transfers::ledger_id.eq(ledger_id))
+        )
+        .execute(&mut *conn)
+        .is_ok())
     }
 }
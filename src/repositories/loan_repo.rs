@@ -0,0 +1,69 @@
+use std::rc::Rc;
+use diesel::prelude::*;
+use bigdecimal::BigDecimal;
+use chrono::NaiveDate;
+
+use crate::db::PgConnector;
+use crate::models::{Loan, NewLoan};
+use crate::schema::loans;
+
+pub struct LoanRepo {
+    pg_connector: Rc<PgConnector>,
+}
+
+impl LoanRepo {
+    pub fn new(pg_connector: Rc<PgConnector>) -> Self {
+        Self { pg_connector }
+    }
+
+    pub fn find_all(&self) -> Vec<Loan> {
+        let mut conn = self.pg_connector.get_connection();
+        loans::table
+            .load::<Loan>(&mut *conn)
+            .expect("Error loading loans")
+    }
+
+    pub fn find_by_bill(&self, loan_bill_id: i32) -> Option<Loan> {
+        let mut conn = self.pg_connector.get_connection();
+        loans::table
+            .filter(loans::bill_id.eq(loan_bill_id))
+            .first::<Loan>(&mut *conn)
+            .optional()
+            .unwrap_or_default()
+    }
+
+    /// Set a bill's loan terms, replacing any earlier ones
+    pub fn save(&self, loan_bill_id: i32, loan_principal: BigDecimal, loan_annual_rate: BigDecimal, loan_term_months: i32, loan_start_date: NaiveDate, loan_notes: Option<String>) -> Loan {
+        let mut conn = self.pg_connector.get_connection();
+        let new_loan = NewLoan {
+            bill_id: loan_bill_id,
+            principal: loan_principal.clone(),
+            annual_rate: loan_annual_rate.clone(),
+            term_months: loan_term_months,
+            start_date: loan_start_date,
+            notes: loan_notes.clone(),
+        };
+
+        diesel::insert_into(loans::table)
+            .values(&new_loan)
+            .on_conflict(loans::bill_id)
+            .do_update()
+            .set((
+                loans::principal.eq(loan_principal),
+                loans::annual_rate.eq(loan_annual_rate),
+                loans::term_months.eq(loan_term_months),
+                loans::start_date.eq(loan_start_date),
+                loans::notes.eq(loan_notes),
+            ))
+            .returning(Loan::as_returning())
+            .get_result(&mut *conn)
+            .expect("Error saving loan")
+    }
+
+    pub fn delete(&self, loan_id: i32) -> bool {
+        let mut conn = self.pg_connector.get_connection();
+        diesel::delete(loans::table.filter(loans::id.eq(loan_id)))
+            .execute(&mut *conn)
+            .is_ok()
+    }
+}
@@ -1,13 +1,66 @@
 pub mod income_repo;
+pub mod income_source_repo;
 pub mod bill_repo;
 pub mod ledger_repo;
 pub mod pto_repo;
 pub mod pto_plan_repo;
 pub mod holiday_hours_repo;
+pub mod category_repo;
+pub mod envelope_repo;
+pub mod savings_goal_repo;
+pub mod debt_repo;
+pub mod loan_repo;
+pub mod account_repo;
+pub mod credit_card_repo;
+pub mod tax_repo;
+
+use diesel::prelude::*;
+
+use crate::db::PgConnector;
+use crate::schema::{incomes, ledgers};
 
 pub use income_repo::IncomeRepo;
+pub use income_source_repo::IncomeSourceRepo;
 pub use bill_repo::BillRepo;
-pub use ledger_repo::LedgerRepo;
+pub use ledger_repo::{LedgerRepo, LedgerClosedError};
 pub use pto_repo::PtoRepo;
 pub use pto_plan_repo::PtoPlanRepo;
 pub use holiday_hours_repo::HolidayHoursRepo;
+pub use category_repo::CategoryRepo;
+pub use envelope_repo::EnvelopeRepo;
+pub use savings_goal_repo::SavingsGoalRepo;
+pub use debt_repo::DebtRepo;
+pub use loan_repo::LoanRepo;
+pub use account_repo::AccountRepo;
+pub use credit_card_repo::CreditCardRepo;
+pub use tax_repo::TaxRepo;
+
+/// Shared by every repo that writes into a ledger; a missing ledger counts as open
+pub(crate) fn ensure_ledger_open(pg_connector: &PgConnector, ledger_id: i32) -> Result<(), LedgerClosedError> {
+    let mut conn = pg_connector.get_connection();
+    let is_closed = ledgers::table
+        .find(ledger_id)
+        .select(ledgers::is_closed)
+        .first::<bool>(&mut *conn)
+        .unwrap_or(false);
+
+    if is_closed { Err(LedgerClosedError) } else { Ok(()) }
+}
+
+/// Incomes assigned to a closed ledger are locked along with it
+pub(crate) fn ensure_income_ledger_open(pg_connector: &PgConnector, income_id: i32) -> Result<(), LedgerClosedError> {
+    let ledger_id = {
+        let mut conn = pg_connector.get_connection();
+        incomes::table
+            .find(income_id)
+            .select(incomes::ledger_id)
+            .first::<Option<i32>>(&mut *conn)
+            .ok()
+            .flatten()
+    };
+
+    match ledger_id {
+        Some(lid) => ensure_ledger_open(pg_connector, lid),
+        None => Ok(()),
+    }
+}
@@ -0,0 +1,179 @@
+use std::rc::Rc;
+use diesel::prelude::*;
+use bigdecimal::BigDecimal;
+use chrono::NaiveDate;
+
+use crate::db::PgConnector;
+use crate::models::{Ledger, LedgerSavingsContribution, NewLedgerSavingsContribution, NewSavingsGoal, SavingsGoal};
+use crate::repositories::{self, LedgerClosedError};
+use crate::schema::{ledger_savings_contributions, ledgers, savings_goals};
+
+pub struct SavingsGoalRepo {
+    pg_connector: Rc<PgConnector>,
+}
+
+impl SavingsGoalRepo {
+    pub fn new(pg_connector: Rc<PgConnector>) -> Self {
+        Self { pg_connector }
+    }
+
+    pub fn find_all(&self) -> Vec<SavingsGoal> {
+        let mut conn = self.pg_connector.get_connection();
+        savings_goals::table
+            .order((savings_goals::target_date.asc(), savings_goals::name.asc()))
+            .load::<SavingsGoal>(&mut *conn)
+            .expect("Error loading savings goals")
+    }
+
+    pub fn create(&self, goal_name: String, goal_target_amount: BigDecimal, goal_target_date: NaiveDate, goal_bill_id: Option<i32>, goal_notes: Option<String>) -> SavingsGoal {
+        let mut conn = self.pg_connector.get_connection();
+        let new_goal = NewSavingsGoal {
+            name: goal_name,
+            target_amount: goal_target_amount,
+            target_date: goal_target_date,
+            bill_id: goal_bill_id,
+            notes: goal_notes,
+        };
+
+        diesel::insert_into(savings_goals::table)
+            .values(&new_goal)
+            .returning(SavingsGoal::as_returning())
+            .get_result(&mut *conn)
+            .expect("Error saving new savings goal")
+    }
+
+    pub fn update(&self, goal_id: i32, goal_name: String, goal_target_amount: BigDecimal, goal_target_date: NaiveDate, goal_bill_id: Option<i32>, goal_notes: Option<String>) -> SavingsGoal {
+        let mut conn = self.pg_connector.get_connection();
+        diesel::update(savings_goals::table.filter(savings_goals::id.eq(goal_id)))
+            .set((
+                savings_goals::name.eq(goal_name),
+                savings_goals::target_amount.eq(goal_target_amount),
+                savings_goals::target_date.eq(goal_target_date),
+                savings_goals::bill_id.eq(goal_bill_id),
+                savings_goals::notes.eq(goal_notes),
+            ))
+            .returning(SavingsGoal::as_returning())
+            .get_result(&mut *conn)
+            .expect("Error updating savings goal")
+    }
+
+    pub fn delete(&self, goal_id: i32) -> bool {
+        let mut conn = self.pg_connector.get_connection();
+        diesel::delete(savings_goals::table.filter(savings_goals::id.eq(goal_id)))
+            .execute(&mut *conn)
+            .is_ok()
+    }
+
+    // Contributions
+
+    pub fn find_contributions(&self) -> Vec<LedgerSavingsContribution> {
+        let mut conn = self.pg_connector.get_connection();
+        ledger_savings_contributions::table
+            .load::<LedgerSavingsContribution>(&mut *conn)
+            .unwrap_or_default()
+    }
+
+    pub fn find_contributions_by_ledger(&self, ledger_id: i32) -> Vec<LedgerSavingsContribution> {
+        let mut conn = self.pg_connector.get_connection();
+        ledger_savings_contributions::table
+            .filter(ledger_savings_contributions::ledger_id.eq(ledger_id))
+            .order(ledger_savings_contributions::id.asc())
+            .load::<LedgerSavingsContribution>(&mut *conn)
+            .unwrap_or_default()
+    }
+
+    /// A goal's contributions with their ledgers, oldest ledger first
+    pub fn find_contribution_history(&self, goal_id: i32) -> Vec<(LedgerSavingsContribution, Ledger)> {
+        let mut conn = self.pg_connector.get_connection();
+        ledger_savings_contributions::table
+            .inner_join(ledgers::table)
+            .filter(ledger_savings_contributions::savings_goal_id.eq(goal_id))
+            .order((ledgers::date.asc(), ledgers::id.asc()))
+            .select((LedgerSavingsContribution::as_select(), Ledger::as_select()))
+            .load::<(LedgerSavingsContribution, Ledger)>(&mut *conn)
+            .unwrap_or_default()
+    }
+
+    /// Add this month's contribution line for every goal still being saved for
+    /// The amount is what's needed given contributions planned in other ledgers
+    pub fn generate_contributions(&self, ledger_id: i32, ledger_date: NaiveDate) -> Result<usize, LedgerClosedError> {
+        repositories::ensure_ledger_open(&self.pg_connector, ledger_id)?;
+        let contributions = self.find_contributions();
+        let mut added = 0;
+
+        for goal in self.find_all() {
+            if crate::budget_logic::months_until(ledger_date, goal.target_date) == 0 {
+                continue;
+            }
+
+            let saved: BigDecimal = contributions
+                .iter()
+                .filter(|c| c.savings_goal_id == goal.id && c.ledger_id != ledger_id)
+                .map(|c| c.amount.clone())
+                .sum();
+            let amount = crate::budget_logic::calculate_monthly_contribution(&goal.target_amount, &saved, ledger_date, goal.target_date);
+            if amount <= BigDecimal::from(0) {
+                continue;
+            }
+
+            let new_contribution = NewLedgerSavingsContribution {
+                ledger_id,
+                savings_goal_id: goal.id,
+                amount,
+            };
+
+            let mut conn = self.pg_connector.get_connection();
+            added += diesel::insert_into(ledger_savings_contributions::table)
+                .values(&new_contribution)
+                .on_conflict_do_nothing()
+                .execute(&mut *conn)
+                .expect("Error saving savings contribution");
+        }
+
+        Ok(added)
+    }
+
+    pub fn update_contribution(&self, contribution_id: i32, contribution_amount: BigDecimal) -> Result<LedgerSavingsContribution, LedgerClosedError> {
+        self.ensure_contribution_open(contribution_id)?;
+        let mut conn = self.pg_connector.get_connection();
+        Ok(diesel::update(ledger_savings_contributions::table.filter(ledger_savings_contributions::id.eq(contribution_id)))
+            .set(ledger_savings_contributions::amount.eq(contribution_amount))
+            .returning(LedgerSavingsContribution::as_returning())
+            .get_result(&mut *conn)
+            .expect("Error updating savings contribution"))
+    }
+
+    pub fn set_contribution_transferred(&self, contribution_id: i32, transferred: bool) -> Result<LedgerSavingsContribution, LedgerClosedError> {
+        self.ensure_contribution_open(contribution_id)?;
+        let mut conn = self.pg_connector.get_connection();
+        Ok(diesel::update(ledger_savings_contributions::table.filter(ledger_savings_contributions::id.eq(contribution_id)))
+            .set(ledger_savings_contributions::is_transferred.eq(transferred))
+            .returning(LedgerSavingsContribution::as_returning())
+            .get_result(&mut *conn)
+            .expect("Error updating savings contribution"))
+    }
+
+    pub fn delete_contribution(&self, contribution_id: i32) -> Result<bool, LedgerClosedError> {
+        self.ensure_contribution_open(contribution_id)?;
+        let mut conn = self.pg_connector.get_connection();
+        Ok(diesel::delete(ledger_savings_contributions::table.filter(ledger_savings_contributions::id.eq(contribution_id)))
+            .execute(&mut *conn)
+            .is_ok())
+    }
+
+    fn ensure_contribution_open(&self, contribution_id: i32) -> Result<(), LedgerClosedError> {
+        let ledger_id = {
+            let mut conn = self.pg_connector.get_connection();
+            ledger_savings_contributions::table
+                .find(contribution_id)
+                .select(ledger_savings_contributions::ledger_id)
+                .first::<i32>(&mut *conn)
+                .ok()
+        };
+
+        match ledger_id {
+            Some(lid) => repositories::ensure_ledger_open(&self.pg_connector, lid),
+            None => Ok(()),
+        }
+    }
+}
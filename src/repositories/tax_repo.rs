@@ -0,0 +1,106 @@
+use std::rc::Rc;
+use diesel::prelude::*;
+use bigdecimal::BigDecimal;
+use chrono::NaiveDate;
+
+use crate::db::PgConnector;
+use crate::models::TaxCategory;
+use crate::schema::{bills, income_sources, incomes, ledger_bill_payments, ledger_bills, ledgers};
+use crate::ledger_logic;
+use crate::tax_logic::{self, TaxLine, TaxLineKind};
+
+/// ledger bill id, amount, paid flag, due day, ledger date, ledger bill category, catalog bill category, bill name
+type TaxBillRow = (i32, BigDecimal, bool, Option<NaiveDate>, NaiveDate, Option<String>, Option<String>, String);
+
+/// amount, date, category, notes, source name
+type ReceivedIncomeRow = (BigDecimal, NaiveDate, Option<String>, Option<String>, Option<String>);
+
+pub struct TaxRepo {
+    pg_connector: Rc<PgConnector>,
+}
+
+impl TaxRepo {
+    pub fn new(pg_connector: Rc<PgConnector>) -> Self {
+        Self { pg_connector }
+    }
+
+    /// Bill payments and received incomes with a tax category in a calendar year
+    pub fn find_tax_lines(&self, year: i32) -> Vec<TaxLine> {
+        let (Some(year_start), Some(year_end)) = (NaiveDate::from_ymd_opt(year, 1, 1), NaiveDate::from_ymd_opt(year, 12, 31)) else {
+            return Vec::new();
+        };
+
+        let mut conn = self.pg_connector.get_connection();
+
+        let tagged_bills: Vec<TaxBillRow> = ledger_bills::table
+            .inner_join(ledgers::table)
+            .inner_join(bills::table)
+            .filter(ledger_bills::tax_category.is_not_null().or(bills::tax_category.is_not_null()))
+            .select((
+                ledger_bills::id,
+                ledger_bills::amount,
+                ledger_bills::is_payed,
+                ledger_bills::due_day,
+                ledgers::date,
+                ledger_bills::tax_category,
+                bills::tax_category,
+                bills::name,
+            ))
+            .load(&mut *conn)
+            .unwrap_or_default();
+
+        let tagged_ids: Vec<i32> = tagged_bills.iter().map(|row| row.0).collect();
+        let payments: Vec<(i32, NaiveDate, BigDecimal)> = ledger_bill_payments::table
+            .filter(ledger_bill_payments::ledger_bill_id.eq_any(&tagged_ids))
+            .select((ledger_bill_payments::ledger_bill_id, ledger_bill_payments::date, ledger_bill_payments::amount))
+            .load(&mut *conn)
+            .unwrap_or_default();
+
+        let received: Vec<ReceivedIncomeRow> = incomes::table
+            .left_join(income_sources::table)
+            .filter(incomes::is_expected.eq(false))
+            .filter(incomes::tax_category.is_not_null())
+            .filter(incomes::date.ge(year_start))
+            .filter(incomes::date.le(year_end))
+            .select((incomes::amount, incomes::date, incomes::tax_category, incomes::notes, income_sources::name.nullable()))
+            .load(&mut *conn)
+            .unwrap_or_default();
+
+        // Each payment counts in the year it was made; bills flagged paid by hand count on their due day, else the ledger date
+        let mut lines: Vec<TaxLine> = tagged_bills
+            .into_iter()
+            .flat_map(|(ledger_bill_id, amount, is_payed, due_day, ledger_date, own_category, bill_category, name)| {
+                let bill_payments: Vec<(NaiveDate, BigDecimal)> = payments
+                    .iter()
+                    .filter(|(lbid, _, _)| *lbid == ledger_bill_id)
+                    .map(|(_, date, paid)| (*date, paid.clone()))
+                    .collect();
+                let category = tax_logic::effective_category(own_category.as_deref(), bill_category.as_deref());
+                ledger_logic::dated_bill_payments(&amount, is_payed, &bill_payments, due_day.unwrap_or(ledger_date))
+                    .into_iter()
+                    .filter(|(date, _)| *date >= year_start && *date <= year_end)
+                    .filter_map(move |(date, paid)| category.map(|category| TaxLine {
+                        category,
+                        kind: TaxLineKind::Expense,
+                        date,
+                        description: name.clone(),
+                        amount: paid,
+                    }))
+                    .collect::<Vec<TaxLine>>()
+            })
+            .collect();
+
+        lines.extend(received.into_iter().filter_map(|(amount, date, category, notes, source_name)| {
+            category.map(|c| TaxLine {
+                category: TaxCategory::from(c),
+                kind: TaxLineKind::Income,
+                date,
+                description: notes.or(source_name).unwrap_or_else(|| "Income".to_string()),
+                amount,
+            })
+        }));
+
+        lines.sort_by_key(|l| l.date);
+        lines
+    }
+}
@@ -0,0 +1,308 @@
+use std::cmp::Ordering;
+use std::rc::Rc;
+use std::str::FromStr;
+use bigdecimal::BigDecimal;
+use chrono::{Local, NaiveDate};
+use cursive::Cursive;
+use cursive::traits::*;
+use cursive::views::{Button, Dialog, EditView, HideableView, LinearLayout, ListView, Panel, SelectView, TextArea, TextView};
+use cursive_table_view::{TableView, TableViewItem};
+
+use crate::budget_logic;
+use crate::models;
+use crate::repositories::{BillRepo, SavingsGoalRepo};
+use crate::ui_helpers::toggle_buttons_visible;
+
+const GOAL_EDIT_BUTTON: &str = "savings_goal_edit_button";
+const GOAL_DELETE_BUTTON: &str = "savings_goal_delete_button";
+const GOAL_HISTORY_BUTTON: &str = "savings_goal_history_button";
+const TOGGLE_BUTTONS: &[&str] = &[GOAL_EDIT_BUTTON, GOAL_DELETE_BUTTON, GOAL_HISTORY_BUTTON];
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
+enum GoalColumn {
+    Name,
+    Target,
+    TargetDate,
+    Saved,
+    Progress,
+    Monthly,
+    Bill,
+}
+
+#[derive(Clone, Debug)]
+struct SavingsGoalDisplay {
+    id: i32,
+    name: String,
+    target_amount: BigDecimal,
+    target_date: NaiveDate,
+    bill_id: Option<i32>,
+    bill_name: Option<String>,
+    notes: Option<String>,
+    saved: BigDecimal,
+    planned: BigDecimal,
+    progress: BigDecimal,
+    monthly: BigDecimal,
+}
+
+impl TableViewItem<GoalColumn> for SavingsGoalDisplay {
+    fn to_column(&self, column: GoalColumn) -> String {
+        match column {
+            GoalColumn::Name => self.name.clone(),
+            GoalColumn::Target => format!("${}", self.target_amount),
+            GoalColumn::TargetDate => self.target_date.format("%d/%m/%Y").to_string(),
+            GoalColumn::Saved => format!("${} / ${}", self.saved, self.planned),
+            GoalColumn::Progress => format!("{}%", self.progress),
+            GoalColumn::Monthly => format!("${}", self.monthly),
+            GoalColumn::Bill => self.bill_name.clone().unwrap_or_else(|| "-".to_string()),
+        }
+    }
+
+    fn cmp(&self, other: &Self, column: GoalColumn) -> Ordering where Self: Sized {
+        match column {
+            GoalColumn::Name => self.name.cmp(&other.name),
+            GoalColumn::Target => self.target_amount.cmp(&other.target_amount),
+            GoalColumn::TargetDate => self.target_date.cmp(&other.target_date),
+            GoalColumn::Saved => self.saved.cmp(&other.saved),
+            GoalColumn::Progress => self.progress.cmp(&other.progress),
+            GoalColumn::Monthly => self.monthly.cmp(&other.monthly),
+            GoalColumn::Bill => self.bill_name.cmp(&other.bill_name),
+        }
+    }
+}
+
+fn load_goal_displays(savings_goal_repo: &SavingsGoalRepo, bill_repo: &BillRepo) -> Vec<SavingsGoalDisplay> {
+    let today = Local::now().date_naive();
+    let contributions = savings_goal_repo.find_contributions();
+    let bills = bill_repo.find_all();
+
+    savings_goal_repo.find_all()
+        .into_iter()
+        .map(|g| {
+            let goal_contributions: Vec<&models::LedgerSavingsContribution> = contributions
+                .iter()
+                .filter(|c| c.savings_goal_id == g.id)
+                .collect();
+            // Transferred money is saved; untransferred lines are still planned
+            let saved: BigDecimal = goal_contributions.iter()
+                .filter(|c| c.is_transferred)
+                .map(|c| c.amount.clone())
+                .sum();
+            let planned: BigDecimal = goal_contributions.iter()
+                .map(|c| c.amount.clone())
+                .sum();
+
+            SavingsGoalDisplay {
+                id: g.id,
+                progress: budget_logic::calculate_goal_progress(&g.target_amount, &saved),
+                monthly: budget_logic::calculate_monthly_contribution(&g.target_amount, &planned, today, g.target_date),
+                bill_name: g.bill_id
+                    .and_then(|bid| bills.iter().find(|b| b.id == bid))
+                    .map(|b| b.name.clone()),
+                name: g.name,
+                target_amount: g.target_amount,
+                target_date: g.target_date,
+                bill_id: g.bill_id,
+                notes: g.notes,
+                saved,
+                planned,
+            }
+        })
+        .collect()
+}
+
+pub fn show_savings_goal_table(siv: &mut Cursive, savings_goal_repo: &Rc<SavingsGoalRepo>, bill_repo: &Rc<BillRepo>) {
+    siv.pop_layer();
+
+    let goal_displays = load_goal_displays(savings_goal_repo, bill_repo);
+    let goal_count = goal_displays.len();
+
+    let table = TableView::<SavingsGoalDisplay, GoalColumn>::new()
+        .column(GoalColumn::Name, "Goal", |c| c)
+        .column(GoalColumn::Target, "Target", |c| c.width(12))
+        .column(GoalColumn::TargetDate, "By", |c| c.width(12))
+        .column(GoalColumn::Saved, "Saved / Planned", |c| c.width(22))
+        .column(GoalColumn::Progress, "%", |c| c.width(6))
+        .column(GoalColumn::Monthly, "Monthly", |c| c.width(12))
+        .column(GoalColumn::Bill, "Bill", |c| c.width(16))
+        .items(goal_displays);
+
+    let repo_add = Rc::clone(savings_goal_repo);
+    let bill_repo_add = Rc::clone(bill_repo);
+    let repo_edit = Rc::clone(savings_goal_repo);
+    let bill_repo_edit = Rc::clone(bill_repo);
+    let repo_delete = Rc::clone(savings_goal_repo);
+    let bill_repo_delete = Rc::clone(bill_repo);
+    let repo_history = Rc::clone(savings_goal_repo);
+
+    let buttons = LinearLayout::horizontal()
+        .child(Button::new("Add", move |s| goal_form(s, None, &repo_add, &bill_repo_add)))
+        .child(HideableView::new(Button::new("Edit", move |s| {
+            if let Some(goal) = selected_goal(s) {
+                goal_form(s, Some(goal), &repo_edit, &bill_repo_edit);
+            }
+        })).with_name(GOAL_EDIT_BUTTON))
+        .child(HideableView::new(Button::new("History", move |s| {
+            if let Some(goal) = selected_goal(s) {
+                show_goal_history(s, &goal, &repo_history);
+            }
+        })).with_name(GOAL_HISTORY_BUTTON))
+        .child(HideableView::new(Button::new("Delete", move |s| delete_goal(s, &repo_delete, &bill_repo_delete))).with_name(GOAL_DELETE_BUTTON));
+
+    let content = LinearLayout::vertical()
+        .child(Panel::new(table.with_name("savings_goal_table").min_size((60, 15))).full_screen())
+        .child(buttons);
+
+    let screen = crate::common_layout::create_screen(
+        "Savings Goals",
+        content,
+        &crate::common_layout::view_footer()
+    );
+
+    siv.add_layer(screen);
+
+    toggle_buttons_visible(siv, goal_count, TOGGLE_BUTTONS);
+}
+
+fn selected_goal(siv: &mut Cursive) -> Option<SavingsGoalDisplay> {
+    siv.call_on_name("savings_goal_table", |v: &mut TableView<SavingsGoalDisplay, GoalColumn>| {
+        v.item().and_then(|i| v.borrow_item(i).cloned())
+    }).flatten()
+}
+
+fn goal_form(siv: &mut Cursive, existing: Option<SavingsGoalDisplay>, savings_goal_repo: &Rc<SavingsGoalRepo>, bill_repo: &Rc<BillRepo>) {
+    let title = if existing.is_some() { "Edit Savings Goal" } else { "Add Savings Goal" };
+
+    let name_value = existing.as_ref().map(|g| g.name.clone()).unwrap_or_default();
+    let target_value = existing.as_ref().map(|g| g.target_amount.to_string()).unwrap_or_default();
+    let date_value = existing
+        .as_ref()
+        .map(|g| g.target_date.format("%d/%m/%Y").to_string())
+        .unwrap_or_default();
+    let notes_value = existing.as_ref().and_then(|g| g.notes.clone()).unwrap_or_default();
+    let bill_value = existing.as_ref().and_then(|g| g.bill_id);
+    let goal_id = existing.map(|g| g.id);
+
+    // Optional bill this goal is saving up for
+    let bills = bill_repo.find_all();
+    let mut bill_select = SelectView::new().popup();
+    bill_select.add_item("(None)", None);
+    for bill in &bills {
+        bill_select.add_item(format!("{} (${})", bill.name, bill.amount), Some(bill.id));
+    }
+    let bill_index = bill_value
+        .and_then(|bid| bills.iter().position(|b| b.id == bid))
+        .map_or(0, |i| i + 1);
+    let bill_select = bill_select.selected(bill_index);
+
+    let repo = Rc::clone(savings_goal_repo);
+    let repo_bill = Rc::clone(bill_repo);
+    siv.add_layer(
+        Dialog::new()
+            .title(title)
+            .button("Ok", move |s| {
+                let name_str = s.call_on_name("goal_name_input", |v: &mut EditView| v.get_content()).unwrap();
+                let target_str = s.call_on_name("goal_target_input", |v: &mut EditView| v.get_content()).unwrap();
+                let date_str = s.call_on_name("goal_date_input", |v: &mut EditView| v.get_content()).unwrap();
+                let notes_str = s.call_on_name("goal_notes_input", |v: &mut TextArea| v.get_content().to_string()).unwrap();
+                let bill_id = s.call_on_name("goal_bill_select", |v: &mut SelectView<Option<i32>>| {
+                    v.selection().and_then(|b| *b)
+                }).flatten();
+
+                if name_str.trim().is_empty() {
+                    s.add_layer(Dialog::info("Name cannot be empty"));
+                    return;
+                }
+
+                let target_amount = match BigDecimal::from_str(target_str.trim()) {
+                    Ok(t) => t,
+                    Err(_) => {
+                        s.add_layer(Dialog::info("Invalid target amount format"));
+                        return;
+                    }
+                };
+
+                let target_date = match NaiveDate::parse_from_str(&date_str, "%d/%m/%Y") {
+                    Ok(d) => d,
+                    Err(_) => {
+                        s.add_layer(Dialog::info("Invalid date format. Use DD/MM/YYYY"));
+                        return;
+                    }
+                };
+
+                let notes = if notes_str.is_empty() { None } else { Some(notes_str) };
+                if let Some(record_id) = goal_id {
+                    repo.update(record_id, name_str.trim().to_string(), target_amount, target_date, bill_id, notes);
+                } else {
+                    repo.create(name_str.trim().to_string(), target_amount, target_date, bill_id, notes);
+                }
+
+                s.pop_layer();
+                show_savings_goal_table(s, &repo, &repo_bill);
+            })
+            .button("Cancel", |s| { s.pop_layer(); })
+            .content(
+                ListView::new()
+                    .child("Name", EditView::new().content(name_value).with_name("goal_name_input").fixed_width(20))
+                    .child("Target Amount", EditView::new().content(target_value).with_name("goal_target_input").fixed_width(20))
+                    .child("Target Date (DD/MM/YYYY)", EditView::new().content(date_value).with_name("goal_date_input").fixed_width(20))
+                    .child("Bill", bill_select.with_name("goal_bill_select"))
+                    .child("Notes", TextArea::new().content(notes_value).with_name("goal_notes_input").min_size((40, 3)))
+            )
+    );
+}
+
+fn show_goal_history(siv: &mut Cursive, goal: &SavingsGoalDisplay, savings_goal_repo: &Rc<SavingsGoalRepo>) {
+    let history = savings_goal_repo.find_contribution_history(goal.id);
+
+    if history.is_empty() {
+        siv.add_layer(Dialog::info("No contributions recorded for this goal yet"));
+        return;
+    }
+
+    let mut content = format!("{:<24}{:>12}{:>6}{:>14}\n", "LEDGER", "AMOUNT", "", "SAVED");
+    content.push_str("────────────────────────────────────────────────────────\n");
+    let mut running = BigDecimal::from(0);
+    for (contribution, ledger) in history {
+        if contribution.is_transferred {
+            running += contribution.amount.clone();
+        }
+        let ledger_str = format!(
+            "{} {}",
+            ledger.date.format("%d/%m/%Y"),
+            ledger.name.unwrap_or_default()
+        );
+        let amount_str = format!("${}", contribution.amount);
+        let running_str = format!("${}", running);
+        content.push_str(&format!(
+            "{:<24}{:>12}{:>6}{:>14}\n",
+            ledger_str,
+            amount_str,
+            if contribution.is_transferred { "✓" } else { "" },
+            running_str
+        ));
+    }
+    content.push_str(&format!("\nTarget: ${} by {}", goal.target_amount, goal.target_date.format("%d/%m/%Y")));
+
+    siv.add_layer(
+        Dialog::around(TextView::new(content).scrollable())
+            .title(format!("{} Progress", goal.name))
+            .button("Close", |s| { s.pop_layer(); })
+    );
+}
+
+fn delete_goal(siv: &mut Cursive, savings_goal_repo: &Rc<SavingsGoalRepo>, bill_repo: &Rc<BillRepo>) {
+    if let Some(goal) = selected_goal(siv) {
+        let repo = Rc::clone(savings_goal_repo);
+        let repo_bill = Rc::clone(bill_repo);
+        siv.add_layer(
+            Dialog::text(format!("Delete savings goal '{}' and its contribution lines?", goal.name))
+                .button("Yes", move |s| {
+                    repo.delete(goal.id);
+
+                    s.pop_layer();
+                    show_savings_goal_table(s, &repo, &repo_bill);
+                })
+                .button("No", |s| { s.pop_layer(); })
+        );
+    }
+}
@@ -1,5 +1,36 @@
 // @generated automatically by Diesel CLI.
 
+diesel::table! {
+    account_balances (id) {
+        id -> Int4,
+        account_id -> Int4,
+        date -> Date,
+        balance -> Numeric,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    accounts (id) {
+        id -> Int4,
+        name -> Varchar,
+        account_type -> Varchar,
+        notes -> Nullable<Text>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    bill_price_changes (id) {
+        id -> Int4,
+        bill_id -> Int4,
+        changed_on -> Date,
+        old_amount -> Numeric,
+        new_amount -> Numeric,
+        created_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     bills (id) {
         id -> Int4,
@@ -9,6 +40,76 @@ diesel::table! {
         is_auto_pay -> Bool,
         created_at -> Timestamp,
         notes -> Nullable<Text>,
+        category_id -> Nullable<Int4>,
+        envelope_id -> Nullable<Int4>,
+        tax_category -> Nullable<Varchar>,
+    }
+}
+
+diesel::table! {
+    card_charges (id) {
+        id -> Int4,
+        credit_card_id -> Int4,
+        date -> Date,
+        amount -> Numeric,
+        description -> Varchar,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    categories (id) {
+        id -> Int4,
+        name -> Varchar,
+        parent_id -> Nullable<Int4>,
+        monthly_budget -> Nullable<Numeric>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    credit_cards (id) {
+        id -> Int4,
+        account_id -> Int4,
+        bill_id -> Nullable<Int4>,
+        closing_day -> Int4,
+        due_day -> Int4,
+        notes -> Nullable<Text>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    debt_payments (id) {
+        id -> Int4,
+        debt_id -> Int4,
+        ledger_bill_id -> Int4,
+        amount -> Numeric,
+        interest -> Numeric,
+        principal -> Numeric,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    debts (id) {
+        id -> Int4,
+        name -> Varchar,
+        balance -> Numeric,
+        apr -> Numeric,
+        minimum_payment -> Numeric,
+        bill_id -> Nullable<Int4>,
+        notes -> Nullable<Text>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    envelopes (id) {
+        id -> Int4,
+        name -> Varchar,
+        notes -> Nullable<Text>,
+        created_at -> Timestamp,
     }
 }
 
@@ -23,6 +124,22 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    income_sources (id) {
+        id -> Int4,
+        name -> Varchar,
+        schedule -> Varchar,
+        anchor_date -> Date,
+        second_day -> Nullable<Int4>,
+        expected_amount -> Numeric,
+        account_id -> Nullable<Int4>,
+        is_active -> Bool,
+        notes -> Nullable<Text>,
+        created_at -> Timestamp,
+        category -> Varchar,
+    }
+}
+
 diesel::table! {
     incomes (id) {
         id -> Int4,
@@ -31,6 +148,33 @@ diesel::table! {
         created_at -> Timestamp,
         ledger_id -> Nullable<Int4>,
         notes -> Nullable<Text>,
+        account_id -> Nullable<Int4>,
+        income_source_id -> Nullable<Int4>,
+        is_expected -> Bool,
+        tax_category -> Nullable<Varchar>,
+        scheduled_date -> Nullable<Date>,
+        category -> Nullable<Varchar>,
+    }
+}
+
+diesel::table! {
+    ledger_accounts (id) {
+        id -> Int4,
+        ledger_id -> Int4,
+        account_id -> Int4,
+        opening_balance -> Numeric,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    ledger_bill_payments (id) {
+        id -> Int4,
+        ledger_bill_id -> Int4,
+        date -> Date,
+        amount -> Numeric,
+        notes -> Nullable<Text>,
+        created_at -> Timestamp,
     }
 }
 
@@ -44,6 +188,56 @@ diesel::table! {
         is_payed -> Bool,
         created_at -> Timestamp,
         notes -> Nullable<Text>,
+        income_id -> Nullable<Int4>,
+        account_id -> Nullable<Int4>,
+        tax_category -> Nullable<Varchar>,
+    }
+}
+
+diesel::table! {
+    ledger_envelopes (id) {
+        id -> Int4,
+        ledger_id -> Int4,
+        envelope_id -> Int4,
+        allocated -> Numeric,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    ledger_reconciliations (id) {
+        id -> Int4,
+        ledger_id -> Int4,
+        date -> Date,
+        actual_balance -> Numeric,
+        expected_balance -> Numeric,
+        delta -> Nullable<Numeric>,
+        notes -> Nullable<Text>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    ledger_savings_contributions (id) {
+        id -> Int4,
+        ledger_id -> Int4,
+        savings_goal_id -> Int4,
+        amount -> Numeric,
+        is_transferred -> Bool,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    ledger_transfers (id) {
+        id -> Int4,
+        ledger_id -> Int4,
+        from_account_id -> Int4,
+        to_account_id -> Int4,
+        amount -> Numeric,
+        date -> Date,
+        notes -> Nullable<Text>,
+        created_at -> Timestamp,
     }
 }
 
@@ -60,6 +254,40 @@ diesel::table! {
         name -> Nullable<Varchar>,
         total -> Nullable<Numeric>,
         notes -> Nullable<Text>,
+        is_closed -> Bool,
+        closed_at -> Nullable<Timestamp>,
+        previous_ledger_id -> Nullable<Int4>,
+        account_id -> Nullable<Int4>,
+    }
+}
+
+diesel::table! {
+    loans (id) {
+        id -> Int4,
+        bill_id -> Int4,
+        principal -> Numeric,
+        annual_rate -> Numeric,
+        term_months -> Int4,
+        start_date -> Date,
+        notes -> Nullable<Text>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    paystubs (id) {
+        id -> Int4,
+        income_id -> Int4,
+        gross -> Numeric,
+        federal_tax -> Numeric,
+        state_tax -> Numeric,
+        social_security -> Numeric,
+        medicare -> Numeric,
+        retirement_401k -> Numeric,
+        hsa -> Numeric,
+        insurance -> Numeric,
+        other_deductions -> Numeric,
+        created_at -> Timestamp,
     }
 }
 
@@ -92,18 +320,77 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    savings_goals (id) {
+        id -> Int4,
+        name -> Varchar,
+        target_amount -> Numeric,
+        target_date -> Date,
+        bill_id -> Nullable<Int4>,
+        notes -> Nullable<Text>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::joinable!(account_balances -> accounts (account_id));
+diesel::joinable!(bill_price_changes -> bills (bill_id));
+diesel::joinable!(bills -> categories (category_id));
+diesel::joinable!(bills -> envelopes (envelope_id));
+diesel::joinable!(card_charges -> credit_cards (credit_card_id));
+diesel::joinable!(credit_cards -> accounts (account_id));
+diesel::joinable!(credit_cards -> bills (bill_id));
+diesel::joinable!(debt_payments -> debts (debt_id));
+diesel::joinable!(debt_payments -> ledger_bills (ledger_bill_id));
+diesel::joinable!(debts -> bills (bill_id));
 diesel::joinable!(holiday_hours -> ptos (pto_id));
+diesel::joinable!(income_sources -> accounts (account_id));
+diesel::joinable!(incomes -> accounts (account_id));
+diesel::joinable!(incomes -> income_sources (income_source_id));
 diesel::joinable!(incomes -> ledgers (ledger_id));
+diesel::joinable!(ledger_accounts -> accounts (account_id));
+diesel::joinable!(ledger_accounts -> ledgers (ledger_id));
+diesel::joinable!(ledger_bill_payments -> ledger_bills (ledger_bill_id));
+diesel::joinable!(ledger_bills -> accounts (account_id));
 diesel::joinable!(ledger_bills -> bills (bill_id));
+diesel::joinable!(ledger_bills -> incomes (income_id));
 diesel::joinable!(ledger_bills -> ledgers (ledger_id));
+diesel::joinable!(ledger_envelopes -> envelopes (envelope_id));
+diesel::joinable!(ledger_envelopes -> ledgers (ledger_id));
+diesel::joinable!(ledger_reconciliations -> ledgers (ledger_id));
+diesel::joinable!(ledger_savings_contributions -> ledgers (ledger_id));
+diesel::joinable!(ledger_savings_contributions -> savings_goals (savings_goal_id));
+diesel::joinable!(ledger_transfers -> ledgers (ledger_id));
+diesel::joinable!(ledgers -> accounts (account_id));
+diesel::joinable!(loans -> bills (bill_id));
+diesel::joinable!(paystubs -> incomes (income_id));
 diesel::joinable!(pto_plan -> ptos (pto_id));
+diesel::joinable!(savings_goals -> bills (bill_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
+    account_balances,
+    accounts,
+    bill_price_changes,
     bills,
+    card_charges,
+    categories,
+    credit_cards,
+    debt_payments,
+    debts,
+    envelopes,
     holiday_hours,
+    income_sources,
     incomes,
+    ledger_accounts,
+    ledger_bill_payments,
     ledger_bills,
+    ledger_envelopes,
+    ledger_reconciliations,
+    ledger_savings_contributions,
+    ledger_transfers,
     ledgers,
+    loans,
+    paystubs,
     pto_plan,
     ptos,
+    savings_goals,
 );
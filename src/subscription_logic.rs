@@ -0,0 +1,150 @@
+use std::collections::HashMap;
+use bigdecimal::{BigDecimal, RoundingMode};
+use chrono::NaiveDate;
+
+/// A catalog bill as the subscriptions view sees it
+#[derive(Debug, Clone)]
+pub struct CatalogBill {
+    pub bill_id: i32,
+    pub name: String,
+    pub amount: BigDecimal,
+}
+
+/// A dated change to a bill's catalog amount
+#[derive(Debug, Clone, PartialEq)]
+pub struct PriceChange {
+    pub bill_id: i32,
+    pub changed_on: NaiveDate,
+    pub old_amount: BigDecimal,
+    pub new_amount: BigDecimal,
+}
+
+impl PriceChange {
+    /// Percent up or down from the old amount, one decimal place; None when the old amount was zero
+    pub fn percent(&self) -> Option<BigDecimal> {
+        let zero = BigDecimal::from(0);
+        if self.old_amount == zero {
+            return None;
+        }
+        let percent = (&self.new_amount - &self.old_amount) * BigDecimal::from(100) / &self.old_amount;
+        Some(percent.with_scale_round(1, RoundingMode::HalfUp))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SubscriptionRow {
+    pub bill_id: i32,
+    pub name: String,
+    pub monthly: BigDecimal,
+    pub annual: BigDecimal,
+    /// What the latest ledger has for this bill, if it's in that ledger
+    pub ledger_amount: Option<BigDecimal>,
+    pub changes: Vec<PriceChange>,
+}
+
+impl SubscriptionRow {
+    /// The latest ledger charged something other than the catalog amount
+    pub fn differs_from_catalog(&self) -> bool {
+        self.ledger_amount.as_ref().is_some_and(|amount| *amount != self.monthly)
+    }
+
+    pub fn last_change(&self) -> Option<&PriceChange> {
+        self.changes.last()
+    }
+}
+
+pub fn annualized(monthly: &BigDecimal) -> BigDecimal {
+    monthly * BigDecimal::from(12)
+}
+
+/// Total per bill across a ledger's lines, since a bill can appear more than once
+pub fn ledger_amounts_by_bill(lines: &[(i32, BigDecimal)]) -> HashMap<i32, BigDecimal> {
+    let mut totals: HashMap<i32, BigDecimal> = HashMap::new();
+    for (bill_id, amount) in lines {
+        *totals.entry(*bill_id).or_insert_with(|| BigDecimal::from(0)) += amount;
+    }
+    totals
+}
+
+/// One row per catalog bill, most expensive first; changes must be ordered oldest first
+pub fn subscription_rows(bills: &[CatalogBill], ledger_amounts: &HashMap<i32, BigDecimal>, changes: &[PriceChange]) -> Vec<SubscriptionRow> {
+    let mut rows: Vec<SubscriptionRow> = bills
+        .iter()
+        .map(|bill| SubscriptionRow {
+            bill_id: bill.bill_id,
+            name: bill.name.clone(),
+            monthly: bill.amount.clone(),
+            annual: annualized(&bill.amount),
+            ledger_amount: ledger_amounts.get(&bill.bill_id).cloned(),
+            changes: changes.iter().filter(|c| c.bill_id == bill.bill_id).cloned().collect(),
+        })
+        .collect();
+    rows.sort_by(|a, b| b.annual.cmp(&a.annual).then_with(|| a.name.cmp(&b.name)));
+    rows
+}
+
+/// Monthly and annualized totals across rows
+pub fn totals(rows: &[SubscriptionRow]) -> (BigDecimal, BigDecimal) {
+    let monthly: BigDecimal = rows.iter().map(|r| r.monthly.clone()).sum();
+    let annual = annualized(&monthly);
+    (monthly, annual)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn dec(value: &str) -> BigDecimal {
+        BigDecimal::from_str(value).unwrap()
+    }
+
+    fn change(bill_id: i32, day: u32, old: &str, new: &str) -> PriceChange {
+        PriceChange {
+            bill_id,
+            changed_on: NaiveDate::from_ymd_opt(2026, 10, day).unwrap(),
+            old_amount: dec(old),
+            new_amount: dec(new),
+        }
+    }
+
+    #[test]
+    fn test_rows_sort_by_annual_cost_and_flag_ledger_mismatch() {
+        let bills = vec![
+            CatalogBill { bill_id: 1, name: "Streaming".to_string(), amount: dec("17.99") },
+            CatalogBill { bill_id: 2, name: "Internet".to_string(), amount: dec("70.00") },
+            CatalogBill { bill_id: 3, name: "Music".to_string(), amount: dec("10.99") },
+        ];
+        let ledger_amounts = ledger_amounts_by_bill(&[(1, dec("15.49")), (2, dec("70"))]);
+        let changes = vec![change(1, 1, "13.99", "15.49"), change(1, 9, "15.49", "17.99")];
+
+        let rows = subscription_rows(&bills, &ledger_amounts, &changes);
+
+        let names: Vec<&str> = rows.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["Internet", "Streaming", "Music"]);
+        assert_eq!(rows[1].annual, dec("215.88"));
+        assert!(rows[1].differs_from_catalog());
+        assert!(!rows[0].differs_from_catalog());
+        assert!(!rows[2].differs_from_catalog());
+        assert_eq!(rows[1].last_change().map(|c| c.new_amount.clone()), Some(dec("17.99")));
+    }
+
+    #[test]
+    fn test_price_change_percent() {
+        assert_eq!(change(1, 1, "13.99", "15.49").percent(), Some(dec("10.7")));
+        assert_eq!(change(1, 1, "20.00", "15.00").percent(), Some(dec("-25.0")));
+        assert_eq!(change(1, 1, "0", "5").percent(), None);
+    }
+
+    #[test]
+    fn test_totals_and_repeated_ledger_lines() {
+        let bills = vec![
+            CatalogBill { bill_id: 1, name: "A".to_string(), amount: dec("10") },
+            CatalogBill { bill_id: 2, name: "B".to_string(), amount: dec("5.50") },
+        ];
+        let rows = subscription_rows(&bills, &HashMap::new(), &[]);
+
+        assert_eq!(totals(&rows), (dec("15.50"), dec("186")));
+        assert_eq!(ledger_amounts_by_bill(&[(1, dec("5")), (1, dec("5"))]).get(&1), Some(&dec("10")));
+    }
+}
@@ -0,0 +1,145 @@
+use std::rc::Rc;
+use bigdecimal::BigDecimal;
+use cursive::Cursive;
+use cursive::theme::{BaseColor, Color};
+use cursive::traits::*;
+use cursive::utils::markup::StyledString;
+use cursive::views::{Dialog, LinearLayout, Panel, SelectView, TextView};
+
+use crate::models::Ledger;
+use crate::repositories::{BillRepo, LedgerRepo};
+use crate::subscription_logic::{self, CatalogBill, PriceChange, SubscriptionRow};
+
+fn load_rows(bill_repo: &BillRepo, latest: Option<&Ledger>, ledger_repo: &LedgerRepo) -> Vec<SubscriptionRow> {
+    let bills: Vec<CatalogBill> = bill_repo
+        .find_all()
+        .into_iter()
+        .map(|b| CatalogBill { bill_id: b.id, name: b.name, amount: b.amount })
+        .collect();
+
+    let ledger_lines: Vec<(i32, BigDecimal)> = latest
+        .map(|ledger| {
+            ledger_repo
+                .find_ledger_bills_with_bill_names(ledger.id)
+                .into_iter()
+                .map(|(lb, _)| (lb.bill_id, lb.amount))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let changes: Vec<PriceChange> = bill_repo
+        .find_price_changes()
+        .into_iter()
+        .map(|c| PriceChange {
+            bill_id: c.bill_id,
+            changed_on: c.changed_on,
+            old_amount: c.old_amount,
+            new_amount: c.new_amount,
+        })
+        .collect();
+
+    subscription_logic::subscription_rows(&bills, &subscription_logic::ledger_amounts_by_bill(&ledger_lines), &changes)
+}
+
+pub fn show_subscriptions(siv: &mut Cursive, bill_repo: &Rc<BillRepo>, ledger_repo: &Rc<LedgerRepo>) {
+    let latest = ledger_repo.find_all().into_iter().max_by_key(|l| l.date);
+    let rows = Rc::new(load_rows(bill_repo, latest.as_ref(), ledger_repo));
+
+    siv.pop_layer();
+
+    let (monthly, annual) = subscription_logic::totals(&rows);
+    let mismatched = rows.iter().filter(|r| r.differs_from_catalog()).count();
+    let mut summary = format!("{} bills: ${} a month, ${} a year", rows.len(), monthly, annual);
+    match &latest {
+        Some(ledger) if mismatched > 0 => summary.push_str(&format!(
+            "\n{} differ from the catalog in {} ({}), highlighted below",
+            mismatched,
+            ledger.name.clone().unwrap_or_else(|| "the latest ledger".to_string()),
+            ledger.date.format("%d/%m/%Y")
+        )),
+        Some(ledger) => summary.push_str(&format!(
+            "\nEvery bill in {} matches the catalog",
+            ledger.name.clone().unwrap_or_else(|| "the latest ledger".to_string())
+        )),
+        None => summary.push_str("\nNo ledgers yet to compare against"),
+    }
+
+    let mut select = SelectView::<i32>::new();
+    for row in rows.iter() {
+        select.add_item(row_label(row), row.bill_id);
+    }
+    let rows_history = Rc::clone(&rows);
+    let select = select.on_submit(move |s, bill_id: &i32| {
+        if let Some(row) = rows_history.iter().find(|r| r.bill_id == *bill_id) {
+            show_price_history(s, row);
+        }
+    });
+
+    let header = format!(
+        "{:<24}{:>12}{:>14}{:>16}  {}",
+        "BILL", "MONTHLY", "ANNUAL", "LATEST LEDGER", "LAST PRICE CHANGE"
+    );
+
+    let content = LinearLayout::vertical()
+        .child(TextView::new(summary))
+        .child(Panel::new(
+            LinearLayout::vertical()
+                .child(TextView::new(header))
+                .child(select.with_name("subscription_select").scrollable())
+        ).title("Enter: price history").full_screen());
+
+    let screen = crate::common_layout::create_screen(
+        "Subscriptions",
+        content,
+        &crate::common_layout::view_footer()
+    );
+
+    siv.add_layer(screen);
+}
+
+/// Yellow when the latest ledger's amount differs from the catalog
+fn row_label(row: &SubscriptionRow) -> StyledString {
+    let ledger_str = row.ledger_amount.as_ref().map_or("-".to_string(), |a| format!("${}", a));
+    let change_str = row.last_change().map_or(String::new(), |c| {
+        format!("{} ${} → ${}", c.changed_on.format("%d/%m/%Y"), c.old_amount, c.new_amount)
+    });
+    let line = format!(
+        "{:<24}{:>12}{:>14}{:>16}  {}",
+        row.name,
+        format!("${}", row.monthly),
+        format!("${}", row.annual),
+        ledger_str,
+        change_str
+    );
+
+    if row.differs_from_catalog() {
+        StyledString::styled(line, Color::Light(BaseColor::Yellow))
+    } else {
+        StyledString::plain(line)
+    }
+}
+
+fn show_price_history(siv: &mut Cursive, row: &SubscriptionRow) {
+    if row.changes.is_empty() {
+        siv.add_layer(Dialog::info(format!("No price changes recorded for {}", row.name)));
+        return;
+    }
+
+    let mut text = format!("{:<12}{:>12}{:>12}{:>10}\n", "DATE", "FROM", "TO", "CHANGE");
+    for change in row.changes.iter().rev() {
+        let percent_str = change.percent().map_or(String::new(), |p| format!("{}%", p));
+        text.push_str(&format!(
+            "{:<12}{:>12}{:>12}{:>10}\n",
+            change.changed_on.format("%d/%m/%Y").to_string(),
+            format!("${}", change.old_amount),
+            format!("${}", change.new_amount),
+            percent_str
+        ));
+    }
+
+    siv.add_layer(
+        Dialog::around(TextView::new(text).scrollable())
+            .title(format!("{} Price History", row.name))
+            .button("Close", |s| { s.pop_layer(); })
+    );
+}
@@ -0,0 +1,156 @@
+use std::collections::BTreeMap;
+use bigdecimal::BigDecimal;
+use chrono::NaiveDate;
+
+use crate::income_logic::PaystubLines;
+use crate::models::TaxCategory;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TaxLineKind {
+    Expense,
+    Income,
+}
+
+impl TaxLineKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            TaxLineKind::Expense => "Expense",
+            TaxLineKind::Income => "Income",
+        }
+    }
+}
+
+/// One tax-relevant item: a paid ledger bill or a received income
+#[derive(Debug, Clone, PartialEq)]
+pub struct TaxLine {
+    pub category: TaxCategory,
+    pub kind: TaxLineKind,
+    pub date: NaiveDate,
+    pub description: String,
+    pub amount: BigDecimal,
+}
+
+/// A ledger bill's own category wins; otherwise it inherits the bill's
+pub fn effective_category(ledger_bill_category: Option<&str>, bill_category: Option<&str>) -> Option<TaxCategory> {
+    ledger_bill_category
+        .or(bill_category)
+        .map(|c| TaxCategory::from(c.to_string()))
+}
+
+/// Total and count per category and kind, in category order
+pub fn category_totals(lines: &[TaxLine]) -> Vec<(TaxCategory, TaxLineKind, BigDecimal, usize)> {
+    let mut grouped: BTreeMap<(TaxCategory, TaxLineKind), (BigDecimal, usize)> = BTreeMap::new();
+    for line in lines {
+        let entry = grouped.entry((line.category, line.kind)).or_insert((BigDecimal::from(0), 0));
+        entry.0 += &line.amount;
+        entry.1 += 1;
+    }
+
+    grouped
+        .into_iter()
+        .map(|((category, kind), (total, count))| (category, kind, total, count))
+        .collect()
+}
+
+/// Quote a CSV field when it holds a comma, quote or line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn csv_row(fields: &[&str]) -> String {
+    let quoted: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+    format!("{}\n", quoted.join(","))
+}
+
+/// The year's items, category totals and paystub withholding as one CSV sheet
+pub fn tax_csv(lines: &[TaxLine], withholding: &PaystubLines) -> String {
+    let mut csv = csv_row(&["Section", "Category", "Type", "Date", "Description", "Amount"]);
+
+    let mut sorted = lines.to_vec();
+    sorted.sort_by(|a, b| a.category.cmp(&b.category).then(a.date.cmp(&b.date)));
+    for line in &sorted {
+        csv.push_str(&csv_row(&[
+            "Item",
+            &line.category.to_string(),
+            line.kind.label(),
+            &line.date.format("%Y-%m-%d").to_string(),
+            &line.description,
+            &line.amount.to_string(),
+        ]));
+    }
+
+    for (category, kind, total, _) in category_totals(lines) {
+        csv.push_str(&csv_row(&["Total", &category.to_string(), kind.label(), "", "", &total.to_string()]));
+    }
+
+    csv.push_str(&csv_row(&["Withholding", "", "", "", "Gross Pay", &withholding.gross.to_string()]));
+    for (label, amount) in withholding.deduction_lines() {
+        csv.push_str(&csv_row(&["Withholding", "", "", "", label, &amount.to_string()]));
+    }
+
+    csv
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(category: TaxCategory, kind: TaxLineKind, day: u32, description: &str, amount: i32) -> TaxLine {
+        TaxLine {
+            category,
+            kind,
+            date: NaiveDate::from_ymd_opt(2026, 3, day).unwrap(),
+            description: description.to_string(),
+            amount: BigDecimal::from(amount),
+        }
+    }
+
+    #[test]
+    fn test_ledger_bill_category_overrides_bill() {
+        assert_eq!(effective_category(Some("Medical"), Some("Business")), Some(TaxCategory::Medical));
+        assert_eq!(effective_category(None, Some("HSA-Eligible")), Some(TaxCategory::HsaEligible));
+        assert_eq!(effective_category(None, None), None);
+    }
+
+    #[test]
+    fn test_category_totals_split_by_kind() {
+        let lines = vec![
+            line(TaxCategory::Business, TaxLineKind::Expense, 1, "Software", 30),
+            line(TaxCategory::Charitable, TaxLineKind::Expense, 2, "Food bank", 50),
+            line(TaxCategory::Business, TaxLineKind::Income, 3, "Consulting", 900),
+            line(TaxCategory::Business, TaxLineKind::Expense, 4, "Hosting", 20),
+        ];
+
+        let totals = category_totals(&lines);
+
+        assert_eq!(totals, vec![
+            (TaxCategory::Charitable, TaxLineKind::Expense, BigDecimal::from(50), 1),
+            (TaxCategory::Business, TaxLineKind::Expense, BigDecimal::from(50), 2),
+            (TaxCategory::Business, TaxLineKind::Income, BigDecimal::from(900), 1),
+        ]);
+    }
+
+    #[test]
+    fn test_csv_quotes_awkward_descriptions() {
+        let lines = vec![line(TaxCategory::Medical, TaxLineKind::Expense, 9, "Dr. \"Bob\", DDS", 120)];
+        let withholding = PaystubLines {
+            gross: BigDecimal::from(1000),
+            federal_tax: BigDecimal::from(100),
+            ..PaystubLines::default()
+        };
+
+        let csv = tax_csv(&lines, &withholding);
+        let rows: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(rows[0], "Section,Category,Type,Date,Description,Amount");
+        assert_eq!(rows[1], "Item,Medical,Expense,2026-03-09,\"Dr. \"\"Bob\"\", DDS\",120");
+        assert_eq!(rows[2], "Total,Medical,Expense,,,120");
+        assert_eq!(rows[3], "Withholding,,,,Gross Pay,1000");
+        assert_eq!(rows[4], "Withholding,,,,Federal Tax,100");
+        assert_eq!(rows.len(), 12);
+    }
+}
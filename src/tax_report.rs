@@ -0,0 +1,160 @@
+use std::rc::Rc;
+use chrono::{Datelike, Local, NaiveDate};
+use cursive::Cursive;
+use cursive::traits::*;
+use cursive::views::{Button, Dialog, EditView, LinearLayout, ListView, Panel, SelectView, TextView};
+
+use crate::income_logic::{self, PaystubLines};
+use crate::models::TaxCategory;
+use crate::repositories::{IncomeRepo, TaxRepo};
+use crate::tax_logic::{self, TaxLine};
+
+/// How many past years the year picker offers
+const TAX_YEARS_SHOWN: i32 = 6;
+
+/// Popup for a tax category; the first item stores None under `none_label`
+pub fn build_tax_category_select(none_label: &str, current: Option<&str>) -> SelectView<Option<String>> {
+    let mut select = SelectView::new().popup();
+    select.add_item(none_label, None);
+    for category in TaxCategory::all() {
+        select.add_item(category.to_string(), Some(category.to_string()));
+    }
+
+    let index = current
+        .and_then(|c| TaxCategory::all().iter().position(|t| t.to_string() == c))
+        .map_or(0, |i| i + 1);
+    select.selected(index)
+}
+
+/// Year-to-date paystub lines for a calendar year
+fn withholding_for(year: i32, income_repo: &IncomeRepo) -> PaystubLines {
+    let (Some(year_start), Some(year_end)) = (NaiveDate::from_ymd_opt(year, 1, 1), NaiveDate::from_ymd_opt(year, 12, 31)) else {
+        return PaystubLines::default();
+    };
+
+    let paystubs: Vec<PaystubLines> = income_repo
+        .find_paystubs_between(year_start, year_end)
+        .iter()
+        .map(crate::income_logic::to_paystub_lines)
+        .collect();
+    income_logic::paystub_totals(&paystubs)
+}
+
+pub fn show_tax_report(siv: &mut Cursive, tax_repo: &Rc<TaxRepo>, income_repo: &Rc<IncomeRepo>) {
+    siv.pop_layer();
+
+    let current_year = Local::now().year();
+    let initial_text = format_tax_report(&tax_repo.find_tax_lines(current_year), &withholding_for(current_year, income_repo));
+
+    let mut year_select = SelectView::new().popup();
+    for year in (current_year - TAX_YEARS_SHOWN + 1..=current_year).rev() {
+        year_select.add_item(year.to_string(), year);
+    }
+    let repo_year = Rc::clone(tax_repo);
+    let income_repo_year = Rc::clone(income_repo);
+    let year_select = year_select.on_submit(move |s, year: &i32| {
+        let text = format_tax_report(&repo_year.find_tax_lines(*year), &withholding_for(*year, &income_repo_year));
+        s.call_on_name("tax_report_text", |v: &mut TextView| v.set_content(text));
+    });
+
+    let repo_export = Rc::clone(tax_repo);
+    let income_repo_export = Rc::clone(income_repo);
+    let buttons = LinearLayout::horizontal()
+        .child(Button::new("Export CSV", move |s| {
+            let year = s.call_on_name("tax_year_select", |v: &mut SelectView<i32>| {
+                v.selection().map(|y| *y)
+            }).flatten().unwrap_or(current_year);
+            export_form(s, year, &repo_export, &income_repo_export);
+        }));
+
+    let content = LinearLayout::vertical()
+        .child(LinearLayout::horizontal()
+            .child(TextView::new("Tax Year: "))
+            .child(year_select.with_name("tax_year_select")))
+        .child(buttons)
+        .child(Panel::new(TextView::new(initial_text).with_name("tax_report_text").scrollable()).full_screen());
+
+    let screen = crate::common_layout::create_screen(
+        "Tax Summary",
+        content,
+        &crate::common_layout::view_footer()
+    );
+
+    siv.add_layer(screen);
+}
+
+/// Category totals, the itemized lines behind them, and paystub withholding
+fn format_tax_report(lines: &[TaxLine], withholding: &PaystubLines) -> String {
+    let mut text = String::new();
+
+    if lines.is_empty() {
+        text.push_str("No tax-flagged bills or incomes this year.\nSet a Tax category on bills, ledger bills or incomes to include them.\n");
+    } else {
+        text.push_str(&format!("{:<16}{:<10}{:>8}{:>14}\n", "CATEGORY", "TYPE", "COUNT", "TOTAL"));
+        for (category, kind, total, count) in tax_logic::category_totals(lines) {
+            let total_str = format!("${}", total);
+            text.push_str(&format!("{:<16}{:<10}{:>8}{:>14}\n", category.to_string(), kind.label(), count, total_str));
+        }
+
+        text.push_str(&format!("\n{:<12}{:<16}{:<28}{:>14}\n", "DATE", "CATEGORY", "DESCRIPTION", "AMOUNT"));
+        for line in lines {
+            let amount_str = format!("${}", line.amount);
+            text.push_str(&format!(
+                "{:<12}{:<16}{:<28}{:>14}\n",
+                line.date.format("%d/%m/%Y").to_string(),
+                line.category.to_string(),
+                line.description,
+                amount_str
+            ));
+        }
+    }
+
+    text.push_str("\nPAYSTUB WITHHOLDING\n");
+    let gross_str = format!("${}", withholding.gross);
+    text.push_str(&format!("{:<18}{:>14}\n", "Gross Pay", gross_str));
+    for (label, amount) in withholding.deduction_lines() {
+        let amount_str = format!("${}", amount);
+        text.push_str(&format!("{:<18}{:>14}\n", label, amount_str));
+    }
+
+    text
+}
+
+fn export_form(siv: &mut Cursive, year: i32, tax_repo: &Rc<TaxRepo>, income_repo: &Rc<IncomeRepo>) {
+    let default_path = std::env::var("HOME")
+        .map(|h| format!("{}/money-bae-tax-{}.csv", h, year))
+        .unwrap_or_else(|_| format!("money-bae-tax-{}.csv", year));
+
+    let repo_save = Rc::clone(tax_repo);
+    let income_repo_save = Rc::clone(income_repo);
+    siv.add_layer(
+        Dialog::around(
+            ListView::new()
+                .child("File", EditView::new().content(default_path).with_name("tax_export_path").fixed_width(40))
+        )
+        .title(format!("Export {} Tax Summary", year))
+        .button("Export", move |s| {
+            let path = s.call_on_name("tax_export_path", |v: &mut EditView| {
+                v.get_content()
+            }).unwrap();
+
+            if path.trim().is_empty() {
+                s.add_layer(Dialog::info("File path cannot be empty"));
+                return;
+            }
+
+            let csv = tax_logic::tax_csv(&repo_save.find_tax_lines(year), &withholding_for(year, &income_repo_save));
+            match std::fs::write(path.trim(), csv) {
+                Ok(_) => {
+                    s.pop_layer();
+                    s.add_layer(Dialog::info(format!("Saved {}", path.trim())));
+                }
+                Err(e) => {
+                    log::error!("Failed to export tax summary to {}: {}", path.trim(), e);
+                    s.add_layer(Dialog::info(format!("Could not write file: {}", e)));
+                }
+            }
+        })
+        .button("Cancel", |s| { s.pop_layer(); })
+    );
+}
@@ -0,0 +1,201 @@
+use std::collections::BTreeMap;
+use bigdecimal::{BigDecimal, RoundingMode, ToPrimitive};
+use chrono::{Datelike, NaiveDate};
+
+/// Block characters for a column's partial top cell, in eighths
+const BLOCKS: [char; 9] = [' ', '▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Negative columns hang below the axis in whole cells
+const NEGATIVE_BLOCK: char = '░';
+
+/// One ledger's figures, as stored on the ledger
+#[derive(Debug, Clone)]
+pub struct LedgerFigures {
+    pub date: NaiveDate,
+    pub income: BigDecimal,
+    pub expenses: BigDecimal,
+    pub net: BigDecimal,
+    pub bank_balance: BigDecimal,
+}
+
+/// All ledgers dated in one month: income and expenses summed, balances as of the latest ledger
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonthFigures {
+    pub month: NaiveDate,
+    pub income: BigDecimal,
+    pub expenses: BigDecimal,
+    pub net: BigDecimal,
+    pub bank_balance: BigDecimal,
+    pub ledger_count: usize,
+}
+
+/// Combine ledgers by the month they are dated in, oldest month first
+/// Income and expenses add up; bank balance and net are point-in-time, so the month's latest ledger's are kept
+pub fn monthly_figures(ledgers: &[LedgerFigures]) -> Vec<MonthFigures> {
+    let mut sorted: Vec<&LedgerFigures> = ledgers.iter().collect();
+    sorted.sort_by_key(|ledger| ledger.date);
+
+    let mut months: BTreeMap<NaiveDate, MonthFigures> = BTreeMap::new();
+    for ledger in sorted {
+        let month = ledger.date.with_day(1).unwrap_or(ledger.date);
+        let entry = months.entry(month).or_insert_with(|| MonthFigures {
+            month,
+            income: BigDecimal::from(0),
+            expenses: BigDecimal::from(0),
+            net: BigDecimal::from(0),
+            bank_balance: BigDecimal::from(0),
+            ledger_count: 0,
+        });
+        entry.income += &ledger.income;
+        entry.expenses += &ledger.expenses;
+        entry.net = ledger.net.clone();
+        entry.bank_balance = ledger.bank_balance.clone();
+        entry.ledger_count += 1;
+    }
+    months.into_values().collect()
+}
+
+/// Change from the previous value; the first has nothing to compare against
+pub fn changes(values: &[BigDecimal]) -> Vec<Option<BigDecimal>> {
+    let mut result = Vec::with_capacity(values.len());
+    let mut previous: Option<&BigDecimal> = None;
+    for value in values {
+        result.push(previous.map(|p| value - p));
+        previous = Some(value);
+    }
+    result
+}
+
+/// Mean rounded to cents, None when there is nothing to average
+pub fn average(values: &[BigDecimal]) -> Option<BigDecimal> {
+    if values.is_empty() {
+        return None;
+    }
+    let total: BigDecimal = values.iter().sum();
+    Some((total / BigDecimal::from(values.len() as u32)).with_scale_round(2, RoundingMode::HalfUp))
+}
+
+/// Average month-over-month change, positive when trending up
+pub fn average_change(values: &[BigDecimal]) -> Option<BigDecimal> {
+    let deltas: Vec<BigDecimal> = changes(values).into_iter().flatten().collect();
+    average(&deltas)
+}
+
+/// Rows of a column chart, top row first, one column per value
+/// Positive values rise from the axis in eighth-block steps; negative values hang below an axis line
+pub fn column_chart(values: &[BigDecimal], height: usize, column_width: usize) -> Vec<String> {
+    let amounts: Vec<f64> = values.iter().map(|v| v.to_f64().unwrap_or(0.0)).collect();
+    let max_positive = amounts.iter().fold(0.0_f64, |m, a| m.max(*a));
+    let max_negative = amounts.iter().fold(0.0_f64, |m, a| m.max(-a));
+    let span = max_positive + max_negative;
+    if span == 0.0 || height == 0 {
+        return Vec::new();
+    }
+
+    // Split the rows between the two sides in proportion to their extremes
+    let negative_rows = if max_negative > 0.0 {
+        ((height as f64 * max_negative / span).round() as usize).clamp(1, height)
+    } else {
+        0
+    };
+    let positive_rows = height - negative_rows;
+    let per_row = span / height as f64;
+
+    let mut rows = Vec::with_capacity(height + 1);
+    for row in (0..positive_rows).rev() {
+        let line: String = amounts
+            .iter()
+            .map(|a| {
+                let eighths = if *a > 0.0 { (a / per_row * 8.0).round() as usize } else { 0 };
+                let filled = eighths.saturating_sub(row * 8).min(8);
+                format!("{} ", BLOCKS[filled].to_string().repeat(column_width))
+            })
+            .collect();
+        rows.push(line.trim_end().to_string());
+    }
+
+    if negative_rows > 0 {
+        rows.push("─".repeat((column_width + 1) * amounts.len()));
+        for row in 0..negative_rows {
+            let line: String = amounts
+                .iter()
+                .map(|a| {
+                    let cells = if *a < 0.0 { (-a / per_row).round() as usize } else { 0 };
+                    let cell = if cells > row { NEGATIVE_BLOCK } else { ' ' };
+                    format!("{} ", cell.to_string().repeat(column_width))
+                })
+                .collect();
+            rows.push(line.trim_end().to_string());
+        }
+    }
+
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use super::*;
+
+    fn ledger(year: i32, month: u32, day: u32, income: i32, expenses: i32, bank_balance: i32) -> LedgerFigures {
+        LedgerFigures {
+            date: NaiveDate::from_ymd_opt(year, month, day).unwrap(),
+            income: BigDecimal::from(income),
+            expenses: BigDecimal::from(expenses),
+            net: BigDecimal::from(bank_balance + income - expenses),
+            bank_balance: BigDecimal::from(bank_balance),
+        }
+    }
+
+    #[test]
+    fn test_ledgers_in_same_month_combine() {
+        let ledgers = vec![
+            ledger(2026, 2, 1, 3000, 2000, 500),
+            ledger(2026, 1, 1, 2800, 2100, 400),
+            ledger(2026, 2, 15, 200, 100, 1000),
+        ];
+
+        let months = monthly_figures(&ledgers);
+
+        assert_eq!(months.len(), 2);
+        assert_eq!(months[0].month, NaiveDate::from_ymd_opt(2026, 1, 1).unwrap());
+        assert_eq!(months[1].income, BigDecimal::from(3200));
+        assert_eq!(months[1].expenses, BigDecimal::from(2100));
+        assert_eq!(months[1].net, BigDecimal::from(1100));
+        assert_eq!(months[1].bank_balance, BigDecimal::from(1000));
+        assert_eq!(months[1].ledger_count, 2);
+    }
+
+    #[test]
+    fn test_changes_and_averages() {
+        let values = vec![BigDecimal::from(100), BigDecimal::from(150), BigDecimal::from(120)];
+
+        assert_eq!(changes(&values), vec![None, Some(BigDecimal::from(50)), Some(BigDecimal::from(-30))]);
+        assert_eq!(average(&values), Some(BigDecimal::from_str("123.33").unwrap()));
+        assert_eq!(average_change(&values), Some(BigDecimal::from(10)));
+        assert_eq!(average(&[]), None);
+    }
+
+    #[test]
+    fn test_column_chart_uses_partial_blocks() {
+        let values = vec![BigDecimal::from(8), BigDecimal::from(3), BigDecimal::from(0)];
+
+        let rows = column_chart(&values, 2, 1);
+
+        assert_eq!(rows, vec!["█".to_string(), "█ ▆".to_string()]);
+    }
+
+    #[test]
+    fn test_column_chart_hangs_negatives_below_axis() {
+        let values = vec![BigDecimal::from(4), BigDecimal::from(-4)];
+
+        let rows = column_chart(&values, 2, 1);
+
+        assert_eq!(rows, vec!["█".to_string(), "────".to_string(), "  ░".to_string()]);
+    }
+
+    #[test]
+    fn test_column_chart_empty_when_all_zero() {
+        assert!(column_chart(&[BigDecimal::from(0)], 4, 2).is_empty());
+    }
+}
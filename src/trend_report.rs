@@ -0,0 +1,181 @@
+use std::rc::Rc;
+use bigdecimal::BigDecimal;
+use chrono::{Datelike, Local, Months, NaiveDate};
+use cursive::Cursive;
+use cursive::traits::*;
+use cursive::views::{Button, Dialog, EditView, LinearLayout, Panel, TextView};
+
+use crate::repositories::LedgerRepo;
+use crate::trend_logic::{self, LedgerFigures, MonthFigures};
+
+/// Months shown when the report first opens, including the current one
+const DEFAULT_TREND_MONTHS: u32 = 12;
+
+/// Rows per chart
+const CHART_HEIGHT: usize = 8;
+
+/// Characters per month column; wide enough for a month abbreviation
+const CHART_COLUMN_WIDTH: usize = 3;
+
+fn load_month_figures(ledger_repo: &LedgerRepo, from: NaiveDate, through: NaiveDate) -> Vec<MonthFigures> {
+    let ledgers: Vec<LedgerFigures> = ledger_repo
+        .find_between(from, through)
+        .into_iter()
+        .map(|l| LedgerFigures {
+            date: l.date,
+            net: l.net.unwrap_or_else(|| &l.bank_balance + &l.income - &l.expenses),
+            income: l.income,
+            expenses: l.expenses,
+            bank_balance: l.bank_balance,
+        })
+        .collect();
+    trend_logic::monthly_figures(&ledgers)
+}
+
+pub fn show_trend_report(siv: &mut Cursive, ledger_repo: &Rc<LedgerRepo>) {
+    siv.pop_layer();
+
+    let today = Local::now().date_naive();
+    let from = today
+        .with_day(1)
+        .and_then(|d| d.checked_sub_months(Months::new(DEFAULT_TREND_MONTHS - 1)))
+        .unwrap_or(today);
+    let initial_text = format_trend_report(&load_month_figures(ledger_repo, from, today));
+
+    let repo_apply = Rc::clone(ledger_repo);
+    let range = LinearLayout::horizontal()
+        .child(TextView::new("From (DD/MM/YYYY): "))
+        .child(EditView::new().content(from.format("%d/%m/%Y").to_string()).with_name("trend_from").fixed_width(12))
+        .child(TextView::new("  To: "))
+        .child(EditView::new().content(today.format("%d/%m/%Y").to_string()).with_name("trend_to").fixed_width(12))
+        .child(Button::new("Apply", move |s| {
+            let from_str = s.call_on_name("trend_from", |v: &mut EditView| v.get_content()).unwrap();
+            let to_str = s.call_on_name("trend_to", |v: &mut EditView| v.get_content()).unwrap();
+
+            let (Ok(from), Ok(through)) = (
+                NaiveDate::parse_from_str(&from_str, "%d/%m/%Y"),
+                NaiveDate::parse_from_str(&to_str, "%d/%m/%Y"),
+            ) else {
+                s.add_layer(Dialog::info("Invalid date format. Use DD/MM/YYYY"));
+                return;
+            };
+            if from > through {
+                s.add_layer(Dialog::info("From date must be on or before the To date"));
+                return;
+            }
+
+            let text = format_trend_report(&load_month_figures(&repo_apply, from, through));
+            s.call_on_name("trend_report_text", |v: &mut TextView| v.set_content(text));
+        }));
+
+    let content = LinearLayout::vertical()
+        .child(range)
+        .child(Panel::new(TextView::new(initial_text).with_name("trend_report_text").scrollable()).full_screen());
+
+    let screen = crate::common_layout::create_screen(
+        "Trends",
+        content,
+        &crate::common_layout::view_footer()
+    );
+
+    siv.add_layer(screen);
+}
+
+/// An amount with its sign spelled out, e.g. +$25 or -$10
+fn signed(amount: &BigDecimal) -> String {
+    let zero = BigDecimal::from(0);
+    if *amount < zero {
+        format!("-${}", amount.abs())
+    } else {
+        format!("+${}", amount)
+    }
+}
+
+/// A titled column chart with month labels underneath; years are marked on the first month and each January
+fn format_chart(title: &str, months: &[MonthFigures], values: &[BigDecimal]) -> String {
+    let mut text = title.to_string();
+    if let Some(avg) = trend_logic::average(values) {
+        text.push_str(&format!("  (average ${})", avg));
+    }
+    text.push('\n');
+
+    let rows = trend_logic::column_chart(values, CHART_HEIGHT, CHART_COLUMN_WIDTH);
+    if rows.is_empty() {
+        text.push_str("  (all zero)\n\n");
+        return text;
+    }
+    for row in rows {
+        text.push_str(&format!("  {}\n", row));
+    }
+
+    let months_row: String = months
+        .iter()
+        .map(|m| format!("{:<width$} ", m.month.format("%b").to_string(), width = CHART_COLUMN_WIDTH))
+        .collect();
+    let years_row: String = months
+        .iter()
+        .enumerate()
+        .map(|(i, m)| {
+            let label = if i == 0 || m.month.month() == 1 { m.month.format("'%y").to_string() } else { String::new() };
+            format!("{:<width$} ", label, width = CHART_COLUMN_WIDTH)
+        })
+        .collect();
+    text.push_str(&format!("  {}\n  {}\n\n", months_row.trim_end(), years_row.trim_end()));
+    text
+}
+
+/// Charts for each figure, then month-over-month changes with averages
+fn format_trend_report(months: &[MonthFigures]) -> String {
+    if months.is_empty() {
+        return "No ledgers in this range.".to_string();
+    }
+
+    let income: Vec<BigDecimal> = months.iter().map(|m| m.income.clone()).collect();
+    let expenses: Vec<BigDecimal> = months.iter().map(|m| m.expenses.clone()).collect();
+    let net: Vec<BigDecimal> = months.iter().map(|m| m.net.clone()).collect();
+    let balance: Vec<BigDecimal> = months.iter().map(|m| m.bank_balance.clone()).collect();
+
+    let mut text = String::new();
+    text.push_str(&format_chart("INCOME", months, &income));
+    text.push_str(&format_chart("EXPENSES", months, &expenses));
+    text.push_str(&format_chart("NET", months, &net));
+    text.push_str(&format_chart("BANK BALANCE", months, &balance));
+
+    let series = [&income, &expenses, &net, &balance];
+    let series_changes: Vec<Vec<Option<BigDecimal>>> = series.iter().map(|values| trend_logic::changes(values.as_slice())).collect();
+
+    text.push_str(&format!(
+        "{:<10}{:>12}{:>12}{:>12}{:>12}{:>12}{:>12}{:>12}{:>12}\n",
+        "MONTH", "INCOME", "CHANGE", "EXPENSES", "CHANGE", "NET", "CHANGE", "BALANCE", "CHANGE"
+    ));
+    for (index, month) in months.iter().enumerate() {
+        let mut line = format!("{:<10}", month.month.format("%m/%Y").to_string());
+        for (values, deltas) in series.iter().zip(&series_changes) {
+            let value_str = format!("${}", values[index]);
+            let change_str = deltas[index].as_ref().map_or(String::new(), signed);
+            line.push_str(&format!("{:>12}{:>12}", value_str, change_str));
+        }
+        text.push_str(&line);
+        text.push('\n');
+    }
+
+    let mut average_line = format!("{:<10}", "AVERAGE");
+    for values in series {
+        let average_str = trend_logic::average(values).map_or(String::new(), |a| format!("${}", a));
+        let change_str = trend_logic::average_change(values).as_ref().map_or(String::new(), signed);
+        average_line.push_str(&format!("{:>12}{:>12}", average_str, change_str));
+    }
+    text.push_str("──────────────────────────────────────────────────────────────────────────────────────────────────────\n");
+    text.push_str(&average_line);
+    text.push('\n');
+
+    let zero = BigDecimal::from(0);
+    for (label, values) in [("Net", &net), ("Bank balance", &balance)] {
+        if let Some(change) = trend_logic::average_change(values) {
+            let direction = if change > zero { "up" } else if change < zero { "down" } else { "flat" };
+            text.push_str(&format!("\n{} is trending {} ({} per month on average)", label, direction, signed(&change)));
+        }
+    }
+
+    text
+}
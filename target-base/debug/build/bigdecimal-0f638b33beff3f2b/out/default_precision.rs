@@ -0,0 +1 @@
+const DEFAULT_PRECISION: u64 = 100;
\ No newline at end of file
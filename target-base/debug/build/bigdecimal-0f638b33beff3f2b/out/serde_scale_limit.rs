@@ -0,0 +1 @@
+const SERDE_SCALE_LIMIT: i64 = 150000;
\ No newline at end of file